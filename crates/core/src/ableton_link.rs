@@ -14,6 +14,11 @@ pub struct AbletonLinkManager {
     session_state: SessionState,
     is_enabled: bool,
     num_peers: u64,
+    // Beats per bar used for phase, Link's quantum
+    quantum: f64,
+    start_stop_sync: bool,
+    tempo: f64,
+    phase: f64,
 }
 
 impl AbletonLinkManager {
@@ -23,6 +28,10 @@ impl AbletonLinkManager {
             session_state: SessionState::new(),
             is_enabled: false,
             num_peers: 0,
+            quantum: 4.0,
+            start_stop_sync: true,
+            tempo: 120.0,
+            phase: 0.0,
         }
     }
 
@@ -32,7 +41,9 @@ impl AbletonLinkManager {
         }
 
         // AblLink::new() doesn't return a Result, it just takes a BPM parameter
-        let link = AblLink::new(120.0);
+        let link = AblLink::new(self.tempo);
+        link.enable(true);
+        link.enable_start_stop_sync(self.start_stop_sync);
         let link_arc = Arc::new(Mutex::new(link));
         self.link = Some(link_arc);
         self.is_enabled = true;
@@ -55,6 +66,28 @@ impl AbletonLinkManager {
         self.num_peers
     }
 
+    pub fn tempo(&self) -> f64 {
+        self.tempo
+    }
+
+    /// Position within the current bar, from 0 up to the quantum
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
+    pub fn quantum(&self) -> f64 {
+        self.quantum
+    }
+
+    pub fn set_quantum(&mut self, quantum: f64) {
+        self.quantum = quantum.clamp(1.0, 16.0);
+        log::info!("Set Ableton Link quantum to {}", self.quantum);
+    }
+
+    pub fn start_stop_sync(&self) -> bool {
+        self.start_stop_sync
+    }
+
     pub async fn update(&mut self) -> Option<(f64, f64)> {
         if !self.is_enabled {
             return None;
@@ -72,7 +105,9 @@ impl AbletonLinkManager {
             // Get tempo and beat time
             let tempo = self.session_state.tempo();
             let clock_micros = link.clock_micros();
-            let beat_time = self.session_state.beat_at_time(clock_micros, 4.0); // 4/4 time signature
+            let beat_time = self.session_state.beat_at_time(clock_micros, self.quantum);
+            self.tempo = tempo;
+            self.phase = self.session_state.phase_at_time(clock_micros, self.quantum);
 
             // Update the session state with our current state
             link.commit_app_session_state(&self.session_state);
@@ -100,9 +135,11 @@ impl AbletonLinkManager {
         }
     }
 
+    /// Follow start/stop from other Link peers, remembered until Link is next enabled
     pub async fn enable_start_stop_sync(&mut self, enable: bool) -> Result<(), String> {
+        self.start_stop_sync = enable;
         if !self.is_enabled {
-            return Err("Ableton Link is not enabled".to_string());
+            return Ok(());
         }
 
         if let Some(link_arc) = &self.link {
@@ -118,6 +155,25 @@ impl AbletonLinkManager {
         }
     }
 
+    /// Make now the first beat of the bar for the whole Link session
+    pub async fn realign_downbeat(&mut self) -> Result<(), String> {
+        if !self.is_enabled {
+            return Err("Ableton Link is not enabled".to_string());
+        }
+
+        if let Some(link_arc) = &self.link {
+            let link = link_arc.lock().await;
+            let clock_micros = link.clock_micros();
+            self.session_state
+                .force_beat_at_time(0.0, clock_micros as _, self.quantum);
+            link.commit_app_session_state(&self.session_state);
+            log::info!("Re-aligned Ableton Link downbeat");
+            Ok(())
+        } else {
+            Err("Link not initialized".to_string())
+        }
+    }
+
     pub async fn is_playing(&self) -> bool {
         if let Some(link_arc) = &self.link {
             let link = link_arc.lock().await;
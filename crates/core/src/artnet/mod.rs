@@ -1,2 +1,3 @@
 pub mod artnet;
 pub mod network_config;
+pub mod network_status;
@@ -114,7 +114,7 @@ impl NetworkConfig {
         }
     }
 
-    fn get_destination_string(&self, mode: &ArtNetMode) -> String {
+    pub fn get_destination_string(&self, mode: &ArtNetMode) -> String {
         match mode {
             ArtNetMode::Unicast(src, destination) => {
                 format!(
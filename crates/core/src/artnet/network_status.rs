@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddrV4, UdpSocket};
+use std::time::Instant;
+
+use artnet_protocol::{ArtCommand, Poll};
+use socket2::{Domain, Protocol, Socket, Type};
+
+const ARTNET_PORT: u16 = 6454;
+// Nodes that haven't answered a few polls in a row are dropped from the list
+const NODE_TIMEOUT_SECS: u64 = 10;
+
+/// Snapshot of Art-Net output health, reported by the DMX module about once a second
+#[derive(Clone, Debug, Default)]
+pub struct NetworkStatus {
+    pub destinations: Vec<DestinationStatus>,
+    pub universes: Vec<UniverseStatus>,
+    pub nodes: Vec<ArtNetNode>,
+    /// DMX frames actually sent per second
+    pub output_fps: f32,
+    /// Why node discovery couldn't start, e.g. another app owns port 6454 exclusively
+    pub discovery_error: Option<String>,
+    pub socket_errors: u64,
+    /// Most recent send errors, oldest first
+    pub recent_errors: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DestinationStatus {
+    pub name: String,
+    pub address: String,
+    pub universes: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct UniverseStatus {
+    pub universe: u8,
+    /// None when the universe has no routing and isn't being sent anywhere
+    pub destination: Option<String>,
+    pub packets_per_sec: f32,
+}
+
+/// A node that answered an ArtPoll
+#[derive(Clone, Debug)]
+pub struct ArtNetNode {
+    pub address: Ipv4Addr,
+    pub short_name: String,
+    pub long_name: String,
+    pub report: String,
+    pub last_seen: Instant,
+}
+
+/// Finds Art-Net nodes by broadcasting ArtPoll and collecting the replies
+///
+/// Replies come back to port 6454, which other Art-Net software on the machine may also be
+/// listening on, so the port is bound shared and only while discovery is running.
+pub struct ArtNetDiscovery {
+    socket: UdpSocket,
+    nodes: HashMap<Ipv4Addr, ArtNetNode>,
+}
+
+impl ArtNetDiscovery {
+    pub fn new() -> Result<Self, anyhow::Error> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        #[cfg(unix)]
+        socket.set_reuse_port(true)?;
+        socket.bind(&SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, ARTNET_PORT).into())?;
+        socket.set_broadcast(true)?;
+        socket.set_nonblocking(true)?;
+        Ok(ArtNetDiscovery {
+            socket: socket.into(),
+            nodes: HashMap::new(),
+        })
+    }
+
+    pub fn poll(&self) -> Result<(), anyhow::Error> {
+        let bytes = ArtCommand::Poll(Poll::default()).write_to_buffer()?;
+        self.socket
+            .send_to(&bytes, (Ipv4Addr::BROADCAST, ARTNET_PORT))?;
+        Ok(())
+    }
+
+    /// Read any waiting replies and return the nodes heard from recently
+    pub fn receive(&mut self) -> Vec<ArtNetNode> {
+        let mut buffer = [0u8; 1024];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((len, _)) => {
+                    // Everything else on the port (including our own broadcast output) is ignored
+                    if let Ok(ArtCommand::PollReply(reply)) =
+                        ArtCommand::from_buffer(&buffer[..len])
+                    {
+                        let node = ArtNetNode {
+                            address: reply.address,
+                            short_name: null_terminated(&reply.short_name),
+                            long_name: null_terminated(&reply.long_name),
+                            report: null_terminated(&reply.node_report),
+                            last_seen: Instant::now(),
+                        };
+                        self.nodes.insert(node.address, node);
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    log::debug!("Art-Net discovery receive failed: {e}");
+                    break;
+                }
+            }
+        }
+
+        self.nodes
+            .retain(|_, node| node.last_seen.elapsed().as_secs() < NODE_TIMEOUT_SECS);
+        let mut nodes: Vec<ArtNetNode> = self.nodes.values().cloned().collect();
+        nodes.sort_by_key(|node| node.address);
+        nodes
+    }
+}
+
+fn null_terminated(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
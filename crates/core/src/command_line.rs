@@ -0,0 +1,366 @@
+use crate::{ConsoleCommand, CueList, FixtureGroup};
+
+// Longest fade the command line accepts, an hour
+const MAX_FADE_TIME_SECS: f64 = 3600.0;
+
+/// Console state the command line needs to turn syntax into commands
+pub struct CommandLineContext<'a> {
+    pub current_cue_list_index: usize,
+    pub selected_fixtures: &'a [usize],
+    pub fixture_groups: &'a [FixtureGroup],
+    pub cue_lists: &'a [CueList],
+}
+
+/// Parse one line of command line input into console commands
+///
+/// Console syntax selects fixtures and sets intensity, e.g. `1 thru 5 - 3 @ 50`, `group 2 @ full`
+/// or `@ out` for the current selection. `go`, `stop`, `clear`, `undo` and `cue 5` work as
+/// words, and `cue 5 time 2.5` sets a cue's fade time in seconds. Slash commands cover everything
+/// else: `/goto cue 5`, `/patch <profile> <universe> <address> [name]`, `/unpatch 3` and `/group
+/// <name>`.
+pub fn parse_command_line(
+    input: &str,
+    context: &CommandLineContext,
+) -> Result<Vec<ConsoleCommand>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+    if let Some(slash) = input.strip_prefix('/') {
+        return parse_slash_command(slash, context);
+    }
+
+    let tokens = tokenize(input);
+    let words: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    match words.as_slice() {
+        ["go"] => return Ok(vec![ConsoleCommand::Play]),
+        ["stop"] => return Ok(vec![ConsoleCommand::Stop]),
+        ["clear"] => return Ok(vec![ConsoleCommand::ClearProgrammer]),
+        ["undo"] => return Ok(vec![ConsoleCommand::UndoProgrammer]),
+        ["cue", number] => return go_to_cue(number, context),
+        ["cue", number, "time", seconds] => return set_cue_fade_time(number, seconds, context),
+        _ => {}
+    }
+
+    let (selection_words, level_words) = match words.iter().position(|w| *w == "@") {
+        Some(at) => (&words[..at], Some(&words[at + 1..])),
+        None => (&words[..], None),
+    };
+
+    let mut commands = Vec::new();
+    let fixture_ids = if selection_words.is_empty() {
+        context.selected_fixtures.to_vec()
+    } else {
+        let fixture_ids = parse_selection(selection_words, context)?;
+        commands.push(ConsoleCommand::SetSelectedFixtures {
+            fixture_ids: fixture_ids.clone(),
+        });
+        fixture_ids
+    };
+
+    if let Some(level_words) = level_words {
+        let value = parse_level(level_words)?;
+        if fixture_ids.is_empty() {
+            return Err("No fixtures selected".to_string());
+        }
+        commands.extend(fixture_ids.into_iter().map(|fixture_id| {
+            ConsoleCommand::SetProgrammerValue {
+                fixture_id,
+                channel: "dimmer".to_string(),
+                value,
+            }
+        }));
+    }
+
+    Ok(commands)
+}
+
+fn parse_slash_command(
+    input: &str,
+    context: &CommandLineContext,
+) -> Result<Vec<ConsoleCommand>, String> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        ["goto", "cue", number] | ["goto", number] => go_to_cue(number, context),
+        ["go"] => Ok(vec![ConsoleCommand::Play]),
+        ["stop"] => Ok(vec![ConsoleCommand::Stop]),
+        ["clear"] => Ok(vec![ConsoleCommand::ClearProgrammer]),
+        ["undo"] => Ok(vec![ConsoleCommand::UndoProgrammer]),
+        ["patch", profile_name, universe, address, name @ ..] => {
+            let universe = parse_number(universe, "universe")?;
+            let address = parse_number(address, "address")?;
+            if !(1..=512).contains(&address) {
+                return Err(format!("Address {address} is outside 1-512"));
+            }
+            let name = if name.is_empty() {
+                format!("{profile_name} {universe}.{address}")
+            } else {
+                name.join(" ")
+            };
+            Ok(vec![ConsoleCommand::PatchFixture {
+                name,
+                profile_name: profile_name.to_string(),
+                universe,
+                address,
+            }])
+        }
+        ["unpatch", fixture_id] => Ok(vec![ConsoleCommand::UnpatchFixture {
+            fixture_id: parse_number(fixture_id, "fixture")?,
+        }]),
+        ["group", name @ ..] if !name.is_empty() => {
+            if context.selected_fixtures.is_empty() {
+                return Err("No fixtures selected".to_string());
+            }
+            Ok(vec![ConsoleCommand::StoreFixtureGroup {
+                name: name.join(" "),
+                fixture_ids: context.selected_fixtures.to_vec(),
+            }])
+        }
+        [command, ..] => Err(format!("Unknown command /{command}")),
+        [] => Err("Missing command after /".to_string()),
+    }
+}
+
+fn go_to_cue(number: &str, context: &CommandLineContext) -> Result<Vec<ConsoleCommand>, String> {
+    let number: usize = parse_number(number, "cue")?;
+    if number == 0 {
+        return Err("Cues are numbered from 1".to_string());
+    }
+    Ok(vec![ConsoleCommand::GoToCue {
+        list_index: context.current_cue_list_index,
+        cue_index: number - 1,
+    }])
+}
+
+fn set_cue_fade_time(
+    number: &str,
+    seconds: &str,
+    context: &CommandLineContext,
+) -> Result<Vec<ConsoleCommand>, String> {
+    let number: usize = parse_number(number, "cue")?;
+    let fade_time: f64 = parse_number(seconds, "fade time")?;
+    // Also rejects NaN, which would panic when the fade becomes a Duration
+    if !(0.0..=MAX_FADE_TIME_SECS).contains(&fade_time) {
+        return Err(format!(
+            "Fade times must be between 0 and {MAX_FADE_TIME_SECS} seconds"
+        ));
+    }
+    let list_index = context.current_cue_list_index;
+    let cue = number
+        .checked_sub(1)
+        .and_then(|index| context.cue_lists.get(list_index)?.cues.get(index))
+        .ok_or(format!("Cue {number} not found"))?;
+    Ok(vec![ConsoleCommand::UpdateCue {
+        list_index,
+        cue_index: number - 1,
+        name: cue.name.clone(),
+        fade_time,
+        timecode: cue.timecode.clone(),
+        is_blocking: cue.is_blocking,
+    }])
+}
+
+/// Split into words, keeping `@`, `+` and `-` as their own tokens even without spaces
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in input.chars() {
+        if c.is_whitespace() || matches!(c, '@' | '+' | '-') {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current).to_lowercase());
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current.to_lowercase());
+    }
+    tokens
+}
+
+/// Fixture ids in selection order, e.g. `1 thru 5 - 3 + group 2`
+fn parse_selection(words: &[&str], context: &CommandLineContext) -> Result<Vec<usize>, String> {
+    let mut selection: Vec<usize> = Vec::new();
+    let mut removing = false;
+    let mut index = 0;
+
+    while index < words.len() {
+        let ids: Vec<usize> = match words[index] {
+            "+" => {
+                removing = false;
+                index += 1;
+                continue;
+            }
+            "-" => {
+                removing = true;
+                index += 1;
+                continue;
+            }
+            "group" | "g" => {
+                let group_word = words.get(index + 1).ok_or("Missing group number")?;
+                let group_id: usize = parse_number(group_word, "group")?;
+                index += 2;
+                context
+                    .fixture_groups
+                    .iter()
+                    .find(|g| g.id == group_id)
+                    .map(|g| g.fixture_ids.clone())
+                    .ok_or(format!("Group {group_id} not found"))?
+            }
+            word => {
+                let start: usize = parse_number(word, "fixture")?;
+                if matches!(words.get(index + 1), Some(&"thru") | Some(&"t")) {
+                    let end_word = words.get(index + 2).ok_or("Missing end of range")?;
+                    let end: usize = parse_number(end_word, "fixture")?;
+                    index += 3;
+                    if start <= end {
+                        (start..=end).collect()
+                    } else {
+                        (end..=start).rev().collect()
+                    }
+                } else {
+                    index += 1;
+                    vec![start]
+                }
+            }
+        };
+
+        if removing {
+            selection.retain(|id| !ids.contains(id));
+        } else {
+            for id in ids {
+                if !selection.contains(&id) {
+                    selection.push(id);
+                }
+            }
+        }
+    }
+
+    Ok(selection)
+}
+
+/// An intensity level in percent, or `full` / `out`, as a DMX value
+fn parse_level(words: &[&str]) -> Result<u8, String> {
+    match words {
+        ["full"] => Ok(255),
+        ["out"] => Ok(0),
+        [percent] => {
+            let percent: f32 = parse_number(percent, "level")?;
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(format!("Level {percent} is outside 0-100"));
+            }
+            Ok((percent / 100.0 * 255.0).round() as u8)
+        }
+        [] => Err("Missing level after @".to_string()),
+        _ => Err("Expected a single level after @".to_string()),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(word: &str, what: &str) -> Result<T, String> {
+    word.parse()
+        .map_err(|_| format!("Expected a {what} number, found '{word}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(selected: &'a [usize], groups: &'a [FixtureGroup]) -> CommandLineContext<'a> {
+        CommandLineContext {
+            current_cue_list_index: 2,
+            selected_fixtures: selected,
+            fixture_groups: groups,
+            cue_lists: &[],
+        }
+    }
+
+    fn selection(commands: &[ConsoleCommand]) -> Vec<usize> {
+        match commands.first() {
+            Some(ConsoleCommand::SetSelectedFixtures { fixture_ids }) => fixture_ids.clone(),
+            _ => panic!("expected a selection"),
+        }
+    }
+
+    #[test]
+    fn test_selection_ranges_and_groups() {
+        let groups = vec![FixtureGroup::new(2, "Wash".to_string(), vec![8, 9])];
+        let commands = parse_command_line("1 thru 5 - 3 + g 2", &context(&[], &groups)).unwrap();
+        assert_eq!(selection(&commands), vec![1, 2, 4, 5, 8, 9]);
+
+        let commands = parse_command_line("4 t 2", &context(&[], &groups)).unwrap();
+        assert_eq!(selection(&commands), vec![4, 3, 2]);
+
+        assert!(parse_command_line("group 7", &context(&[], &groups)).is_err());
+    }
+
+    #[test]
+    fn test_level_applies_to_current_selection() {
+        let commands = parse_command_line("@ 50", &context(&[3, 4], &[])).unwrap();
+        assert_eq!(commands.len(), 2);
+        assert!(matches!(
+            commands[0],
+            ConsoleCommand::SetProgrammerValue {
+                fixture_id: 3,
+                value: 128,
+                ..
+            }
+        ));
+
+        assert!(parse_command_line("@ full", &context(&[], &[])).is_err());
+        assert!(parse_command_line("1 @ 150", &context(&[], &[])).is_err());
+    }
+
+    #[test]
+    fn test_slash_commands() {
+        let commands = parse_command_line("/goto cue 5", &context(&[], &[])).unwrap();
+        assert!(matches!(
+            commands[0],
+            ConsoleCommand::GoToCue {
+                list_index: 2,
+                cue_index: 4
+            }
+        ));
+
+        let commands =
+            parse_command_line("/patch generic-rgb 1 17 Front Wash", &context(&[], &[])).unwrap();
+        assert!(matches!(
+            &commands[0],
+            ConsoleCommand::PatchFixture { name, address: 17, .. } if name == "Front Wash"
+        ));
+
+        assert!(parse_command_line("/dance", &context(&[], &[])).is_err());
+    }
+
+    #[test]
+    fn test_cue_fade_time() {
+        let cue_lists = vec![CueList {
+            name: "Main".to_string(),
+            cues: vec![crate::Cue {
+                name: "Opener".to_string(),
+                ..Default::default()
+            }],
+            audio_file: None,
+        }];
+        let context = CommandLineContext {
+            current_cue_list_index: 0,
+            selected_fixtures: &[],
+            fixture_groups: &[],
+            cue_lists: &cue_lists,
+        };
+
+        let commands = parse_command_line("cue 1 time 2.5", &context).unwrap();
+        assert!(matches!(
+            &commands[0],
+            ConsoleCommand::UpdateCue { cue_index: 0, fade_time, name, .. }
+                if *fade_time == 2.5 && name == "Opener"
+        ));
+        assert!(parse_command_line("cue 2 time 1", &context).is_err());
+        for seconds in ["-1", "nan", "inf", "1e30"] {
+            assert!(parse_command_line(&format!("cue 1 time {seconds}"), &context).is_err());
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use halo_fixtures::{Fixture, FixtureLibrary};
+use halo_fixtures::{ChannelType, Fixture, FixtureLibrary};
 use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::task::JoinHandle;
 
@@ -10,29 +10,53 @@ use crate::artnet::network_config::NetworkConfig;
 use crate::audio::device_enumerator;
 use crate::cue::cue::Cue;
 use crate::cue::cue_manager::{CueManager, PlaybackState};
-use crate::messages::{ConsoleCommand, ConsoleEvent, Settings};
+use crate::messages::{ConsoleCommand, ConsoleEvent, ProgrammerEffectSummary, Settings};
+use crate::midi::mapping::{
+    learn_mapping, MidiControl, MidiFeedbackState, MidiMapping, MidiMappingAction,
+    MidiMappingContext,
+};
 use crate::midi::midi::{MidiMessage, MidiOverride};
+use crate::midi::profile::{MidiProfile, MIDI_PROFILE_DIR};
 use crate::modules::{
     AudioModule, DmxModule, MidiModule, ModuleEvent, ModuleId, ModuleManager, ModuleMessage,
     SmpteModule,
 };
+use crate::performance::PerformanceTracker;
 use crate::pixel::PixelEngine;
+use crate::preset::preset::{EffectPreset, EffectPresetType, Preset, PresetType};
+use crate::preset::preset_library::PresetLibrary;
 use crate::programmer::Programmer;
 use crate::rhythm::rhythm::RhythmState;
 use crate::show::show_manager::ShowManager;
 use crate::timecode::timecode::TimeCode;
 use crate::tracking_state::TrackingState;
-use crate::{AbletonLinkManager, CueList};
+use crate::{
+    AbletonLinkManager, ColorSwatch, CueList, CueResolver, FixtureGridLayout, FixtureGroup,
+    MagicSheet, PresetReference, QuickSelect, StaticValue,
+};
 
 pub struct LightingConsole {
     // Core components
     show_name: String,
+    // Whether show data has been edited since the last save or load
+    show_dirty: bool,
     tempo: f64,
     fixture_library: FixtureLibrary,
     pub fixtures: Arc<RwLock<Vec<Fixture>>>,
     pub cue_manager: Arc<RwLock<CueManager>>,
     pub programmer: Arc<RwLock<Programmer>>,
     pub show_manager: Arc<RwLock<ShowManager>>,
+    preset_library: PresetLibrary,
+    fixture_groups: Vec<FixtureGroup>,
+    quick_selects: Vec<QuickSelect>,
+    color_swatches: Vec<ColorSwatch>,
+    fixture_grid: FixtureGridLayout,
+    magic_sheet: MagicSheet,
+    // Output overrides from the magic sheet's flash and smoke buttons
+    flashed_fixtures: HashSet<usize>,
+    smoke_active: bool,
+    // Last changed-vs-tracked set sent to the UI
+    programmer_changed: Vec<(usize, String)>,
 
     // Async module system
     module_manager: ModuleManager,
@@ -42,10 +66,26 @@ pub struct LightingConsole {
     // MIDI overrides
     midi_overrides: HashMap<u8, MidiOverride>,
     active_overrides: HashMap<u8, (bool, u8)>,
+    // Action waiting for a control to be moved while MIDI learn is armed
+    midi_learn: Option<MidiMappingAction>,
+    // Controller profiles, the one named in the settings maps controls that weren't learned
+    midi_profiles: Vec<MidiProfile>,
+    // Last value sent to each feedback control, so only changes go out
+    midi_feedback_sent: HashMap<MidiControl, u8>,
 
     // Rhythm state
     rhythm_state: Arc<RwLock<RhythmState>>,
 
+    // Global effect masters (1.0 = 100%, range 0.0 to 2.0)
+    fx_rate_master: f64,
+    fx_size_master: f64,
+    // Effect clock, advanced by the rhythm clock scaled by the FX rate master
+    fx_beats: f64,
+    // Beat clock at the last effect clock update, None until the first update and whenever the
+    // beat clock switches between Link and the internal tempo
+    fx_source_beats: Option<f64>,
+    fx_rhythm_state: RhythmState,
+
     // Ableton Link integration
     link_manager: Arc<Mutex<AbletonLinkManager>>,
 
@@ -85,25 +125,41 @@ impl LightingConsole {
 
         // Only register MIDI module if enabled and device is not "None"
         if settings.midi_enabled && settings.midi_device != "None" {
-            module_manager.register_module(Box::new(MidiModule::new(settings.midi_device.clone())));
+            module_manager.register_module(Box::new(
+                MidiModule::new(settings.midi_device.clone()).with_rtp_port(settings.rtp_midi_port),
+            ));
         }
 
         let show_manager = ShowManager::new()?;
 
         Ok(Self {
             show_name: "Untitled Show".to_string(),
+            show_dirty: false,
             tempo: bpm,
             fixture_library: FixtureLibrary::new(),
             fixtures: Arc::new(RwLock::new(Vec::new())),
             cue_manager: Arc::new(RwLock::new(CueManager::new(Vec::new()))),
             programmer: Arc::new(RwLock::new(Programmer::new())),
             show_manager: Arc::new(RwLock::new(show_manager)),
+            preset_library: PresetLibrary::new(),
+            fixture_groups: Vec::new(),
+            quick_selects: Vec::new(),
+            color_swatches: Vec::new(),
+            fixture_grid: FixtureGridLayout::default(),
+            magic_sheet: MagicSheet::default(),
+            flashed_fixtures: HashSet::new(),
+            smoke_active: false,
+            programmer_changed: Vec::new(),
             module_manager,
             message_handler: None,
             message_rx: None,
             midi_overrides: HashMap::new(),
             active_overrides: HashMap::new(),
+            midi_learn: None,
+            midi_profiles: MidiProfile::load_all(std::path::Path::new(MIDI_PROFILE_DIR)),
+            midi_feedback_sent: HashMap::new(),
             rhythm_state: Arc::new(RwLock::new(RhythmState {
+                beat_time: 0.0,
                 beat_phase: 0.0,
                 bar_phase: 0.0,
                 phrase_phase: 0.0,
@@ -112,6 +168,20 @@ impl LightingConsole {
                 last_tap_time: None,
                 tap_count: 0,
             })),
+            fx_rate_master: 1.0,
+            fx_size_master: 1.0,
+            fx_beats: 0.0,
+            fx_source_beats: None,
+            fx_rhythm_state: RhythmState {
+                beat_time: 0.0,
+                beat_phase: 0.0,
+                bar_phase: 0.0,
+                phrase_phase: 0.0,
+                beats_per_bar: 4,
+                bars_per_phrase: 4,
+                last_tap_time: None,
+                tap_count: 0,
+            },
             link_manager: Arc::new(Mutex::new(AbletonLinkManager::new())),
             settings: Arc::new(RwLock::new(settings)),
             pixel_engine: Arc::new(RwLock::new(PixelEngine::new())),
@@ -148,11 +218,8 @@ impl LightingConsole {
         Ok(())
     }
 
-    async fn handle_midi_input(
-        midi_msg: MidiMessage,
-        _rhythm_state: &Arc<RwLock<RhythmState>>,
-        cue_manager: &Arc<RwLock<CueManager>>,
-    ) {
+    /// Log MIDI input that no mapping or override used
+    fn handle_midi_input(midi_msg: MidiMessage) {
         match midi_msg {
             MidiMessage::Clock => {
                 // Handle MIDI clock for tempo sync
@@ -168,24 +235,6 @@ impl LightingConsole {
             }
             MidiMessage::ControlChange(cc, value) => {
                 log::info!("MIDI CC: {} value: {}", cc, value);
-
-                // Handle specific control changes
-                match cc {
-                    116 if value > 64 => {
-                        // Go button
-                        let mut cue_mgr = cue_manager.write().await;
-                        if let Err(e) = cue_mgr.go() {
-                            log::error!("Error advancing cue: {}", e);
-                        }
-                    }
-                    22 => {
-                        // BPM control
-                        let bpm = 60.0 + (value as f64 / 127.0) * (187.0 - 60.0);
-                        log::info!("Setting BPM to {}", bpm);
-                        // Update tempo via rhythm state
-                    }
-                    _ => {}
-                }
             }
         }
     }
@@ -217,6 +266,9 @@ impl LightingConsole {
             self.update_rhythm_state(self.accumulated_beats).await;
         }
 
+        // Advance the effect clock, scaled by the FX rate master
+        self.update_fx_rhythm_state().await;
+
         // Process current cue if playing - update tracking state
         {
             let cue_manager = self.cue_manager.read().await;
@@ -248,10 +300,37 @@ impl LightingConsole {
 
     async fn update_rhythm_state(&self, beat_time: f64) {
         let mut rhythm = self.rhythm_state.write().await;
-        rhythm.beat_phase = beat_time.fract();
-        rhythm.bar_phase = (beat_time / rhythm.beats_per_bar as f64).fract();
-        rhythm.phrase_phase =
-            (beat_time / (rhythm.beats_per_bar * rhythm.bars_per_phrase) as f64).fract();
+        rhythm.set_beat_time(beat_time);
+    }
+
+    /// Advance the effect clock by the beats elapsed since the last update, scaled by the FX
+    /// rate master. Accumulating (rather than scaling the phase) keeps effects continuous when
+    /// the rate changes.
+    async fn update_fx_rhythm_state(&mut self) {
+        // Link can move the beat clock backwards (e.g. on session changes), don't rewind effects
+        let beat_delta = self
+            .fx_source_beats
+            .map_or(0.0, |source| (self.accumulated_beats - source).max(0.0));
+        self.fx_source_beats = Some(self.accumulated_beats);
+        self.fx_beats += beat_delta * self.fx_rate_master;
+
+        let rhythm = self.rhythm_state.read().await;
+        self.fx_rhythm_state = rhythm.clone();
+        self.fx_rhythm_state.set_beat_time(self.fx_beats);
+    }
+
+    /// Set the global FX rate master (0.0 to 2.0, 1.0 = 100%)
+    pub fn set_fx_rate_master(&mut self, rate: f64) {
+        self.fx_rate_master = rate.clamp(0.0, 2.0);
+    }
+
+    /// Set the global FX size master (0.0 to 2.0, 1.0 = 100%)
+    pub async fn set_fx_size_master(&mut self, size: f64) {
+        self.fx_size_master = size.clamp(0.0, 2.0);
+        self.pixel_engine
+            .write()
+            .await
+            .set_fx_size(self.fx_size_master);
     }
 
     /// Update rhythm state based on internal time when Link isn't available
@@ -276,7 +355,18 @@ impl LightingConsole {
     }
 
     /// Update tracking state with current cue
-    async fn update_tracking_state(&self, cue: crate::cue::cue::Cue) {
+    ///
+    /// Preset references are resolved here on every update, so edits to a stored preset show
+    /// up immediately in any cue that references it.
+    async fn update_tracking_state(&self, mut cue: crate::cue::cue::Cue) {
+        if !cue.preset_references.is_empty() {
+            let resolved =
+                CueResolver::new(&self.preset_library, &self.fixture_groups).resolve_cue(&cue);
+            cue.static_values = resolved.static_values;
+            cue.effects = resolved.effects;
+            cue.pixel_effects = resolved.pixel_effects;
+        }
+
         let mut tracking_state = self.tracking_state.write().await;
 
         if cue.is_blocking {
@@ -326,69 +416,121 @@ impl LightingConsole {
     }
 
     /// Apply effects from tracking state to fixtures
+    ///
+    /// Playback and live programmer effects are applied together in priority order. At the same
+    /// priority playback effects go first, in start order, with programmer effects on top. Live
+    /// programmer effects, like programmer values, only run in preview mode. Each effect merges
+    /// onto the value beneath it using its merge mode, starting from the tracked static value
+    /// (or zero) for that channel.
     async fn apply_effects(&self) {
         let tracking_state = self.tracking_state.read().await;
-        let effects = tracking_state.get_effects();
-        let rhythm_state = self.rhythm_state.read().await;
+        let mut effects = tracking_state.get_effects();
+        let programmer = self.programmer.read().await;
+        if programmer.get_preview_mode() {
+            effects.extend(programmer.get_effects().iter().cloned());
+        }
+        drop(programmer);
+        // A stable sort, so equal priorities keep playback beneath the programmer
+        effects.sort_by_key(|e| e.priority);
+
+        let static_values = tracking_state.get_static_values();
+        let rhythm_state = &self.fx_rhythm_state;
         let mut fixtures = self.fixtures.write().await;
+        let mut outputs: Vec<(usize, halo_fixtures::ChannelType, u8)> = Vec::new();
 
         for effect_mapping in effects {
-            // Calculate effect phase based on rhythm state
-            let phase = crate::effect::effect::get_effect_phase(
-                &rhythm_state,
-                &effect_mapping.effect.params,
-            );
+            // Calculate effect phase based on rhythm state, running in the mapping's direction
+            let phase = effect_mapping.phase(rhythm_state);
 
-            // Apply the effect to get normalized value (0.0 to 1.0)
-            let normalized_value = effect_mapping.effect.apply(phase);
-
-            // Scale to min/max range
+            // Scale to min/max range, with the FX size master scaling the effect's travel
             let min = effect_mapping.effect.min as f64;
             let max = effect_mapping.effect.max as f64;
-            let scaled_value = (min + (max - min) * normalized_value) as u8;
-
-            // Apply effect to fixtures based on distribution
-            match &effect_mapping.distribution {
-                crate::EffectDistribution::All => {
-                    // Apply same value to all fixtures
-                    for fixture_id in &effect_mapping.fixture_ids {
-                        if let Some(fixture) = fixtures.iter_mut().find(|f| f.id == *fixture_id) {
-                            for channel_type in &effect_mapping.channel_types {
-                                fixture.set_channel_value(channel_type, scaled_value);
-                            }
-                        }
-                    }
-                }
-                crate::EffectDistribution::Step(step_size) => {
-                    // Apply effect with step distribution
-                    for (idx, fixture_id) in effect_mapping.fixture_ids.iter().enumerate() {
-                        let step_phase = (phase + (idx / step_size) as f64) % 1.0;
-                        let step_normalized = effect_mapping.effect.apply(step_phase);
-                        let step_value = (min + (max - min) * step_normalized) as u8;
+            let size = self.fx_size_master;
+            let scale = |normalized: f64| (min + (max - min) * normalized * size).clamp(0.0, 255.0);
 
-                        if let Some(fixture) = fixtures.iter_mut().find(|f| f.id == *fixture_id) {
-                            for channel_type in &effect_mapping.channel_types {
-                                fixture.set_channel_value(channel_type, step_value);
-                            }
-                        }
-                    }
-                }
-                crate::EffectDistribution::Wave(phase_offset) => {
-                    // Apply effect with wave distribution (phase offset per fixture)
-                    for (idx, fixture_id) in effect_mapping.fixture_ids.iter().enumerate() {
-                        let wave_phase = (phase + idx as f64 * phase_offset) % 1.0;
-                        let wave_normalized = effect_mapping.effect.apply(wave_phase);
-                        let wave_value = (min + (max - min) * wave_normalized) as u8;
+            // Work out each fixture's value
+            let levels: Vec<(usize, u8)> = effect_mapping
+                .fixture_ids
+                .iter()
+                .copied()
+                .zip(effect_mapping.levels(phase, |id| {
+                    fixtures
+                        .iter()
+                        .find(|f| f.id == id)
+                        .and_then(|f| f.position)
+                }))
+                .map(|(id, level)| (id, scale(level) as u8))
+                .collect();
 
-                        if let Some(fixture) = fixtures.iter_mut().find(|f| f.id == *fixture_id) {
-                            for channel_type in &effect_mapping.channel_types {
-                                fixture.set_channel_value(channel_type, wave_value);
-                            }
-                        }
+            // Merge onto whatever is beneath this effect on each channel
+            for (fixture_id, value) in levels {
+                for channel_type in &effect_mapping.channel_types {
+                    if let Some(output) = outputs
+                        .iter_mut()
+                        .find(|(id, channel, _)| *id == fixture_id && channel == channel_type)
+                    {
+                        output.2 = effect_mapping.merge.apply(output.2, value);
+                    } else {
+                        let under = static_values
+                            .iter()
+                            .find(|v| v.fixture_id == fixture_id && v.channel_type == *channel_type)
+                            .map_or(0, |v| v.value);
+                        let merged = effect_mapping.merge.apply(under, value);
+                        outputs.push((fixture_id, channel_type.clone(), merged));
                     }
                 }
             }
         }
+
+        for (fixture_id, channel_type, value) in outputs {
+            if let Some(fixture) = fixtures.iter_mut().find(|f| f.id == fixture_id) {
+                fixture.set_channel_value(&channel_type, value);
+            }
+        }
+    }
+
+    /// Snapshot the programmer before an edit and tell the UI what undo would revert
+    async fn checkpoint_programmer(
+        &self,
+        action: &'static str,
+        event_tx: &mpsc::UnboundedSender<ConsoleEvent>,
+    ) {
+        let mut programmer = self.programmer.write().await;
+        Self::checkpoint_locked_programmer(&mut programmer, action, event_tx);
+    }
+
+    /// `checkpoint_programmer` for a handler already holding the programmer, so it can check
+    /// the edit will change something first
+    fn checkpoint_locked_programmer(
+        programmer: &mut Programmer,
+        action: &'static str,
+        event_tx: &mpsc::UnboundedSender<ConsoleEvent>,
+    ) {
+        programmer.checkpoint(action);
+        let _ = event_tx.send(ConsoleEvent::ProgrammerUndoChanged {
+            next_undo: programmer.undo_action().map(str::to_string),
+            next_redo: programmer.redo_action().map(str::to_string),
+        });
+    }
+
+    /// Send the selection along with the highlight and stepping state, which any selection
+    /// change can alter by ending a selection step
+    fn send_selection_state(
+        programmer: &Programmer,
+        event_tx: &mpsc::UnboundedSender<ConsoleEvent>,
+    ) {
+        let (highlight, lowlight) = programmer.get_highlight();
+        let _ = event_tx.send(ConsoleEvent::ProgrammerStateUpdated {
+            preview_mode: programmer.get_preview_mode(),
+            selected_fixtures: programmer.get_selected_fixtures().clone(),
+        });
+        let _ = event_tx.send(ConsoleEvent::ProgrammerHighlightChanged {
+            highlight,
+            lowlight,
+        });
+        let _ = event_tx.send(ConsoleEvent::SelectionStepChanged {
+            step: programmer.get_selection_step(),
+        });
     }
 
     async fn apply_programmer_values(&self) {
@@ -407,11 +549,11 @@ impl LightingConsole {
 
     async fn send_dmx_data(&self) -> Result<Vec<(usize, Vec<(u8, u8, u8)>)>, anyhow::Error> {
         let fixtures = self.fixtures.read().await;
+        let programmer = self.programmer.read().await;
 
         // Render pixel fixtures first
         let pixel_engine = self.pixel_engine.read().await;
-        let rhythm_state = self.rhythm_state.read().await;
-        let mut universe_data = pixel_engine.render(&fixtures, &rhythm_state);
+        let mut universe_data = pixel_engine.render(&fixtures, &self.fx_rhythm_state);
 
         // Merge regular fixtures into universe buffers
         for fixture in fixtures.iter() {
@@ -422,7 +564,11 @@ impl LightingConsole {
                     .or_insert_with(|| vec![0; 512]);
 
                 let start_channel = (fixture.start_address - 1) as usize;
-                let fixture_data = fixture.get_dmx_values();
+                // Highlight overrides the output without touching the fixture's values
+                let mut fixture_data = programmer
+                    .highlight_values(fixture)
+                    .unwrap_or_else(|| fixture.get_dmx_values());
+                self.apply_output_overrides(fixture, &mut fixture_data);
                 let end_channel = (start_channel + fixture_data.len()).min(512);
 
                 universe_buffer[start_channel..end_channel].copy_from_slice(&fixture_data);
@@ -467,6 +613,131 @@ impl LightingConsole {
         Ok(pixel_data)
     }
 
+    /// Send the show timecode to the MIDI module for MTC output, while playing or chasing
+    async fn send_mtc_output(&self) {
+        let timecode = {
+            let cue_manager = self.cue_manager.read().await;
+            let running = cue_manager.get_playback_state() == PlaybackState::Playing
+                || cue_manager.is_chasing();
+            cue_manager.current_timecode.filter(|_| running)
+        };
+        // Without a MIDI device there's no MIDI module to send to
+        let _ = self
+            .module_manager
+            .send_to_module(ModuleId::Midi, ModuleEvent::MtcOutput(timecode))
+            .await;
+    }
+
+    /// Send the beat clock to the MIDI module for MIDI clock output
+    async fn send_midi_clock_output(&self) {
+        let playing = self.cue_manager.read().await.get_playback_state() == PlaybackState::Playing;
+        let event = ModuleEvent::MidiClockOutput {
+            clock: Some((self.accumulated_beats, self.tempo)),
+            playing,
+        };
+        let _ = self
+            .module_manager
+            .send_to_module(ModuleId::Midi, event)
+            .await;
+    }
+
+    /// Reflect console state on the controller's LEDs and motor faders, as the profile describes
+    async fn send_midi_feedback(&mut self) {
+        let (channel, feedback, init) = {
+            let settings = self.settings.read().await;
+            let Some(profile) = self
+                .midi_profiles
+                .iter()
+                .find(|profile| profile.name == settings.midi_profile)
+            else {
+                return;
+            };
+            (
+                settings.midi_channel,
+                profile.feedback.clone(),
+                profile.init.clone(),
+            )
+        };
+        if feedback.is_empty() {
+            return;
+        }
+
+        let cue_manager = self.cue_manager.read().await;
+        let programmer = self.programmer.read().await;
+        let cue_list_lengths = cue_manager.cue_list_lengths();
+        let messages = {
+            let programmer_value = |channel: &str| {
+                let fixture_id = *programmer.get_selected_fixtures().first()?;
+                programmer.get_value(fixture_id, &Self::channel_string_to_type(channel))
+            };
+            let state = MidiFeedbackState {
+                playing: cue_manager.get_playback_state() == PlaybackState::Playing,
+                current_cue_list_index: cue_manager.get_current_cue_list_idx(),
+                current_cue_index: cue_manager.get_current_cue_idx(),
+                cue_list_lengths: &cue_list_lengths,
+                bpm: self.tempo,
+                fx_rate_master: self.fx_rate_master,
+                fx_size_master: self.fx_size_master,
+                smoke_active: self.smoke_active,
+                flashed_fixtures: &self.flashed_fixtures,
+                fixture_groups: &self.fixture_groups,
+                programmer_value: &programmer_value,
+            };
+
+            // Nothing sent yet since the profile was picked, so set the controller up first
+            let mut messages = if self.midi_feedback_sent.is_empty() {
+                init
+            } else {
+                Vec::new()
+            };
+            for item in &feedback {
+                let value = item.value(&state);
+                if self.midi_feedback_sent.insert(item.control, value) != Some(value) {
+                    messages.push(item.control.message(channel, value));
+                }
+            }
+            messages
+        };
+        drop(programmer);
+        drop(cue_manager);
+
+        for message in messages {
+            // Without a MIDI device there's no MIDI module to send to
+            let _ = self
+                .module_manager
+                .send_to_module(ModuleId::Midi, ModuleEvent::MidiOutput(message))
+                .await;
+        }
+    }
+
+    /// Flash and smoke buttons drive the output to full without touching the fixture's values
+    fn apply_output_overrides(&self, fixture: &Fixture, values: &mut [u8]) {
+        let flashed = self.flashed_fixtures.contains(&fixture.id);
+        let smoke =
+            self.smoke_active && fixture.profile.fixture_type == halo_fixtures::FixtureType::Smoke;
+        if !flashed && !smoke {
+            return;
+        }
+
+        // Fixtures without a dimmer are flashed through their color channels
+        let has_dimmer = fixture
+            .channels
+            .iter()
+            .any(|c| c.channel_type == ChannelType::Dimmer);
+        for (channel, value) in fixture.channels.iter().zip(values.iter_mut()) {
+            match &channel.channel_type {
+                ChannelType::Dimmer if flashed => *value = 255,
+                ChannelType::Red | ChannelType::Green | ChannelType::Blue | ChannelType::White
+                    if flashed && !has_dimmer =>
+                {
+                    *value = 255
+                }
+                ChannelType::Other(name) if smoke && name == "Smoke" => *value = 255,
+                _ => {}
+            }
+        }
+    }
+
     /// Load fixture library
     pub fn load_fixture_library(&mut self) {
         self.fixture_library = FixtureLibrary::new();
@@ -486,6 +757,9 @@ impl LightingConsole {
             "white" => ChannelType::White,
             "amber" => ChannelType::Amber,
             "uv" => ChannelType::UV,
+            "cyan" => ChannelType::Cyan,
+            "magenta" => ChannelType::Magenta,
+            "yellow" => ChannelType::Yellow,
             "strobe" => ChannelType::Strobe,
             "pan" => ChannelType::Pan,
             "tilt" => ChannelType::Tilt,
@@ -501,6 +775,42 @@ impl LightingConsole {
         }
     }
 
+    /// Center point of the given fixtures' stage positions, ignoring unpositioned fixtures
+    async fn fixture_centroid(&self, fixture_ids: &[usize]) -> halo_fixtures::FixturePosition {
+        let fixtures = self.fixtures.read().await;
+        let positions: Vec<_> = fixtures
+            .iter()
+            .filter(|f| fixture_ids.contains(&f.id))
+            .filter_map(|f| f.position)
+            .collect();
+
+        let mut center = halo_fixtures::FixturePosition::default();
+        if !positions.is_empty() {
+            let count = positions.len() as f64;
+            center.x = positions.iter().map(|p| p.x).sum::<f64>() / count;
+            center.y = positions.iter().map(|p| p.y).sum::<f64>() / count;
+            center.z = positions.iter().map(|p| p.z).sum::<f64>() / count;
+        }
+        center
+    }
+
+    /// Summarize the programmer's live effects for the UI
+    fn programmer_effects_summary(programmer: &Programmer) -> Vec<ProgrammerEffectSummary> {
+        programmer
+            .get_effects()
+            .iter()
+            .map(|e| {
+                (
+                    e.name.clone(),
+                    e.effect.effect_type,
+                    e.fixture_ids.clone(),
+                    e.channel_types.iter().map(|c| c.to_string()).collect(),
+                    e.direction,
+                )
+            })
+            .collect()
+    }
+
     /// Patch a fixture
     pub async fn patch_fixture(
         &mut self,
@@ -533,6 +843,7 @@ impl LightingConsole {
             universe,
             start_address: address,
             pan_tilt_limits: None,
+            position: None,
         };
 
         fixtures.push(fixture);
@@ -618,13 +929,9 @@ impl LightingConsole {
                 .enable()
                 .await
                 .map_err(|e| anyhow::anyhow!("Failed to enable Ableton Link: {}", e))?;
-
-            // Enable start/stop sync
-            link_manager
-                .enable_start_stop_sync(true)
-                .await
-                .map_err(|e| anyhow::anyhow!("Failed to enable start/stop sync: {}", e))?;
         }
+        // Link's beat count replaces ours, don't jump effects by the difference
+        self.fx_source_beats = None;
 
         log::info!("Ableton Link enabled and synchronized");
         Ok(())
@@ -634,6 +941,7 @@ impl LightingConsole {
     pub async fn disable_ableton_link(&mut self) {
         let mut link_manager = self.link_manager.lock().await;
         link_manager.disable();
+        self.fx_source_beats = None;
         log::info!("Ableton Link disabled");
     }
 
@@ -649,6 +957,17 @@ impl LightingConsole {
         link_manager.num_peers()
     }
 
+    /// Send the Link tempo, phase and session settings to the UI
+    async fn send_link_session(&self, event_tx: &mpsc::UnboundedSender<ConsoleEvent>) {
+        let link_manager = self.link_manager.lock().await;
+        let _ = event_tx.send(ConsoleEvent::LinkSessionUpdated {
+            tempo: link_manager.tempo(),
+            phase: link_manager.phase(),
+            quantum: link_manager.quantum(),
+            start_stop_sync: link_manager.start_stop_sync(),
+        });
+    }
+
     /// Set the BPM/tempo
     pub async fn set_bpm(&mut self, bpm: f64) -> Result<(), anyhow::Error> {
         // Set the tempo using ableton's boundary
@@ -676,12 +995,78 @@ impl LightingConsole {
         self.active_overrides.insert(note, (false, 0));
     }
 
+    /// Learn or dispatch a MIDI mapping, returning whether the message was used
+    async fn handle_mapped_midi(
+        &mut self,
+        midi_msg: &MidiMessage,
+        event_tx: &mpsc::UnboundedSender<ConsoleEvent>,
+    ) -> bool {
+        let Some((control, value)) = MidiControl::from_message(midi_msg) else {
+            return false;
+        };
+
+        if let Some(action) = self.midi_learn.take() {
+            // Ignore note velocity 0 and CC button releases so the release doesn't get learned
+            if value == 0 {
+                self.midi_learn = Some(action);
+                return true;
+            }
+            log::info!("Learned {} for {}", control.label(), action.label());
+            let settings = {
+                let mut settings = self.settings.write().await;
+                learn_mapping(&mut settings.midi_mappings, MidiMapping { control, action });
+                settings.clone()
+            };
+            let _ = event_tx.send(ConsoleEvent::MidiLearnChanged { action: None });
+            let _ = event_tx.send(ConsoleEvent::SettingsUpdated { settings });
+            return true;
+        }
+
+        let action = {
+            let settings = self.settings.read().await;
+            let profile = self
+                .midi_profiles
+                .iter()
+                .find(|profile| profile.name == settings.midi_profile);
+            settings
+                .midi_mappings
+                .iter()
+                .chain(profile.into_iter().flat_map(|profile| &profile.mappings))
+                .find(|mapping| mapping.control == control)
+                .map(|mapping| mapping.action.clone())
+        };
+        let Some(action) = action else {
+            return false;
+        };
+        let selected_fixtures = self.programmer.read().await.get_selected_fixtures().clone();
+        let context = MidiMappingContext {
+            current_cue_list_index: self.cue_manager.read().await.get_current_cue_list_idx(),
+            selected_fixtures: &selected_fixtures,
+            fixture_groups: &self.fixture_groups,
+        };
+        for command in action.commands(value, &context) {
+            if let Err(e) = self.process_command(command, event_tx).await {
+                log::warn!("MIDI mapping for {} failed: {e}", control.label());
+            }
+        }
+        true
+    }
+
     /// Create a new show
     pub async fn new_show(&mut self, name: String) -> Result<(), anyhow::Error> {
         let _ = self.show_manager.write().await.new_show(name);
+        self.show_dirty = false;
+        self.release_flash_and_smoke();
         Ok(())
     }
 
+    /// Drop held flashes and smoke, which belong to the show that was running rather than the
+    /// next one (flashed fixture IDs may not even exist in it)
+    fn release_flash_and_smoke(&mut self) {
+        self.flashed_fixtures.clear();
+        self.smoke_active = false;
+    }
+
     /// Reload the current show
     pub async fn reload_show(&mut self) -> Result<(), anyhow::Error> {
         let current_path = {
@@ -701,6 +1086,7 @@ impl LightingConsole {
             .write()
             .await
             .save_show(&self.get_show().await.clone())?;
+        self.show_dirty = false;
         Ok(result)
     }
 
@@ -716,6 +1102,7 @@ impl LightingConsole {
             .write()
             .await
             .save_show_as(&self.get_show().await.clone(), path)?;
+        self.show_dirty = false;
         Ok(result)
     }
 
@@ -792,7 +1179,15 @@ impl LightingConsole {
 
         // After all fixtures are loaded with their original IDs, set the cue lists
         self.set_cue_lists(show.cue_lists).await;
+        self.preset_library = show.presets;
+        self.fixture_groups = show.fixture_groups;
+        self.quick_selects = show.quick_selects;
+        self.color_swatches = show.color_swatches;
+        self.fixture_grid = show.fixture_grid;
+        self.magic_sheet = show.magic_sheet;
         self.show_name = show.name.clone();
+        self.show_dirty = false;
+        self.release_flash_and_smoke();
 
         log::info!("Successfully loaded show '{}'", show.name);
 
@@ -815,6 +1210,12 @@ impl LightingConsole {
         let mut show = crate::show::show::Show::new(self.show_name.clone());
         show.fixtures = fixtures.clone();
         show.cue_lists = cue_lists;
+        show.presets = self.preset_library.clone();
+        show.fixture_groups = self.fixture_groups.clone();
+        show.quick_selects = self.quick_selects.clone();
+        show.color_swatches = self.color_swatches.clone();
+        show.fixture_grid = self.fixture_grid.clone();
+        show.magic_sheet = self.magic_sheet.clone();
         show.modified_at = std::time::SystemTime::now();
         show
     }
@@ -842,6 +1243,26 @@ impl LightingConsole {
         &mut self,
         command: ConsoleCommand,
         event_tx: &mpsc::UnboundedSender<ConsoleEvent>,
+    ) -> Result<(), anyhow::Error> {
+        let was_dirty = self.show_dirty;
+        let modifies_show = command.modifies_show();
+
+        let result = self.handle_command(command, event_tx).await;
+        if modifies_show && result.is_ok() {
+            self.show_dirty = true;
+        }
+        if self.show_dirty != was_dirty {
+            let _ = event_tx.send(ConsoleEvent::ShowDirtyChanged {
+                dirty: self.show_dirty,
+            });
+        }
+        result
+    }
+
+    async fn handle_command(
+        &mut self,
+        command: ConsoleCommand,
+        event_tx: &mpsc::UnboundedSender<ConsoleEvent>,
     ) -> Result<(), anyhow::Error> {
         use ConsoleCommand::*;
 
@@ -866,6 +1287,7 @@ impl LightingConsole {
             NewShow { name } => {
                 self.new_show(name.clone()).await?;
                 let _ = event_tx.send(ConsoleEvent::ShowCreated { name });
+                let _ = event_tx.send(ConsoleEvent::SmokeChanged { active: false });
             }
             LoadShow { path } => {
                 log::info!("Processing LoadShow command for path: {:?}", path);
@@ -875,6 +1297,7 @@ impl LightingConsole {
                         let settings = self.settings.read().await.clone();
                         let _ = event_tx.send(ConsoleEvent::ShowLoaded { show });
                         let _ = event_tx.send(ConsoleEvent::CurrentSettings { settings });
+                        let _ = event_tx.send(ConsoleEvent::SmokeChanged { active: false });
                         log::info!("LoadShow command completed successfully");
                     }
                     Err(e) => {
@@ -900,6 +1323,7 @@ impl LightingConsole {
                     let settings = self.settings.read().await.clone();
                     let _ = event_tx.send(ConsoleEvent::ShowLoaded { show });
                     let _ = event_tx.send(ConsoleEvent::CurrentSettings { settings });
+                    let _ = event_tx.send(ConsoleEvent::SmokeChanged { active: false });
                     log::info!("ReloadShow command completed successfully");
                 }
                 Err(e) => {
@@ -984,6 +1408,22 @@ impl LightingConsole {
                     log::info!("Set pan/tilt limits for fixture {fixture_id}: pan({pan_min}-{pan_max}), tilt({tilt_min}-{tilt_max})");
                 }
             }
+            SetFixturePosition {
+                fixture_id,
+                position,
+            } => {
+                let mut fixtures = self.fixtures.write().await;
+                if let Some(fixture) = fixtures.iter_mut().find(|f| f.id == fixture_id) {
+                    match position {
+                        Some(position) => fixture.set_position(position),
+                        None => fixture.clear_position(),
+                    }
+                    let _ = event_tx.send(ConsoleEvent::FixtureUpdated {
+                        fixture_id,
+                        fixture: fixture.clone(),
+                    });
+                }
+            }
             ClearPanTiltLimits { fixture_id } => {
                 let mut fixtures = self.fixtures.write().await;
                 if let Some(fixture) = fixtures.iter_mut().find(|f| f.id == fixture_id) {
@@ -1025,15 +1465,46 @@ impl LightingConsole {
                     }
                 }
             }
-            DeleteCue {
+            SetCueColor {
                 list_index,
                 cue_index,
+                red,
+                green,
+                blue,
             } => {
+                let fixture_ids: Vec<usize> = {
+                    let cue_manager = self.cue_manager.read().await;
+                    let mut ids: Vec<usize> = cue_manager
+                        .get_cue_lists()
+                        .get(list_index)
+                        .and_then(|list| list.cues.get(cue_index))
+                        .map(|cue| cue.static_values.iter().map(|v| v.fixture_id).collect())
+                        .unwrap_or_default();
+                    ids.sort_unstable();
+                    ids.dedup();
+                    ids
+                };
+                let values: Vec<StaticValue> = {
+                    let fixtures = self.fixtures.read().await;
+                    fixtures
+                        .iter()
+                        .filter(|f| fixture_ids.contains(&f.id))
+                        .flat_map(|fixture| {
+                            fixture.color_values([red, green, blue]).into_iter().map(
+                                |(channel_type, value)| StaticValue {
+                                    fixture_id: fixture.id,
+                                    channel_type,
+                                    value,
+                                },
+                            )
+                        })
+                        .collect()
+                };
                 let result = self
                     .cue_manager
                     .write()
                     .await
-                    .remove_cue(list_index, cue_index);
+                    .set_cue_static_values(list_index, cue_index, values);
                 match result {
                     Ok(_) => {
                         let cue_lists = self.cue_manager.read().await.get_cue_lists();
@@ -1041,13 +1512,21 @@ impl LightingConsole {
                     }
                     Err(e) => {
                         let _ = event_tx.send(ConsoleEvent::Error {
-                            message: format!("Failed to delete cue: {}", e),
+                            message: format!("Failed to set cue color: {e}"),
                         });
                     }
                 }
             }
-            DeleteCueList { list_index } => {
-                let result = self.cue_manager.write().await.remove_cue_list(list_index);
+            AddCueEffect {
+                list_index,
+                cue_index,
+                effect,
+            } => {
+                let result = self
+                    .cue_manager
+                    .write()
+                    .await
+                    .add_cue_effect(list_index, cue_index, effect);
                 match result {
                     Ok(_) => {
                         let cue_lists = self.cue_manager.read().await.get_cue_lists();
@@ -1055,26 +1534,126 @@ impl LightingConsole {
                     }
                     Err(e) => {
                         let _ = event_tx.send(ConsoleEvent::Error {
-                            message: format!("Failed to delete cue list: {}", e),
+                            message: format!("Failed to add cue effect: {}", e),
                         });
                     }
                 }
             }
-            SetCueListAudioFile {
+            UpdateCueEffect {
                 list_index,
-                audio_file,
+                cue_index,
+                effect_index,
+                effect,
             } => {
-                let result = if let Some(file_path) = &audio_file {
+                let result = self.cue_manager.write().await.update_cue_effect(
+                    list_index,
+                    cue_index,
+                    effect_index,
+                    effect,
+                );
+                match result {
+                    Ok(_) => {
+                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to update cue effect: {}", e),
+                        });
+                    }
+                }
+            }
+            StoreEffectPreset {
+                name,
+                effect,
+                distribution,
+                channel_types,
+            } => {
+                let id = self.preset_library.next_id(&PresetType::Effect);
+                let mut preset = EffectPreset::new_standard(id, name, Vec::new(), effect);
+                preset.distribution = distribution;
+                preset.channel_types = channel_types;
+                self.preset_library.add_preset(Preset::Effect(preset));
+
+                let presets = self.preset_library.clone();
+                let _ = event_tx.send(ConsoleEvent::PresetsUpdated { presets });
+            }
+            UpdateEffectPreset { preset } => {
+                if self
+                    .preset_library
+                    .update_preset(Preset::Effect(preset.clone()))
+                {
+                    // Editing a preset updates every cue effect that references it
+                    let updated = self.cue_manager.write().await.sync_effect_preset(&preset);
+                    log::debug!(
+                        "Effect preset '{}' updated {} cue effects",
+                        preset.name,
+                        updated
+                    );
+
+                    let presets = self.preset_library.clone();
+                    let _ = event_tx.send(ConsoleEvent::PresetsUpdated { presets });
+                    let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                    let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                } else {
+                    let _ = event_tx.send(ConsoleEvent::Error {
+                        message: format!("Effect preset {} not found", preset.id),
+                    });
+                }
+            }
+            DeleteEffectPreset { preset_id } => {
+                if self
+                    .preset_library
+                    .delete_preset(&PresetType::Effect, preset_id)
+                {
                     self.cue_manager
                         .write()
                         .await
-                        .set_audio_file(list_index, file_path.clone())
-                } else {
-                    // Clear the audio file
-                    self.cue_manager
+                        .detach_effect_preset(preset_id);
+
+                    let presets = self.preset_library.clone();
+                    let _ = event_tx.send(ConsoleEvent::PresetsUpdated { presets });
+                    let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                    let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                }
+            }
+            ApplyEffectPreset {
+                preset_id,
+                list_index,
+                cue_index,
+                fixture_ids,
+            } => {
+                let mapping = self
+                    .preset_library
+                    .effect
+                    .iter()
+                    .find(|p| p.id == preset_id)
+                    .ok_or_else(|| format!("Effect preset {} not found", preset_id))
+                    .and_then(|preset| match &preset.effect {
+                        EffectPresetType::Standard(effect) => Ok(crate::EffectMapping {
+                            name: preset.name.clone(),
+                            effect: effect.clone(),
+                            fixture_ids,
+                            channel_types: preset.target_channels(),
+                            distribution: preset.distribution.clone(),
+                            release: crate::EffectRelease::Hold,
+                            preset_id: Some(preset.id),
+                            priority: 0,
+                            merge: crate::EffectMerge::Replace,
+                            direction: crate::EffectDirection::Forward,
+                        }),
+                        EffectPresetType::Pixel(_) => {
+                            Err("Pixel effect presets can't be applied as cue effects".to_string())
+                        }
+                    });
+
+                let result = match mapping {
+                    Ok(mapping) => self
+                        .cue_manager
                         .write()
                         .await
-                        .set_audio_file(list_index, String::new())
+                        .add_cue_effect(list_index, cue_index, mapping),
+                    Err(e) => Err(e),
                 };
                 match result {
                     Ok(_) => {
@@ -1083,50 +1662,255 @@ impl LightingConsole {
                     }
                     Err(e) => {
                         let _ = event_tx.send(ConsoleEvent::Error {
-                            message: format!("Failed to set audio file: {}", e),
+                            message: format!("Failed to apply effect preset: {}", e),
                         });
                     }
                 }
             }
-            AddCue {
-                list_index,
+            StoreProgrammerPreset {
+                preset_type,
+                preset_id,
                 name,
-                fade_time,
-                timecode,
-                is_blocking,
+                changed_only,
             } => {
-                let cue = Cue {
-                    id: 0, // Will be set by the cue manager
+                let values = if changed_only {
+                    let tracked = self.tracking_state.read().await.get_static_values();
+                    self.programmer.read().await.changed_values(&tracked)
+                } else {
+                    self.programmer.read().await.get_values().clone()
+                };
+                let name = if name.is_empty() {
+                    format!("{} {}", preset_type.as_str(), preset_id)
+                } else {
+                    name
+                };
+                match Preset::from_values(
+                    &preset_type,
+                    preset_id,
                     name,
-                    fade_time: Duration::from_secs_f64(fade_time),
-                    timecode,
-                    static_values: Vec::new(),
-                    effects: Vec::new(),
-                    pixel_effects: Vec::new(),
-                    is_blocking,
+                    &values,
+                    &self.fixture_groups,
+                ) {
+                    Some(preset) => {
+                        self.preset_library.store_preset(preset);
+                        let presets = self.preset_library.clone();
+                        let _ = event_tx.send(ConsoleEvent::PresetsUpdated { presets });
+                    }
+                    None => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!(
+                                "No {} values in the programmer to store",
+                                preset_type.as_str()
+                            ),
+                        });
+                    }
+                }
+            }
+            RecallPreset {
+                preset_type,
+                preset_id,
+                fixture_ids,
+            } => {
+                self.checkpoint_programmer("recall preset", event_tx).await;
+                let reference = PresetReference {
+                    preset_type,
+                    preset_id,
+                    fixture_group_id: None,
+                    fixture_ids,
+                    overrides: Vec::new(),
                 };
-                let result = self.cue_manager.write().await.add_cue(list_index, cue);
-                match result {
-                    Ok(_) => {
-                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
-                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                let resolved = CueResolver::new(&self.preset_library, &self.fixture_groups)
+                    .resolve_reference(&reference);
+                match resolved {
+                    Some(resolved) => {
+                        let mut programmer = self.programmer.write().await;
+                        for value in resolved.static_values {
+                            programmer.add_value(value.fixture_id, value.channel_type, value.value);
+                        }
+                        let values: Vec<(usize, String, u8)> = programmer
+                            .get_values()
+                            .iter()
+                            .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                            .collect();
+                        drop(programmer);
+
+                        let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
                     }
-                    Err(e) => {
+                    None => {
                         let _ = event_tx.send(ConsoleEvent::Error {
-                            message: format!("Failed to add cue: {}", e),
+                            message: format!("Preset {} not found", preset_id),
                         });
                     }
                 }
             }
-            PlayCue {
-                list_index,
-                cue_index,
+            DeletePreset {
+                preset_type,
+                preset_id,
             } => {
-                let _ = self
-                    .cue_manager
+                if self.preset_library.delete_preset(&preset_type, preset_id) {
+                    let mut cue_manager = self.cue_manager.write().await;
+                    if preset_type == PresetType::Effect {
+                        cue_manager.detach_effect_preset(preset_id);
+                    }
+                    cue_manager.remove_preset_references(&preset_type, preset_id);
+                    let cue_lists = cue_manager.get_cue_lists();
+                    drop(cue_manager);
+
+                    let presets = self.preset_library.clone();
+                    let _ = event_tx.send(ConsoleEvent::PresetsUpdated { presets });
+                    let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                }
+            }
+            AddCuePresetReference {
+                list_index,
+                cue_index,
+                reference,
+            } => {
+                let result = self
+                    .cue_manager
+                    .write()
+                    .await
+                    .add_cue_preset_reference(list_index, cue_index, reference);
+                match result {
+                    Ok(_) => {
+                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to reference preset: {}", e),
+                        });
+                    }
+                }
+            }
+            RemoveCuePresetReference {
+                list_index,
+                cue_index,
+                reference_index,
+            } => {
+                let result = self.cue_manager.write().await.remove_cue_preset_reference(
+                    list_index,
+                    cue_index,
+                    reference_index,
+                );
+                match result {
+                    Ok(_) => {
+                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to remove preset reference: {}", e),
+                        });
+                    }
+                }
+            }
+            DeleteCue {
+                list_index,
+                cue_index,
+            } => {
+                let result = self
+                    .cue_manager
+                    .write()
+                    .await
+                    .remove_cue(list_index, cue_index);
+                match result {
+                    Ok(_) => {
+                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to delete cue: {}", e),
+                        });
+                    }
+                }
+            }
+            DeleteCueList { list_index } => {
+                let result = self.cue_manager.write().await.remove_cue_list(list_index);
+                match result {
+                    Ok(_) => {
+                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to delete cue list: {}", e),
+                        });
+                    }
+                }
+            }
+            SetCueListAudioFile {
+                list_index,
+                audio_file,
+            } => {
+                let result = if let Some(file_path) = &audio_file {
+                    self.cue_manager
+                        .write()
+                        .await
+                        .set_audio_file(list_index, file_path.clone())
+                } else {
+                    // Clear the audio file
+                    self.cue_manager
+                        .write()
+                        .await
+                        .set_audio_file(list_index, String::new())
+                };
+                match result {
+                    Ok(_) => {
+                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to set audio file: {}", e),
+                        });
+                    }
+                }
+            }
+            AddCue {
+                list_index,
+                name,
+                fade_time,
+                timecode,
+                is_blocking,
+            } => {
+                let cue = Cue {
+                    id: 0, // Will be set by the cue manager
+                    name,
+                    fade_time: Duration::from_secs_f64(fade_time),
+                    timecode,
+                    static_values: Vec::new(),
+                    effects: Vec::new(),
+                    pixel_effects: Vec::new(),
+                    is_blocking,
+                    preset_references: Vec::new(),
+                };
+                let result = self.cue_manager.write().await.add_cue(list_index, cue);
+                match result {
+                    Ok(_) => {
+                        let cue_lists = self.cue_manager.read().await.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to add cue: {}", e),
+                        });
+                    }
+                }
+            }
+            PlayCue {
+                list_index,
+                cue_index,
+            } => {
+                if let Err(err) = self
+                    .cue_manager
                     .write()
                     .await
-                    .go_to_cue(list_index, cue_index);
+                    .go_to_cue(list_index, cue_index)
+                {
+                    log::warn!("Error going to cue {cue_index} in list {list_index}: {err}");
+                }
                 let _ = event_tx.send(ConsoleEvent::CueStarted {
                     list_index,
                     cue_index,
@@ -1150,11 +1934,14 @@ impl LightingConsole {
                 list_index,
                 cue_index,
             } => {
-                let _ = self
+                if let Err(err) = self
                     .cue_manager
                     .write()
                     .await
-                    .go_to_cue(list_index, cue_index);
+                    .go_to_cue(list_index, cue_index)
+                {
+                    log::warn!("Error going to cue {cue_index} in list {list_index}: {err}");
+                }
                 let _ = event_tx.send(ConsoleEvent::CueStarted {
                     list_index,
                     cue_index,
@@ -1169,7 +1956,9 @@ impl LightingConsole {
                 });
             }
             NextCue { list_index: _ } => {
-                let _ = self.cue_manager.write().await.go_to_next_cue();
+                if let Err(err) = self.cue_manager.write().await.go_to_next_cue() {
+                    log::warn!("Error going to the next cue: {err}");
+                }
                 // Send current cue update
                 let cue_manager = self.cue_manager.read().await;
                 let cue_index = cue_manager.get_current_cue_idx().unwrap_or(0);
@@ -1180,7 +1969,9 @@ impl LightingConsole {
                 });
             }
             PrevCue { list_index: _ } => {
-                let _ = self.cue_manager.write().await.go_to_previous_cue();
+                if let Err(err) = self.cue_manager.write().await.go_to_previous_cue() {
+                    log::warn!("Error going to the previous cue: {err}");
+                }
                 // Send current cue update
                 let cue_manager = self.cue_manager.read().await;
                 let cue_index = cue_manager.get_current_cue_idx().unwrap_or(0);
@@ -1400,6 +2191,30 @@ impl LightingConsole {
                 self.midi_overrides.remove(&note);
                 let _ = event_tx.send(ConsoleEvent::MidiOverrideRemoved { note });
             }
+            StartMidiLearn { action } => {
+                self.midi_learn = Some(action.clone());
+                let _ = event_tx.send(ConsoleEvent::MidiLearnChanged {
+                    action: Some(action),
+                });
+            }
+            CancelMidiLearn => {
+                self.midi_learn = None;
+                let _ = event_tx.send(ConsoleEvent::MidiLearnChanged { action: None });
+            }
+            QueryMidiProfiles => {
+                let profiles = self.midi_profiles.iter().map(|p| p.name.clone()).collect();
+                let _ = event_tx.send(ConsoleEvent::MidiProfilesList { profiles });
+            }
+            RemoveMidiMapping { index } => {
+                let settings = {
+                    let mut settings = self.settings.write().await;
+                    if index < settings.midi_mappings.len() {
+                        settings.midi_mappings.remove(index);
+                    }
+                    settings.clone()
+                };
+                let _ = event_tx.send(ConsoleEvent::SettingsUpdated { settings });
+            }
             ProcessMidiMessage { message } => {
                 // TODO: Process MIDI message
                 let _ = event_tx.send(ConsoleEvent::MidiMessageReceived { message });
@@ -1419,22 +2234,93 @@ impl LightingConsole {
                 let _ = event_tx.send(ConsoleEvent::AudioVolumeChanged { volume });
             }
 
+            // Effect masters
+            SetFxRateMaster { rate } => {
+                self.set_fx_rate_master(rate);
+                let _ = event_tx.send(ConsoleEvent::FxMastersChanged {
+                    rate: self.fx_rate_master,
+                    size: self.fx_size_master,
+                });
+            }
+            SetFxSizeMaster { size } => {
+                self.set_fx_size_master(size).await;
+                let _ = event_tx.send(ConsoleEvent::FxMastersChanged {
+                    rate: self.fx_rate_master,
+                    size: self.fx_size_master,
+                });
+            }
+
             // Effects
             ApplyEffect {
-                fixture_ids: _,
-                channel_type: _,
-                effect_type: _,
-                frequency: _,
-                amplitude: _,
-                offset: _,
+                fixture_ids,
+                channel_type,
+                effect_type,
+                frequency,
+                amplitude,
+                offset,
             } => {
-                // TODO: Implement apply_effect method
+                self.checkpoint_programmer("apply effect", event_tx).await;
+                let channel_type = Self::channel_string_to_type(&channel_type);
+
+                // Frequency is cycles per beat, offset is a phase offset and amplitude scales the
+                // effect's range
+                let effect = crate::Effect {
+                    effect_type,
+                    max: (255.0 * amplitude.clamp(0.0, 1.0)) as u8,
+                    amplitude,
+                    frequency,
+                    offset,
+                    params: crate::EffectParams {
+                        interval_ratio: frequency as f64,
+                        phase: (offset as f64).rem_euclid(1.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                let effect_mapping = crate::EffectMapping {
+                    name: format!("Live_{}_{}", effect_type.as_str(), channel_type),
+                    effect,
+                    fixture_ids: fixture_ids.clone(),
+                    channel_types: vec![channel_type.clone()],
+                    distribution: crate::EffectDistribution::All,
+                    release: crate::EffectRelease::Hold,
+                    preset_id: None,
+                    priority: 0,
+                    merge: crate::EffectMerge::Replace,
+                    direction: crate::EffectDirection::Forward,
+                };
+
+                // A new live effect replaces whatever was running on the same fixtures/channel
+                let mut programmer = self.programmer.write().await;
+                programmer.clear_effect_targets(&fixture_ids, &channel_type);
+                programmer.add_effect(effect_mapping);
+
+                let effects = Self::programmer_effects_summary(&programmer);
+                let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated { effects });
+            }
+            SetProgrammerEffectDirection {
+                effect_index,
+                direction,
+            } => {
+                self.checkpoint_programmer("effect direction", event_tx)
+                    .await;
+                let mut programmer = self.programmer.write().await;
+                if programmer.set_effect_direction(effect_index, direction) {
+                    let effects = Self::programmer_effects_summary(&programmer);
+                    let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated { effects });
+                }
             }
             ClearEffect {
-                fixture_ids: _,
-                channel_type: _,
+                fixture_ids,
+                channel_type,
             } => {
-                // TODO: Implement clear_effect method
+                self.checkpoint_programmer("clear effect", event_tx).await;
+                let channel_type = Self::channel_string_to_type(&channel_type);
+                let mut programmer = self.programmer.write().await;
+                if programmer.clear_effect_targets(&fixture_ids, &channel_type) {
+                    let effects = Self::programmer_effects_summary(&programmer);
+                    let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated { effects });
+                }
             }
 
             // Programmer
@@ -1443,6 +2329,7 @@ impl LightingConsole {
                 channel,
                 value,
             } => {
+                self.checkpoint_programmer("set value", event_tx).await;
                 // Convert channel string to ChannelType
                 let channel_type = Self::channel_string_to_type(&channel);
                 self.programmer
@@ -1461,6 +2348,243 @@ impl LightingConsole {
 
                 let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
             }
+            CopyFixtureParameters {
+                source_fixture_id,
+                target_fixture_ids,
+                families,
+            } => {
+                self.checkpoint_programmer("copy parameters", event_tx)
+                    .await;
+                let fixtures = self.fixtures.read().await;
+                let Some(source) = fixtures.iter().find(|f| f.id == source_fixture_id) else {
+                    let _ = event_tx.send(ConsoleEvent::Error {
+                        message: format!("Fixture {} not found", source_fixture_id),
+                    });
+                    return Ok(());
+                };
+
+                let mut programmer = self.programmer.write().await;
+                for channel in source.channels.iter().filter(|c| {
+                    families.is_empty()
+                        || families.contains(&PresetType::for_channel(&c.channel_type))
+                }) {
+                    for target in fixtures
+                        .iter()
+                        .filter(|f| f.id != source_fixture_id && target_fixture_ids.contains(&f.id))
+                    {
+                        if target
+                            .channels
+                            .iter()
+                            .any(|c| c.channel_type == channel.channel_type)
+                        {
+                            programmer.add_value(
+                                target.id,
+                                channel.channel_type.clone(),
+                                channel.value,
+                            );
+                        }
+                    }
+                }
+                drop(fixtures);
+
+                let values: Vec<(usize, String, u8)> = programmer
+                    .get_values()
+                    .iter()
+                    .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                    .collect();
+                let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+            }
+            AdjustProgrammerValue {
+                fixture_ids,
+                channel,
+                delta,
+            } => {
+                let channel_type = Self::channel_string_to_type(&channel);
+                let fixtures = self.fixtures.read().await;
+                let mut programmer = self.programmer.write().await;
+                let steps = programmer.encoder_steps(&channel_type, delta);
+                if steps != 0 {
+                    Self::checkpoint_locked_programmer(&mut programmer, "adjust value", event_tx);
+                    for fixture_id in &fixture_ids {
+                        // Start from the programmed value, or what the fixture is outputting
+                        let current =
+                            programmer
+                                .get_value(*fixture_id, &channel_type)
+                                .or_else(|| {
+                                    fixtures
+                                        .iter()
+                                        .find(|f| f.id == *fixture_id)
+                                        .and_then(|f| {
+                                            f.channels
+                                                .iter()
+                                                .find(|c| c.channel_type == channel_type)
+                                        })
+                                        .map(|c| c.value)
+                                });
+                        if let Some(current) = current {
+                            let value = (current as i32 + steps).clamp(0, 255) as u8;
+                            programmer.add_value(*fixture_id, channel_type.clone(), value);
+                        }
+                    }
+
+                    let values: Vec<(usize, String, u8)> = programmer
+                        .get_values()
+                        .iter()
+                        .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                        .collect();
+                    let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+                }
+            }
+            FanProgrammerValue {
+                fixture_ids,
+                channel,
+                start,
+                end,
+            } => {
+                self.checkpoint_programmer("fan", event_tx).await;
+                let channel_type = Self::channel_string_to_type(&channel);
+                let mut programmer = self.programmer.write().await;
+                programmer.fan_value(&fixture_ids, &channel_type, start, end);
+
+                let values: Vec<(usize, String, u8)> = programmer
+                    .get_values()
+                    .iter()
+                    .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                    .collect();
+                let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+            }
+            CaptureLiveOutput { fixture_ids } => {
+                self.checkpoint_programmer("capture", event_tx).await;
+                // Fixture channels hold the last frame's output: tracking, effects and preview
+                let fixtures = self.fixtures.read().await;
+                let mut programmer = self.programmer.write().await;
+                for fixture in fixtures.iter().filter(|f| fixture_ids.contains(&f.id)) {
+                    for channel in &fixture.channels {
+                        programmer.add_value(
+                            fixture.id,
+                            channel.channel_type.clone(),
+                            channel.value,
+                        );
+                    }
+                }
+
+                let values: Vec<(usize, String, u8)> = programmer
+                    .get_values()
+                    .iter()
+                    .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                    .collect();
+                let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+            }
+            SetProgrammerColor {
+                fixture_ids,
+                red,
+                green,
+                blue,
+            } => {
+                self.checkpoint_programmer("color", event_tx).await;
+                let fixtures = self.fixtures.read().await;
+                let mut programmer = self.programmer.write().await;
+                for fixture in fixtures.iter().filter(|f| fixture_ids.contains(&f.id)) {
+                    for (channel_type, value) in fixture.color_values([red, green, blue]) {
+                        programmer.add_value(fixture.id, channel_type, value);
+                    }
+                }
+
+                let values: Vec<(usize, String, u8)> = programmer
+                    .get_values()
+                    .iter()
+                    .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                    .collect();
+                let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+            }
+            SetEncoderSensitivity {
+                channel,
+                sensitivity,
+            } => {
+                let channel_type = Self::channel_string_to_type(&channel);
+                self.programmer
+                    .write()
+                    .await
+                    .set_encoder_sensitivity(channel_type, sensitivity.max(0.0));
+            }
+            MirrorProgrammerPositions {
+                fixture_ids,
+                mirror_tilt,
+            } => {
+                self.checkpoint_programmer("mirror", event_tx).await;
+                let fixtures = self.fixtures.read().await;
+                let mut ordered: Vec<&Fixture> = fixture_ids
+                    .iter()
+                    .filter_map(|id| fixtures.iter().find(|f| f.id == *id))
+                    .collect();
+                if ordered.iter().all(|f| f.position.is_some()) {
+                    ordered.sort_by(|a, b| {
+                        let ax = a.position.map(|p| p.x).unwrap_or_default();
+                        let bx = b.position.map(|p| p.x).unwrap_or_default();
+                        ax.total_cmp(&bx)
+                    });
+                }
+
+                let mut channels = vec![ChannelType::Pan];
+                if mirror_tilt {
+                    channels.push(ChannelType::Tilt);
+                }
+
+                // Pair fixtures from the outside in, the right one of each pair mirrors the left
+                let mut programmer = self.programmer.write().await;
+                let half = ordered.len() / 2;
+                for (left, right) in ordered
+                    .iter()
+                    .take(half)
+                    .zip(ordered.iter().rev().take(half))
+                {
+                    for channel_type in &channels {
+                        let value = programmer.get_value(left.id, channel_type).or_else(|| {
+                            left.channels
+                                .iter()
+                                .find(|c| c.channel_type == *channel_type)
+                                .map(|c| c.value)
+                        });
+                        if let Some(value) = value {
+                            programmer.add_value(right.id, channel_type.clone(), 255 - value);
+                        }
+                    }
+                }
+                drop(fixtures);
+
+                let values: Vec<(usize, String, u8)> = programmer
+                    .get_values()
+                    .iter()
+                    .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                    .collect();
+                let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+            }
+            KnockoutProgrammerValue {
+                fixture_ids,
+                channel,
+            } => {
+                let channel_type = Self::channel_string_to_type(&channel);
+                let mut programmer = self.programmer.write().await;
+                if programmer.targets(&fixture_ids, &channel_type) {
+                    Self::checkpoint_locked_programmer(&mut programmer, "knockout", event_tx);
+                }
+                let values_changed = programmer.remove_values(&fixture_ids, &channel_type);
+                // Any live effect on the knocked out channel stops for those fixtures too
+                let effects_changed = programmer.clear_effect_targets(&fixture_ids, &channel_type);
+
+                if values_changed {
+                    let values: Vec<(usize, String, u8)> = programmer
+                        .get_values()
+                        .iter()
+                        .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                        .collect();
+                    let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+                }
+                if effects_changed {
+                    let effects = Self::programmer_effects_summary(&programmer);
+                    let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated { effects });
+                }
+            }
             SetProgrammerPreviewMode { preview_mode } => {
                 self.programmer.write().await.set_preview_mode(preview_mode);
                 let programmer = self.programmer.read().await;
@@ -1470,61 +2594,259 @@ impl LightingConsole {
                     selected_fixtures,
                 });
             }
-            SetSelectedFixtures { fixture_ids } => {
+            SetProgrammerHighlight {
+                highlight,
+                lowlight,
+            } => {
                 self.programmer
                     .write()
                     .await
-                    .set_selected_fixtures(fixture_ids.clone());
-                let programmer = self.programmer.read().await;
-                let _ = event_tx.send(ConsoleEvent::ProgrammerStateUpdated {
-                    preview_mode: programmer.get_preview_mode(),
-                    selected_fixtures: fixture_ids,
+                    .set_highlight(highlight, lowlight);
+                let _ = event_tx.send(ConsoleEvent::ProgrammerHighlightChanged {
+                    highlight,
+                    lowlight,
                 });
             }
+            SetSelectedFixtures { fixture_ids } => {
+                self.checkpoint_programmer("select", event_tx).await;
+                let mut programmer = self.programmer.write().await;
+                programmer.set_selected_fixtures(fixture_ids);
+                Self::send_selection_state(&programmer, event_tx);
+            }
             AddSelectedFixture { fixture_id } => {
-                self.programmer
-                    .write()
-                    .await
-                    .add_selected_fixture(fixture_id);
-                let programmer = self.programmer.read().await;
-                let selected_fixtures = programmer.get_selected_fixtures().clone();
-                let _ = event_tx.send(ConsoleEvent::ProgrammerStateUpdated {
-                    preview_mode: programmer.get_preview_mode(),
-                    selected_fixtures,
-                });
+                self.checkpoint_programmer("select", event_tx).await;
+                let mut programmer = self.programmer.write().await;
+                programmer.add_selected_fixture(fixture_id);
+                Self::send_selection_state(&programmer, event_tx);
             }
             RemoveSelectedFixture { fixture_id } => {
-                self.programmer
-                    .write()
-                    .await
-                    .remove_selected_fixture(fixture_id);
-                let programmer = self.programmer.read().await;
-                let selected_fixtures = programmer.get_selected_fixtures().clone();
-                let _ = event_tx.send(ConsoleEvent::ProgrammerStateUpdated {
-                    preview_mode: programmer.get_preview_mode(),
-                    selected_fixtures,
+                self.checkpoint_programmer("select", event_tx).await;
+                let mut programmer = self.programmer.write().await;
+                programmer.remove_selected_fixture(fixture_id);
+                Self::send_selection_state(&programmer, event_tx);
+            }
+            SelectFixtureGroup { group_id, mode } => {
+                self.checkpoint_programmer("select", event_tx).await;
+                if let Some(group) = self.fixture_groups.iter().find(|g| g.id == group_id) {
+                    let mut programmer = self.programmer.write().await;
+                    programmer.select_group(group, mode);
+                    Self::send_selection_state(&programmer, event_tx);
+                } else {
+                    let _ = event_tx.send(ConsoleEvent::Error {
+                        message: format!("Fixture group {} not found", group_id),
+                    });
+                }
+            }
+            StoreFixtureGroup { name, fixture_ids } => {
+                let id = self.fixture_groups.iter().map(|g| g.id).max().unwrap_or(0) + 1;
+                self.fixture_groups
+                    .push(FixtureGroup::new(id, name, fixture_ids));
+                let _ = event_tx.send(ConsoleEvent::FixtureGroupsUpdated {
+                    groups: self.fixture_groups.clone(),
                 });
             }
-            ClearSelectedFixtures => {
-                self.programmer.write().await.clear_selected_fixtures();
-                let programmer = self.programmer.read().await;
-                let _ = event_tx.send(ConsoleEvent::ProgrammerStateUpdated {
-                    preview_mode: programmer.get_preview_mode(),
-                    selected_fixtures: Vec::new(),
+            DeleteFixtureGroup { group_id } => {
+                self.fixture_groups.retain(|g| g.id != group_id);
+                let _ = event_tx.send(ConsoleEvent::FixtureGroupsUpdated {
+                    groups: self.fixture_groups.clone(),
                 });
             }
+            StoreQuickSelect {
+                number,
+                fixture_ids,
+            } => {
+                self.quick_selects.retain(|q| q.number != number);
+                self.quick_selects.push(QuickSelect {
+                    number,
+                    fixture_ids,
+                });
+                self.quick_selects.sort_by_key(|q| q.number);
+                let _ = event_tx.send(ConsoleEvent::QuickSelectsUpdated {
+                    quick_selects: self.quick_selects.clone(),
+                });
+            }
+            RecallQuickSelect { number } => {
+                let Some(quick_select) = self.quick_selects.iter().find(|q| q.number == number)
+                else {
+                    let _ = event_tx.send(ConsoleEvent::Error {
+                        message: format!("Quick select {number} is empty"),
+                    });
+                    return Ok(());
+                };
+                let fixture_ids = quick_select.fixture_ids.clone();
+                self.checkpoint_programmer("select", event_tx).await;
+                let mut programmer = self.programmer.write().await;
+                programmer.set_selected_fixtures(fixture_ids);
+                Self::send_selection_state(&programmer, event_tx);
+            }
+            DeleteQuickSelect { number } => {
+                self.quick_selects.retain(|q| q.number != number);
+                let _ = event_tx.send(ConsoleEvent::QuickSelectsUpdated {
+                    quick_selects: self.quick_selects.clone(),
+                });
+            }
+            SetFixtureGridLayout { layout } => {
+                self.fixture_grid = layout;
+                let _ = event_tx.send(ConsoleEvent::FixtureGridLayoutUpdated {
+                    layout: self.fixture_grid.clone(),
+                });
+            }
+            SetMagicSheet { sheet } => {
+                self.magic_sheet = sheet;
+                let _ = event_tx.send(ConsoleEvent::MagicSheetUpdated {
+                    sheet: self.magic_sheet.clone(),
+                });
+            }
+            SetFlash {
+                fixture_ids,
+                active,
+            } => {
+                if active {
+                    self.flashed_fixtures.extend(fixture_ids);
+                } else {
+                    for fixture_id in fixture_ids {
+                        self.flashed_fixtures.remove(&fixture_id);
+                    }
+                }
+            }
+            SetSmoke { active } => {
+                self.smoke_active = active;
+                let _ = event_tx.send(ConsoleEvent::SmokeChanged { active });
+            }
+            StoreColorSwatch { name, color } => {
+                // Re-saving a name replaces its color so the palette stays consistent
+                match self.color_swatches.iter_mut().find(|s| s.name == name) {
+                    Some(swatch) => swatch.color = color,
+                    None => self.color_swatches.push(ColorSwatch { name, color }),
+                }
+                let _ = event_tx.send(ConsoleEvent::ColorSwatchesUpdated {
+                    swatches: self.color_swatches.clone(),
+                });
+            }
+            DeleteColorSwatch { index } => {
+                if index < self.color_swatches.len() {
+                    self.color_swatches.remove(index);
+                }
+                let _ = event_tx.send(ConsoleEvent::ColorSwatchesUpdated {
+                    swatches: self.color_swatches.clone(),
+                });
+            }
+            StepSelection { .. } | RestoreSelection => {
+                let mut programmer = self.programmer.write().await;
+                match command {
+                    StepSelection { forward } => {
+                        programmer.step_selection(forward);
+                    }
+                    _ => programmer.restore_selection(),
+                }
+                Self::send_selection_state(&programmer, event_tx);
+            }
+            ReverseSelectionOrder => {
+                self.checkpoint_programmer("select", event_tx).await;
+                let mut programmer = self.programmer.write().await;
+                programmer.reverse_selection();
+                Self::send_selection_state(&programmer, event_tx);
+            }
+            ClearSelectedFixtures => {
+                self.checkpoint_programmer("select", event_tx).await;
+                let mut programmer = self.programmer.write().await;
+                programmer.clear_selected_fixtures();
+                Self::send_selection_state(&programmer, event_tx);
+            }
             ClearProgrammer => {
+                self.checkpoint_programmer("clear", event_tx).await;
                 self.programmer.write().await.clear();
 
-                // Send empty programmer values to UI
+                // Send empty programmer values and effects to UI
                 let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values: Vec::new() });
+                let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated {
+                    effects: Vec::new(),
+                });
+            }
+            UndoProgrammer | RedoProgrammer => {
+                let mut programmer = self.programmer.write().await;
+                if matches!(command, UndoProgrammer) {
+                    if let Some(action) = programmer.undo() {
+                        log::info!("Undid programmer {}", action);
+                    }
+                } else if let Some(action) = programmer.redo() {
+                    log::info!("Redid programmer {action}");
+                }
+
+                let values: Vec<(usize, String, u8)> = programmer
+                    .get_values()
+                    .iter()
+                    .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                    .collect();
+                let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+                let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated {
+                    effects: Self::programmer_effects_summary(&programmer),
+                });
+                Self::send_selection_state(&programmer, event_tx);
+                let _ = event_tx.send(ConsoleEvent::ProgrammerUndoChanged {
+                    next_undo: programmer.undo_action().map(str::to_string),
+                    next_redo: programmer.redo_action().map(str::to_string),
+                });
+            }
+            ClearProgrammerStaged | ClearProgrammerFamily { .. } => {
+                self.checkpoint_programmer("clear", event_tx).await;
+                let mut programmer = self.programmer.write().await;
+                match command {
+                    ClearProgrammerFamily { family } => programmer.clear_family(&family),
+                    _ => match programmer.staged_clear() {
+                        Some(family) => log::info!("Cleared programmer {}", family.as_str()),
+                        None => log::info!("Cleared programmer"),
+                    },
+                }
+
+                let values: Vec<(usize, String, u8)> = programmer
+                    .get_values()
+                    .iter()
+                    .map(|v| (v.fixture_id, v.channel_type.to_string(), v.value))
+                    .collect();
+                let effects = Self::programmer_effects_summary(&programmer);
+                drop(programmer);
+
+                let _ = event_tx.send(ConsoleEvent::ProgrammerValuesUpdated { values });
+                let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated { effects });
             }
             RecordProgrammerToCue {
                 cue_name,
-                list_index: _,
+                list_index,
+                changed_only,
             } => {
-                // TODO: Implement record_programmer_to_cue method
-                println!("Recording programmer to cue: {}", cue_name);
+                let static_values = if changed_only {
+                    let tracked = self.tracking_state.read().await.get_static_values();
+                    self.programmer.read().await.changed_values(&tracked)
+                } else {
+                    self.programmer.read().await.get_values().clone()
+                };
+                let cue = Cue {
+                    id: 0, // Will be set by the cue manager
+                    name: cue_name.clone(),
+                    fade_time: Duration::ZERO,
+                    timecode: None,
+                    static_values,
+                    effects: self.programmer.read().await.get_effects().clone(),
+                    pixel_effects: Vec::new(),
+                    is_blocking: false,
+                    preset_references: Vec::new(),
+                };
+
+                let mut cue_manager = self.cue_manager.write().await;
+                let list_index = list_index.unwrap_or(cue_manager.get_current_cue_list_idx());
+                match cue_manager.add_cue(list_index, cue) {
+                    Ok(_) => {
+                        log::info!("Recorded programmer to cue {cue_name}");
+                        let cue_lists = cue_manager.get_cue_lists();
+                        let _ = event_tx.send(ConsoleEvent::CueListsUpdated { cue_lists });
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(ConsoleEvent::Error {
+                            message: format!("Failed to record cue: {e}"),
+                        });
+                    }
+                }
             }
             ApplyProgrammerEffect {
                 fixture_ids,
@@ -1537,7 +2859,10 @@ impl LightingConsole {
                 distribution,
                 step_value,
                 wave_offset,
+                spatial_axis,
+                spatial_spread,
             } => {
+                self.checkpoint_programmer("apply effect", event_tx).await;
                 // Convert string channel types to ChannelType enum
                 let channel_types_enum: Vec<halo_fixtures::ChannelType> = channel_types
                     .iter()
@@ -1556,6 +2881,14 @@ impl LightingConsole {
                     0 => crate::EffectDistribution::All,
                     1 => crate::EffectDistribution::Step(step_value.unwrap_or(1)),
                     2 => crate::EffectDistribution::Wave(wave_offset.unwrap_or(0.0) as f64),
+                    3 => crate::EffectDistribution::Linear {
+                        axis: spatial_axis.unwrap_or_default(),
+                        spread: spatial_spread.unwrap_or(1.0) as f64,
+                    },
+                    4 => crate::EffectDistribution::Radial {
+                        center: self.fixture_centroid(&fixture_ids).await,
+                        spread: spatial_spread.unwrap_or(1.0) as f64,
+                    },
                     _ => crate::EffectDistribution::All,
                 };
 
@@ -1571,7 +2904,9 @@ impl LightingConsole {
                         interval: interval_enum,
                         interval_ratio: ratio as f64,
                         phase: phase as f64,
+                        subdivision: None,
                     },
+                    chase: None,
                 };
 
                 // Create effect mapping
@@ -1582,11 +2917,21 @@ impl LightingConsole {
                     channel_types: channel_types_enum,
                     distribution: distribution_enum,
                     release: crate::EffectRelease::Hold,
+                    preset_id: None,
+                    priority: 0,
+                    merge: crate::EffectMerge::Replace,
+                    direction: crate::EffectDirection::Forward,
                 };
 
-                // Add to tracking state
-                let mut tracking_state = self.tracking_state.write().await;
-                tracking_state.add_effect(effect_mapping);
+                // Run it live from the programmer, replacing effects on the same channels
+                let mut programmer = self.programmer.write().await;
+                for channel_type in &effect_mapping.channel_types {
+                    programmer.clear_effect_targets(&effect_mapping.fixture_ids, channel_type);
+                }
+                programmer.add_effect(effect_mapping);
+
+                let effects = Self::programmer_effects_summary(&programmer);
+                let _ = event_tx.send(ConsoleEvent::ProgrammerEffectsUpdated { effects });
             }
 
             // Query commands
@@ -1618,6 +2963,7 @@ impl LightingConsole {
             QueryRhythmState => {
                 let rhythm_guard = self.rhythm_state.read().await;
                 let state = RhythmState {
+                    beat_time: rhythm_guard.beat_time,
                     beat_phase: rhythm_guard.beat_phase,
                     bar_phase: rhythm_guard.bar_phase,
                     phrase_phase: rhythm_guard.phrase_phase,
@@ -1663,10 +3009,56 @@ impl LightingConsole {
                 let num_peers = self.get_ableton_link_peers().await;
                 let _ = event_tx.send(ConsoleEvent::LinkStateChanged { enabled, num_peers });
             }
+            SetLinkQuantum { quantum } => {
+                self.link_manager.lock().await.set_quantum(quantum);
+                self.send_link_session(event_tx).await;
+            }
+            SetLinkStartStopSync { enabled } => {
+                if let Err(e) = self
+                    .link_manager
+                    .lock()
+                    .await
+                    .enable_start_stop_sync(enabled)
+                    .await
+                {
+                    let _ = event_tx.send(ConsoleEvent::Error {
+                        message: format!("Failed to set start/stop sync: {e}"),
+                    });
+                }
+                self.send_link_session(event_tx).await;
+            }
+            RealignLinkDownbeat => {
+                if let Err(e) = self.link_manager.lock().await.realign_downbeat().await {
+                    let _ = event_tx.send(ConsoleEvent::Error {
+                        message: format!("Failed to re-align downbeat: {e}"),
+                    });
+                }
+            }
 
             // Settings management
             UpdateSettings { settings } => {
                 log::info!("Updating settings");
+                if !settings.mtc_input_enabled {
+                    self.cue_manager.write().await.stop_chasing();
+                }
+                if !settings.mtc_output_enabled {
+                    let _ = self
+                        .module_manager
+                        .send_to_module(ModuleId::Midi, ModuleEvent::MtcOutput(None))
+                        .await;
+                }
+                // The profile or channel may have changed, so resend all feedback
+                self.midi_feedback_sent.clear();
+                if !settings.midi_clock_output_enabled {
+                    let event = ModuleEvent::MidiClockOutput {
+                        clock: None,
+                        playing: false,
+                    };
+                    let _ = self
+                        .module_manager
+                        .send_to_module(ModuleId::Midi, event)
+                        .await;
+                }
                 *self.settings.write().await = settings.clone();
                 let _ = event_tx.send(ConsoleEvent::SettingsUpdated { settings });
             }
@@ -1720,6 +3112,12 @@ impl LightingConsole {
                 let mut pixel_engine = self.pixel_engine.write().await;
                 pixel_engine.clear_effects();
             }
+            SetNodeDiscovery { enabled } => {
+                let _ = self
+                    .module_manager
+                    .send_to_module(ModuleId::Dmx, ModuleEvent::NodeDiscovery(enabled))
+                    .await;
+            }
         }
 
         Ok(())
@@ -1734,7 +3132,10 @@ impl LightingConsole {
         log::info!("Console run_with_channels starting...");
 
         // Start the update loop
-        let mut update_interval = tokio::time::interval(std::time::Duration::from_millis(23)); // ~44Hz
+        let update_period = std::time::Duration::from_millis(23); // ~44Hz
+        let mut update_interval = tokio::time::interval(update_period);
+        let mut performance = PerformanceTracker::new(update_period);
+        let mut dmx_fps = 0.0;
         log::info!("Starting console main loop...");
 
         loop {
@@ -1760,6 +3161,7 @@ impl LightingConsole {
 
                 // Regular update tick
                 _ = update_interval.tick() => {
+                    let update_started = std::time::Instant::now();
                     let pixel_data = match self.update().await {
                         Ok(data) => data,
                         Err(e) => {
@@ -1775,15 +3177,29 @@ impl LightingConsole {
                     if let Some(timecode) = self.cue_manager.read().await.current_timecode {
                         let _ = event_tx.send(ConsoleEvent::TimecodeUpdated { timecode });
                     }
+                    let (mtc_output, clock_output) = {
+                        let settings = self.settings.read().await;
+                        (settings.mtc_output_enabled, settings.midi_clock_output_enabled)
+                    };
+                    if mtc_output {
+                        self.send_mtc_output().await;
+                    }
+                    if clock_output {
+                        self.send_midi_clock_output().await;
+                    }
+                    self.send_midi_feedback().await;
 
                     // Send current cue information
                     let cue_manager = self.cue_manager.read().await;
                     let cue_index = cue_manager.get_current_cue_idx().unwrap_or(0);
                     let progress = cue_manager.get_current_cue_progress();
                     let _ = event_tx.send(ConsoleEvent::CurrentCueChanged { cue_index, progress });
+                    let preview = cue_manager.next_cue_preview();
+                    let _ = event_tx.send(ConsoleEvent::NextCuePreviewUpdated { preview });
 
                     let rhythm_guard = self.rhythm_state.read().await;
                     let rhythm_state = RhythmState {
+                        beat_time: rhythm_guard.beat_time,
                         beat_phase: rhythm_guard.beat_phase,
                         bar_phase: rhythm_guard.bar_phase,
                         phrase_phase: rhythm_guard.phrase_phase,
@@ -1793,11 +3209,40 @@ impl LightingConsole {
                         tap_count: rhythm_guard.tap_count,
                     };
                     let _ = event_tx.send(ConsoleEvent::RhythmStateUpdated { state: rhythm_state });
+                    drop(rhythm_guard);
+
+                    // Link phase moves every tick, so the session panel needs it as often as the beat
+                    if self.link_manager.lock().await.is_enabled() {
+                        self.send_link_session(&event_tx).await;
+                    }
 
                     // Send tracking state information
                     let tracking_state = self.tracking_state.read().await;
                     let active_effect_count = tracking_state.active_effect_count();
                     let _ = event_tx.send(ConsoleEvent::TrackingStateUpdated { active_effect_count });
+
+                    let changed: Vec<(usize, String)> = self
+                        .programmer
+                        .read()
+                        .await
+                        .changed_values(&tracking_state.get_static_values())
+                        .iter()
+                        .map(|v| (v.fixture_id, v.channel_type.to_string()))
+                        .collect();
+                    drop(tracking_state);
+                    if changed != self.programmer_changed {
+                        self.programmer_changed = changed.clone();
+                        let _ = event_tx.send(ConsoleEvent::ProgrammerChangedValuesUpdated { changed });
+                    }
+
+                    performance.record_update(update_started, update_started.elapsed());
+                    if let Some(mut metrics) = performance.take_report(std::time::Instant::now()) {
+                        metrics.command_queue_depth = command_rx.len();
+                        metrics.module_queue_depth =
+                            self.message_rx.as_ref().map(|rx| rx.len()).unwrap_or(0);
+                        metrics.dmx_fps = dmx_fps;
+                        let _ = event_tx.send(ConsoleEvent::PerformanceMetricsUpdated { metrics });
+                    }
                 }
 
                 // Process module messages (if available)
@@ -1813,7 +3258,21 @@ impl LightingConsole {
                         ModuleMessage::Event(event) => {
                             match event {
                                 ModuleEvent::MidiInput(midi_msg) => {
-                                    Self::handle_midi_input(midi_msg, &self.rhythm_state, &self.cue_manager).await;
+                                    // Learned mappings, then the controller profile
+                                    if self.handle_mapped_midi(&midi_msg, &event_tx).await {
+                                        continue;
+                                    }
+                                    Self::handle_midi_input(midi_msg);
+                                }
+                                ModuleEvent::SmpteSync { timecode } => {
+                                    let settings = self.settings.read().await;
+                                    if settings.mtc_input_enabled {
+                                        self.cue_manager.write().await.chase_timecode(
+                                            timecode,
+                                            settings.mtc_offset_secs,
+                                            Duration::from_secs_f64(settings.mtc_freewheel_secs.max(0.0)),
+                                        );
+                                    }
                                 }
                                 _ => {
                                     // Handle other inter-module events as needed
@@ -1828,6 +3287,10 @@ impl LightingConsole {
                             // Send error to UI
                             let _ = event_tx.send(ConsoleEvent::Error { message: error });
                         }
+                        ModuleMessage::NetworkStatus(status) => {
+                            dmx_fps = status.output_fps;
+                            let _ = event_tx.send(ConsoleEvent::NetworkStatusUpdated { status });
+                        }
                     }
                 }
             }
@@ -2017,6 +3480,7 @@ impl SyncLightingConsole {
                 pixel_effects: vec![],
                 timecode: None,
                 is_blocking: false,
+                preset_references: vec![],
             };
 
             cue_manager
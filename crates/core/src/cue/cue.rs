@@ -1,9 +1,12 @@
 use std::time::Duration;
 
-use halo_fixtures::ChannelType;
+use halo_fixtures::{ChannelType, FixturePosition};
 use serde::{Deserialize, Serialize};
 
-use crate::{Effect, EffectRelease, PixelEffect};
+use crate::effect::effect::get_effect_phase;
+use crate::{
+    Effect, EffectDirection, EffectMerge, EffectRelease, PixelEffect, PresetType, RhythmState,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CueList {
@@ -26,6 +29,9 @@ pub struct Cue {
     pub timecode: Option<String>,
     // A blocking cue prevents level changes from tracking through it and successive cues.
     pub is_blocking: bool,
+    // Presets referenced by this cue, resolved into values when the cue plays
+    #[serde(default)]
+    pub preset_references: Vec<PresetReference>,
 }
 
 impl Default for Cue {
@@ -40,10 +46,28 @@ impl Default for Cue {
             effects: vec![],
             pixel_effects: vec![],
             is_blocking: false,
+            preset_references: vec![],
         }
     }
 }
 
+/// A reference from a cue to a stored preset
+/// Values are looked up when the cue plays, so editing the preset updates every cue using it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresetReference {
+    pub preset_type: PresetType,
+    pub preset_id: usize,
+    /// Restrict the preset to one of its fixture groups
+    #[serde(default)]
+    pub fixture_group_id: Option<usize>,
+    /// Explicit fixtures to apply the preset to; takes precedence over fixture groups
+    #[serde(default)]
+    pub fixture_ids: Vec<usize>,
+    /// Per-fixture values that replace the preset's values
+    #[serde(default)]
+    pub overrides: Vec<StaticValue>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StaticValue {
     pub fixture_id: usize,
@@ -60,6 +84,18 @@ pub struct EffectMapping {
     pub distribution: EffectDistribution,
     #[serde(default)]
     pub release: EffectRelease,
+    /// Effect preset this mapping was created from; preset edits are copied into the mapping
+    #[serde(default)]
+    pub preset_id: Option<usize>,
+    /// Higher priority effects are applied on top of lower ones
+    #[serde(default)]
+    pub priority: i32,
+    /// How this effect combines with the value underneath it
+    #[serde(default)]
+    pub merge: EffectMerge,
+    /// Which way the effect travels across its fixtures
+    #[serde(default)]
+    pub direction: EffectDirection,
 }
 
 impl<'de> Deserialize<'de> for EffectMapping {
@@ -77,6 +113,14 @@ impl<'de> Deserialize<'de> for EffectMapping {
             distribution: EffectDistribution,
             #[serde(default)]
             release: EffectRelease,
+            #[serde(default)]
+            preset_id: Option<usize>,
+            #[serde(default)]
+            priority: i32,
+            #[serde(default)]
+            merge: EffectMerge,
+            #[serde(default)]
+            direction: EffectDirection,
         }
 
         #[derive(Deserialize)]
@@ -100,15 +144,156 @@ impl<'de> Deserialize<'de> for EffectMapping {
             channel_types,
             distribution: helper.distribution,
             release: helper.release,
+            preset_id: helper.preset_id,
+            priority: helper.priority,
+            merge: helper.merge,
+            direction: helper.direction,
         })
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl EffectMapping {
+    /// The effect's phase at this point in the rhythm, running in the mapping's direction
+    pub fn phase(&self, rhythm: &RhythmState) -> f64 {
+        self.direction
+            .apply(get_effect_phase(rhythm, &self.effect.params))
+    }
+
+    /// Level (0.0 to 1.0) of each fixture at the given phase, in `fixture_ids` order
+    ///
+    /// Chases define their own per-fixture levels, so distribution doesn't apply to them.
+    /// Positions are only looked up for position-based distributions.
+    pub fn levels(
+        &self,
+        phase: f64,
+        position_of: impl Fn(usize) -> Option<FixturePosition>,
+    ) -> Vec<f64> {
+        let fixture_ids = &self.fixture_ids;
+        if let Some(chase) = &self.effect.chase {
+            return fixture_ids
+                .iter()
+                .map(|id| chase.level(phase, *id))
+                .collect();
+        }
+
+        match &self.distribution {
+            // Same value for all fixtures
+            EffectDistribution::All => vec![self.effect.apply(phase); fixture_ids.len()],
+            EffectDistribution::Step(step_size) => (0..fixture_ids.len())
+                .map(|idx| {
+                    let step_phase = (phase + (idx / step_size) as f64) % 1.0;
+                    self.effect.apply(step_phase)
+                })
+                .collect(),
+            // Phase offset per fixture
+            EffectDistribution::Wave(phase_offset) => (0..fixture_ids.len())
+                .map(|idx| {
+                    let wave_phase = (phase + idx as f64 * phase_offset) % 1.0;
+                    self.effect.apply(wave_phase)
+                })
+                .collect(),
+            // Phase offsets from fixture positions
+            distribution @ (EffectDistribution::Linear { .. }
+            | EffectDistribution::Radial { .. }) => {
+                let positions: Vec<_> = fixture_ids.iter().map(|id| position_of(*id)).collect();
+                let offsets = distribution.spatial_offsets(&positions).unwrap_or_default();
+                offsets
+                    .into_iter()
+                    .map(|offset| self.effect.apply((phase + offset).rem_euclid(1.0)))
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub enum EffectDistribution {
+    #[default]
     All,
     Step(usize),
     Wave(f64), // Phase offset between fixtures
+    /// Phase travels along an axis of the fixtures' stage positions
+    /// Spread is the number of cycles across the fixtures, end to end
+    Linear {
+        axis: SpatialAxis,
+        spread: f64,
+    },
+    /// Phase ripples outward from a point on stage
+    /// Spread is the number of cycles out to the furthest fixture
+    Radial {
+        center: FixturePosition,
+        spread: f64,
+    },
+}
+
+impl EffectDistribution {
+    /// Phase offsets (in cycles) for each fixture of a position-based distribution
+    /// If any fixture has no position the whole group is spread in list order instead
+    /// Returns None for index-based distributions
+    pub fn spatial_offsets(&self, positions: &[Option<FixturePosition>]) -> Option<Vec<f64>> {
+        let placed: Option<Vec<FixturePosition>> = positions.iter().copied().collect();
+        let (distances, spread): (Vec<f64>, f64) = match (self, placed) {
+            (
+                EffectDistribution::Linear { spread, .. }
+                | EffectDistribution::Radial { spread, .. },
+                None,
+            ) => (
+                (0..positions.len()).map(|idx| idx as f64).collect(),
+                *spread,
+            ),
+            (EffectDistribution::Linear { axis, spread }, Some(placed)) => {
+                let along: Vec<f64> = placed.iter().map(|p| axis.component(p)).collect();
+                let start = along.iter().copied().fold(f64::INFINITY, f64::min);
+                (along.iter().map(|d| d - start).collect(), *spread)
+            }
+            (EffectDistribution::Radial { center, spread }, Some(placed)) => {
+                (placed.iter().map(|p| p.distance(center)).collect(), *spread)
+            }
+            _ => return None,
+        };
+
+        // Negative offsets make the wave travel along the axis (or outward) as phase advances
+        let span = distances.iter().copied().fold(0.0, f64::max);
+        Some(
+            distances
+                .iter()
+                .map(|d| if span > 0.0 { -d / span * spread } else { 0.0 })
+                .collect(),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpatialAxis {
+    /// Stage left to right
+    #[default]
+    X,
+    /// Downstage to upstage
+    Y,
+    /// Floor to ceiling
+    Z,
+}
+
+impl SpatialAxis {
+    pub fn all() -> [SpatialAxis; 3] {
+        [SpatialAxis::X, SpatialAxis::Y, SpatialAxis::Z]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpatialAxis::X => "Left → Right",
+            SpatialAxis::Y => "Front → Back",
+            SpatialAxis::Z => "Bottom → Top",
+        }
+    }
+
+    pub fn component(&self, position: &FixturePosition) -> f64 {
+        match self {
+            SpatialAxis::X => position.x,
+            SpatialAxis::Y => position.y,
+            SpatialAxis::Z => position.z,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -120,3 +305,36 @@ pub struct PixelEffectMapping {
     #[serde(default)]
     pub release: EffectRelease,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_x(x: f64) -> Option<FixturePosition> {
+        Some(FixturePosition {
+            x,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_spatial_offsets_partly_positioned_group_uses_list_order() {
+        let distribution = EffectDistribution::Linear {
+            axis: SpatialAxis::X,
+            spread: 1.0,
+        };
+
+        let positioned = distribution
+            .spatial_offsets(&[at_x(0.0), at_x(10.0), at_x(2.5)])
+            .unwrap();
+        assert_eq!(positioned, vec![0.0, -1.0, -0.25]);
+
+        // One missing position would otherwise land at x = 1, inside the measured extent
+        let partly = distribution
+            .spatial_offsets(&[at_x(0.0), None, at_x(10.0)])
+            .unwrap();
+        assert_eq!(partly, vec![0.0, -0.5, -1.0]);
+
+        assert_eq!(EffectDistribution::All.spatial_offsets(&[None]), None);
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::HashSet;
 use std::time::{Duration, Instant};
 
-use crate::{Cue, CueList, EffectMapping, PixelEffectMapping, StaticValue, TimeCode};
+use crate::{
+    Cue, CueList, EffectMapping, EffectPreset, EffectPresetType, PixelEffectMapping,
+    PresetReference, PresetType, StaticValue, TimeCode,
+};
 
 #[derive(Clone, Copy, PartialEq, Debug, Default)]
 pub enum PlaybackState {
@@ -10,6 +14,31 @@ pub enum PlaybackState {
     Holding,
 }
 
+/// What the next Go or timecode trigger will play, for the dashboard's next-cue pane
+#[derive(Clone, Debug, PartialEq)]
+pub struct NextCuePreview {
+    pub list_index: usize,
+    pub cue_index: usize,
+    pub name: String,
+    pub fade_time: Duration,
+    pub timecode: Option<String>,
+    /// Seconds until the cue's timecode is reached, only for timecoded cues
+    pub seconds_until: Option<f64>,
+    /// Fixtures named by the cue's values, effects and preset references
+    pub fixture_count: usize,
+    pub effect_count: usize,
+}
+
+/// Timecode received from an external source such as MTC, which playback chases
+#[derive(Clone, Copy, Debug)]
+struct ChasedTimecode {
+    seconds: f64,
+    frame_rate: u8,
+    received: Instant,
+    /// How long to keep running on after the source stops before holding
+    freewheel: Duration,
+}
+
 pub struct CueManager {
     cue_lists: Vec<CueList>,
     current_cue_list: usize,
@@ -21,6 +50,7 @@ pub struct CueManager {
     pub show_elapsed_time: f64,
     /// Current timecode
     pub current_timecode: Option<TimeCode>,
+    chased_timecode: Option<ChasedTimecode>,
     /// Current Cue start time reference point
     current_cue_start_time: Option<Instant>,
     /// Current elapsed time in seconds
@@ -44,6 +74,7 @@ impl CueManager {
             show_start_time: None,
             show_elapsed_time: 0.0,
             current_timecode: None,
+            chased_timecode: None,
             current_cue_start_time: None,
             current_cue_elapsed_time: 0.0,
             last_update: Instant::now(),
@@ -54,6 +85,10 @@ impl CueManager {
 
     pub fn update(&mut self) {
         if self.playback_state != PlaybackState::Playing {
+            // An external source keeps the timecode moving while stopped
+            if self.chased_timecode.is_some() {
+                self.update_timecode();
+            }
             return;
         }
 
@@ -97,10 +132,57 @@ impl CueManager {
     }
 
     pub fn update_timecode(&mut self) {
+        if let Some(chased) = self.chased_timecode {
+            // Run on between messages and through dropouts, up to the freewheel time
+            let running = chased.received.elapsed().min(chased.freewheel);
+            self.current_timecode = Some(TimeCode::from_seconds(
+                chased.seconds + running.as_secs_f64(),
+                chased.frame_rate,
+            ));
+            return;
+        }
+
         // Using 30fps as default
         self.current_timecode = Some(TimeCode::from_seconds(self.show_elapsed_time, 30));
     }
 
+    /// Follow timecode from an external source instead of the show clock
+    ///
+    /// `offset_seconds` is added to the received timecode, so a rig starting at 01:00:00:00 can
+    /// fire cues timed from zero with an offset of -3600.
+    pub fn chase_timecode(&mut self, timecode: TimeCode, offset_seconds: f64, freewheel: Duration) {
+        let seconds = (timecode.to_seconds() + offset_seconds).max(0.0);
+
+        // The source jumped back, so pick up from the cue that should be active there
+        let relocated = self
+            .current_timecode
+            .is_some_and(|current| seconds + 1.0 < current.to_seconds());
+
+        self.chased_timecode = Some(ChasedTimecode {
+            seconds,
+            frame_rate: timecode.frame_rate,
+            received: Instant::now(),
+            freewheel,
+        });
+        self.update_timecode();
+
+        if relocated && self.playback_state == PlaybackState::Playing {
+            let target = TimeCode::from_seconds(seconds, timecode.frame_rate);
+            if let Some(cue_index) = self.find_cue_by_timecode(&target) {
+                let _ = self.go_to_cue(self.current_cue_list, cue_index);
+            }
+        }
+    }
+
+    pub fn is_chasing(&self) -> bool {
+        self.chased_timecode.is_some()
+    }
+
+    /// Go back to timing timecoded cues from the show clock
+    pub fn stop_chasing(&mut self) {
+        self.chased_timecode = None;
+    }
+
     pub fn set_cue_lists(&mut self, cue_lists: Vec<CueList>) {
         self.cue_lists = cue_lists;
     }
@@ -114,6 +196,11 @@ impl CueManager {
         self.cue_lists.clone()
     }
 
+    /// Number of cues in each cue list, without cloning the lists
+    pub fn cue_list_lengths(&self) -> Vec<usize> {
+        self.cue_lists.iter().map(|list| list.cues.len()).collect()
+    }
+
     pub fn get_cue_list(&self, index: usize) -> Option<&CueList> {
         self.cue_lists.get(index)
     }
@@ -208,6 +295,162 @@ impl CueManager {
         }
     }
 
+    pub fn add_cue_effect(
+        &mut self,
+        cue_list_idx: usize,
+        cue_idx: usize,
+        effect: EffectMapping,
+    ) -> Result<(), String> {
+        self.cue_lists
+            .get_mut(cue_list_idx)
+            .ok_or_else(|| "Invalid cue list index".to_string())?
+            .cues
+            .get_mut(cue_idx)
+            .ok_or_else(|| "Invalid cue index".to_string())?
+            .effects
+            .push(effect);
+        Ok(())
+    }
+
+    /// Set values in a cue, replacing any existing value for the same fixture channel
+    pub fn set_cue_static_values(
+        &mut self,
+        cue_list_idx: usize,
+        cue_idx: usize,
+        values: Vec<StaticValue>,
+    ) -> Result<(), String> {
+        let cue = self
+            .cue_lists
+            .get_mut(cue_list_idx)
+            .ok_or_else(|| "Invalid cue list index".to_string())?
+            .cues
+            .get_mut(cue_idx)
+            .ok_or_else(|| "Invalid cue index".to_string())?;
+        for value in values {
+            match cue
+                .static_values
+                .iter_mut()
+                .find(|v| v.fixture_id == value.fixture_id && v.channel_type == value.channel_type)
+            {
+                Some(existing) => existing.value = value.value,
+                None => cue.static_values.push(value),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add_cue_preset_reference(
+        &mut self,
+        cue_list_idx: usize,
+        cue_idx: usize,
+        reference: PresetReference,
+    ) -> Result<(), String> {
+        self.cue_lists
+            .get_mut(cue_list_idx)
+            .ok_or_else(|| "Invalid cue list index".to_string())?
+            .cues
+            .get_mut(cue_idx)
+            .ok_or_else(|| "Invalid cue index".to_string())?
+            .preset_references
+            .push(reference);
+        Ok(())
+    }
+
+    pub fn remove_cue_preset_reference(
+        &mut self,
+        cue_list_idx: usize,
+        cue_idx: usize,
+        reference_idx: usize,
+    ) -> Result<(), String> {
+        let references = &mut self
+            .cue_lists
+            .get_mut(cue_list_idx)
+            .ok_or_else(|| "Invalid cue list index".to_string())?
+            .cues
+            .get_mut(cue_idx)
+            .ok_or_else(|| "Invalid cue index".to_string())?
+            .preset_references;
+        if reference_idx >= references.len() {
+            return Err("Invalid preset reference index".to_string());
+        }
+        references.remove(reference_idx);
+        Ok(())
+    }
+
+    /// Drop every cue reference to a deleted preset
+    pub fn remove_preset_references(&mut self, preset_type: &PresetType, preset_id: usize) {
+        for cue in self
+            .cue_lists
+            .iter_mut()
+            .flat_map(|list| list.cues.iter_mut())
+        {
+            cue.preset_references
+                .retain(|r| !(r.preset_type == *preset_type && r.preset_id == preset_id));
+        }
+    }
+
+    /// Copy an edited effect preset into every cue effect that references it
+    /// Returns the number of effects updated
+    pub fn sync_effect_preset(&mut self, preset: &EffectPreset) -> usize {
+        let EffectPresetType::Standard(effect) = &preset.effect else {
+            return 0;
+        };
+
+        let mut updated = 0;
+        for cue in self
+            .cue_lists
+            .iter_mut()
+            .flat_map(|list| list.cues.iter_mut())
+        {
+            for mapping in cue.effects.iter_mut() {
+                if mapping.preset_id == Some(preset.id) {
+                    mapping.effect = effect.clone();
+                    mapping.distribution = preset.distribution.clone();
+                    mapping.channel_types = preset.target_channels();
+                    updated += 1;
+                }
+            }
+        }
+        updated
+    }
+
+    /// Remove references to a deleted effect preset, leaving the effects in place
+    pub fn detach_effect_preset(&mut self, preset_id: usize) {
+        for cue in self
+            .cue_lists
+            .iter_mut()
+            .flat_map(|list| list.cues.iter_mut())
+        {
+            for mapping in cue.effects.iter_mut() {
+                if mapping.preset_id == Some(preset_id) {
+                    mapping.preset_id = None;
+                }
+            }
+        }
+    }
+
+    pub fn update_cue_effect(
+        &mut self,
+        cue_list_idx: usize,
+        cue_idx: usize,
+        effect_idx: usize,
+        effect: EffectMapping,
+    ) -> Result<(), String> {
+        let cue = self
+            .cue_lists
+            .get_mut(cue_list_idx)
+            .ok_or_else(|| "Invalid cue list index".to_string())?
+            .cues
+            .get_mut(cue_idx)
+            .ok_or_else(|| "Invalid cue index".to_string())?;
+        let slot = cue
+            .effects
+            .get_mut(effect_idx)
+            .ok_or_else(|| "Invalid effect index".to_string())?;
+        *slot = effect;
+        Ok(())
+    }
+
     pub fn remove_cue(&mut self, cue_list_idx: usize, cue_idx: usize) -> Result<(), String> {
         if cue_list_idx >= self.cue_lists.len() {
             return Err("Invalid cue list index".to_string());
@@ -274,6 +517,54 @@ impl CueManager {
         None
     }
 
+    /// The next timecoded cue with its countdown, or else the cue after the current one
+    pub fn next_cue_preview(&self) -> Option<NextCuePreview> {
+        let cue_list = self.get_current_cue_list()?;
+        let (cue_index, seconds_until) = match self.get_next_timecode_cue() {
+            Some((index, timecode)) => {
+                let now = self
+                    .current_timecode
+                    .as_ref()
+                    .map_or(self.show_elapsed_time, |tc| tc.to_seconds());
+                (index, Some((timecode.to_seconds() - now).max(0.0)))
+            }
+            None => (self.current_cue + 1, None),
+        };
+        let cue = cue_list.cues.get(cue_index)?;
+
+        let fixture_ids: HashSet<usize> = cue
+            .static_values
+            .iter()
+            .map(|value| value.fixture_id)
+            .chain(
+                cue.effects
+                    .iter()
+                    .flat_map(|e| e.fixture_ids.iter().copied()),
+            )
+            .chain(
+                cue.pixel_effects
+                    .iter()
+                    .flat_map(|e| e.fixture_ids.iter().copied()),
+            )
+            .chain(
+                cue.preset_references
+                    .iter()
+                    .flat_map(|r| r.fixture_ids.iter().copied()),
+            )
+            .collect();
+
+        Some(NextCuePreview {
+            list_index: self.current_cue_list,
+            cue_index,
+            name: cue.name.clone(),
+            fade_time: cue.fade_time,
+            timecode: cue.timecode.clone(),
+            seconds_until,
+            fixture_count: fixture_ids.len(),
+            effect_count: cue.effects.len() + cue.pixel_effects.len(),
+        })
+    }
+
     pub fn go(&mut self) -> Result<&Cue, String> {
         // Audio playback is now handled by the audio module
         self.go_to_next_cue()
@@ -428,6 +719,7 @@ impl CueManager {
                 pixel_effects,
                 timecode: None,
                 is_blocking: false,
+                preset_references: vec![],
             });
         }
     }
@@ -506,6 +798,7 @@ impl Clone for CueManager {
             show_start_time: self.show_start_time,
             show_elapsed_time: self.show_elapsed_time,
             current_timecode: self.current_timecode.clone(),
+            chased_timecode: self.chased_timecode,
             current_cue_start_time: self.current_cue_start_time,
             current_cue_elapsed_time: self.current_cue_elapsed_time,
             last_update: self.last_update,
@@ -514,3 +807,51 @@ impl Clone for CueManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use halo_fixtures::ChannelType;
+
+    use super::*;
+    use crate::{Effect, EffectRelease};
+
+    #[test]
+    fn test_sync_effect_preset_updates_channels() {
+        let mut preset =
+            EffectPreset::new_standard(3, "Pulse".to_string(), vec![], Effect::default());
+        let linked = EffectMapping {
+            name: "Pulse".to_string(),
+            effect: Effect::default(),
+            fixture_ids: vec![1, 2],
+            channel_types: preset.target_channels(),
+            distribution: Default::default(),
+            release: EffectRelease::Hold,
+            preset_id: Some(3),
+            priority: 0,
+            merge: Default::default(),
+            direction: Default::default(),
+        };
+        let unlinked = EffectMapping {
+            preset_id: None,
+            ..linked.clone()
+        };
+        let mut cue_manager = CueManager::new(vec![CueList {
+            name: "Main".to_string(),
+            cues: vec![Cue {
+                effects: vec![linked, unlinked],
+                ..Default::default()
+            }],
+            audio_file: None,
+        }]);
+
+        preset.channel_types = vec![ChannelType::Red, ChannelType::Blue];
+        assert_eq!(cue_manager.sync_effect_preset(&preset), 1);
+
+        let effects = &cue_manager.get_cue_lists()[0].cues[0].effects;
+        assert_eq!(
+            effects[0].channel_types,
+            vec![ChannelType::Red, ChannelType::Blue]
+        );
+        assert_eq!(effects[1].channel_types, vec![ChannelType::Dimmer]);
+    }
+}
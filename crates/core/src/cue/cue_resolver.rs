@@ -2,7 +2,7 @@ use halo_fixtures::ChannelType;
 
 use crate::{
     Cue, EffectDistribution, EffectMapping, FixtureGroup, PixelEffectMapping, Preset,
-    PresetLibrary, StaticValue,
+    PresetLibrary, PresetReference, StaticValue,
 };
 
 /// Resolves cue preset references into concrete static values and effects
@@ -27,11 +27,7 @@ impl<'a> CueResolver<'a> {
 
         // Process each preset reference
         for preset_ref in &cue.preset_references {
-            if let Some(preset) = self
-                .preset_library
-                .get_preset(&preset_ref.preset_type, preset_ref.preset_id)
-            {
-                let resolved = self.resolve_preset_reference(preset_ref, &preset);
+            if let Some(resolved) = self.resolve_reference(preset_ref) {
                 static_values.extend(resolved.static_values);
                 effects.extend(resolved.effects);
                 pixel_effects.extend(resolved.pixel_effects);
@@ -57,10 +53,17 @@ impl<'a> CueResolver<'a> {
         }
     }
 
-    /// Resolve a single preset reference
+    /// Resolve a single preset reference, or None if the preset no longer exists
+    pub fn resolve_reference(&self, preset_ref: &PresetReference) -> Option<ResolvedCue> {
+        self.preset_library
+            .get_preset(&preset_ref.preset_type, preset_ref.preset_id)
+            .map(|preset| self.resolve_preset_reference(preset_ref, &preset))
+    }
+
+    /// Resolve a single preset reference against a preset
     fn resolve_preset_reference(
         &self,
-        preset_ref: &crate::cue::cue::PresetReference,
+        preset_ref: &PresetReference,
         preset: &Preset,
     ) -> ResolvedCue {
         let mut static_values = Vec::new();
@@ -68,7 +71,11 @@ impl<'a> CueResolver<'a> {
         let mut pixel_effects = Vec::new();
 
         // Get the fixtures to apply this preset to
-        let target_fixtures = self.get_target_fixtures(preset, preset_ref.fixture_group_id);
+        let target_fixtures = if preset_ref.fixture_ids.is_empty() {
+            self.get_target_fixtures(preset, preset_ref.fixture_group_id)
+        } else {
+            preset_ref.fixture_ids.clone()
+        };
 
         // Resolve based on preset type
         match preset {
@@ -125,16 +132,17 @@ impl<'a> CueResolver<'a> {
                 // For effect presets, create effect mappings for target fixtures
                 match &effect_preset.effect {
                     crate::preset::preset::EffectPresetType::Standard(effect) => {
-                        // Get all relevant channel types from the effect
-                        // For now, we'll apply to Dimmer as a default
-                        // This could be expanded based on effect configuration
                         effects.push(EffectMapping {
                             name: format!("Preset: {}", effect_preset.name),
                             effect: effect.clone(),
                             fixture_ids: target_fixtures.clone(),
-                            channel_types: vec![ChannelType::Dimmer],
-                            distribution: EffectDistribution::All,
+                            channel_types: effect_preset.target_channels(),
+                            distribution: effect_preset.distribution.clone(),
                             release: crate::EffectRelease::Hold,
+                            preset_id: Some(effect_preset.id),
+                            priority: 0,
+                            merge: Default::default(),
+                            direction: Default::default(),
                         });
                     }
                     crate::preset::preset::EffectPresetType::Pixel(pixel_effect) => {
@@ -150,6 +158,23 @@ impl<'a> CueResolver<'a> {
             }
         }
 
+        // Fixtures stored with their own values get those instead of the shared ones,
+        // channel by channel so shared channels the fixture didn't store still apply
+        for own in preset
+            .fixture_values()
+            .iter()
+            .filter(|v| target_fixtures.contains(&v.fixture_id))
+        {
+            if let Some(existing) = static_values
+                .iter_mut()
+                .find(|sv| sv.fixture_id == own.fixture_id && sv.channel_type == own.channel_type)
+            {
+                existing.value = own.value;
+            } else {
+                static_values.push(own.clone());
+            }
+        }
+
         // Apply overrides
         for override_val in &preset_ref.overrides {
             // Find and replace the static value for this fixture/channel
@@ -225,3 +250,49 @@ pub struct ResolvedCue {
     pub effects: Vec<EffectMapping>,
     pub pixel_effects: Vec<PixelEffectMapping>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColorPreset, PresetType};
+
+    #[test]
+    fn test_fixture_values_override_shared_per_channel() {
+        let mut color = ColorPreset::new(1, "Amber".to_string(), vec![1]);
+        color.add_value(ChannelType::Red, 255);
+        color.add_value(ChannelType::Green, 100);
+        color.add_value(ChannelType::Blue, 0);
+        // Fixture 2 only stored its own red
+        color.fixture_values = vec![StaticValue {
+            fixture_id: 2,
+            channel_type: ChannelType::Red,
+            value: 10,
+        }];
+        let mut library = PresetLibrary::new();
+        library.add_preset(Preset::Color(color));
+        let groups = vec![FixtureGroup::new(1, "Wash".to_string(), vec![1, 2])];
+
+        let resolved = CueResolver::new(&library, &groups)
+            .resolve_reference(&PresetReference {
+                preset_type: PresetType::Color,
+                preset_id: 1,
+                fixture_group_id: None,
+                fixture_ids: Vec::new(),
+                overrides: Vec::new(),
+            })
+            .unwrap();
+
+        let value = |fixture_id: usize, channel_type: ChannelType| {
+            resolved
+                .static_values
+                .iter()
+                .find(|v| v.fixture_id == fixture_id && v.channel_type == channel_type)
+                .map(|v| v.value)
+        };
+        assert_eq!(value(1, ChannelType::Red), Some(255));
+        assert_eq!(value(2, ChannelType::Red), Some(10));
+        assert_eq!(value(2, ChannelType::Green), Some(100));
+        assert_eq!(value(2, ChannelType::Blue), Some(0));
+        assert_eq!(resolved.static_values.len(), 6);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod cue;
 pub mod cue_manager;
+pub mod cue_resolver;
@@ -3,7 +3,7 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Interval, RhythmState};
+use crate::{Interval, RhythmState, Subdivision};
 
 /// Effect release behavior - controls what happens to effects when cues change
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -31,6 +31,9 @@ pub struct Effect {
     pub frequency: f32,
     pub offset: f32,
     pub params: EffectParams,
+    /// Step sequence for chase effects
+    #[serde(default)]
+    pub chase: Option<Chase>,
     // pub value: f64,
     // pub loop: bool,
     // pub paused: bool,
@@ -51,6 +54,8 @@ impl Effect {
                     2.0 - phase * 2.0
                 }
             },
+            // Chases with steps are played by their mapping, one without steps lights nothing
+            EffectType::Chase => |_| 0.0,
             _ => sine_effect, // Default
         };
         (apply_fn)(phase)
@@ -67,6 +72,7 @@ impl Default for Effect {
             frequency: 1.0,
             offset: 0.0,
             params: EffectParams::default(),
+            chase: None,
         }
     }
 }
@@ -80,6 +86,7 @@ pub enum EffectType {
     Triangle,
     Pulse,
     Random,
+    Chase,
 }
 
 impl EffectType {
@@ -91,15 +98,175 @@ impl EffectType {
             EffectType::Triangle => "Triangle",
             EffectType::Pulse => "Pulse",
             EffectType::Random => "Random",
+            EffectType::Chase => "Chase",
         }
     }
 }
 
+/// How an effect's output combines with the value underneath it on the same channel
+///
+/// Effects are applied in priority order (lowest first), with later-started effects applied
+/// after earlier ones at the same priority. Tracked static values sit underneath all effects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectMerge {
+    /// Replace the underlying value, so the latest effect takes precedence
+    #[default]
+    Replace,
+    /// Add to the underlying value, saturating at full
+    Add,
+    /// Highest takes precedence
+    Max,
+}
+
+impl EffectMerge {
+    pub fn all() -> [EffectMerge; 3] {
+        [EffectMerge::Replace, EffectMerge::Add, EffectMerge::Max]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EffectMerge::Replace => "Replace",
+            EffectMerge::Add => "Add",
+            EffectMerge::Max => "Max",
+        }
+    }
+
+    /// Combine an effect value with the value underneath it
+    pub fn apply(&self, under: u8, value: u8) -> u8 {
+        match self {
+            EffectMerge::Replace => value,
+            EffectMerge::Add => under.saturating_add(value),
+            EffectMerge::Max => under.max(value),
+        }
+    }
+}
+
+/// The order in which an effect steps through its sequence
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectDirection {
+    #[default]
+    Forward,
+    Reverse,
+    PingPong,
+}
+
+impl EffectDirection {
+    pub fn all() -> [EffectDirection; 3] {
+        [
+            EffectDirection::Forward,
+            EffectDirection::Reverse,
+            EffectDirection::PingPong,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EffectDirection::Forward => "Forward",
+            EffectDirection::Reverse => "Reverse",
+            EffectDirection::PingPong => "Ping-Pong",
+        }
+    }
+
+    /// Map an effect's phase so it runs forward, backward or bounces within each cycle
+    pub fn apply(&self, phase: f64) -> f64 {
+        match self {
+            EffectDirection::Forward => phase,
+            EffectDirection::Reverse => (1.0 - phase).rem_euclid(1.0),
+            EffectDirection::PingPong => {
+                if phase < 0.5 {
+                    phase * 2.0
+                } else {
+                    2.0 - phase * 2.0
+                }
+            }
+        }
+    }
+}
+
+/// A single step of a chase: the fixtures that are on while the step is active
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChaseStep {
+    pub fixture_ids: Vec<usize>,
+}
+
+/// An ordered list of steps played back over one cycle of the effect's timing
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Chase {
+    pub steps: Vec<ChaseStep>,
+    /// Portion of each step (0.0 to 1.0) spent fading into the next one
+    pub crossfade: f64,
+    pub direction: EffectDirection,
+}
+
+impl Chase {
+    /// Build a chase with one step per fixture, in the given order
+    pub fn single_fixture_steps(fixture_ids: &[usize]) -> Self {
+        Self {
+            steps: fixture_ids
+                .iter()
+                .map(|&id| ChaseStep {
+                    fixture_ids: vec![id],
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Level (0.0 to 1.0) of a fixture at the given effect phase
+    pub fn level(&self, phase: f64, fixture_id: usize) -> f64 {
+        let step_count = self.steps.len();
+        if step_count == 0 {
+            return 0.0;
+        }
+
+        // Ping-pong plays the steps forward then back without repeating the end steps
+        let sequence_len = match self.direction {
+            EffectDirection::PingPong if step_count > 1 => 2 * (step_count - 1),
+            _ => step_count,
+        };
+        let step_at = |position: usize| {
+            let position = position % sequence_len;
+            match self.direction {
+                EffectDirection::Forward => position,
+                EffectDirection::Reverse => step_count - 1 - position,
+                EffectDirection::PingPong if position < step_count => position,
+                EffectDirection::PingPong => sequence_len - position,
+            }
+        };
+
+        let position = phase.rem_euclid(1.0) * sequence_len as f64;
+        let index = position.floor() as usize;
+        let fraction = position - index as f64;
+
+        let is_on = |step: usize| {
+            if self.steps[step].fixture_ids.contains(&fixture_id) {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        let current = is_on(step_at(index));
+
+        let crossfade = self.crossfade.clamp(0.0, 1.0);
+        let fade_start = 1.0 - crossfade;
+        if crossfade == 0.0 || fraction < fade_start {
+            return current;
+        }
+
+        let mix = (fraction - fade_start) / crossfade;
+        let next = is_on(step_at(index + 1));
+        current + (next - current) * mix
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EffectParams {
     pub interval: Interval,
     pub interval_ratio: f64,
     pub phase: f64,
+    /// Musical cycle length; when set this replaces interval and interval_ratio
+    #[serde(default)]
+    pub subdivision: Option<Subdivision>,
 }
 
 impl Default for EffectParams {
@@ -108,11 +275,16 @@ impl Default for EffectParams {
             interval: Interval::Beat,
             interval_ratio: 1.0,
             phase: 0.0,
+            subdivision: None,
         }
     }
 }
 
 pub fn get_effect_phase(rhythm: &RhythmState, params: &EffectParams) -> f64 {
+    if let Some(subdivision) = &params.subdivision {
+        return (subdivision.phase(rhythm) + params.phase) % 1.0;
+    }
+
     let base_phase = match params.interval {
         Interval::Beat => rhythm.beat_phase,
         Interval::Bar => rhythm.bar_phase,
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+/// How the dashboard fixture grid is arranged, saved with the show
+///
+/// Fixtures are shown section by section in the stored order. Anything not placed in a section
+/// follows at the end in patch order, so an empty layout is just the patch.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct FixtureGridLayout {
+    pub sections: Vec<FixtureGridSection>,
+    pub tile_sizes: Vec<(usize, TileSize)>,
+}
+
+/// A titled run of fixtures in the grid, e.g. "Downstage" or "Truss 2"
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FixtureGridSection {
+    pub name: String,
+    pub fixture_ids: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TileSize {
+    Small,
+    #[default]
+    Normal,
+    Large,
+}
+
+impl TileSize {
+    pub fn all() -> [TileSize; 3] {
+        [TileSize::Small, TileSize::Normal, TileSize::Large]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TileSize::Small => "Small",
+            TileSize::Normal => "Normal",
+            TileSize::Large => "Large",
+        }
+    }
+}
+
+impl FixtureGridLayout {
+    /// Group the patched fixtures into sections, with unplaced fixtures last under no header
+    ///
+    /// Fixtures that are no longer patched are skipped.
+    pub fn arrange(&self, patched_ids: &[usize]) -> Vec<(Option<&str>, Vec<usize>)> {
+        let mut placed = Vec::new();
+        let mut arranged: Vec<(Option<&str>, Vec<usize>)> = self
+            .sections
+            .iter()
+            .map(|section| {
+                let ids: Vec<usize> = section
+                    .fixture_ids
+                    .iter()
+                    .copied()
+                    .filter(|id| patched_ids.contains(id) && !placed.contains(id))
+                    .collect();
+                placed.extend(ids.iter().copied());
+                (Some(section.name.as_str()), ids)
+            })
+            .collect();
+
+        let unplaced: Vec<usize> = patched_ids
+            .iter()
+            .copied()
+            .filter(|id| !placed.contains(id))
+            .collect();
+        if !unplaced.is_empty() {
+            arranged.push((None, unplaced));
+        }
+        arranged
+    }
+
+    pub fn tile_size(&self, fixture_id: usize) -> TileSize {
+        self.tile_sizes
+            .iter()
+            .find(|(id, _)| *id == fixture_id)
+            .map(|(_, size)| *size)
+            .unwrap_or_default()
+    }
+
+    pub fn set_tile_size(&mut self, fixture_id: usize, size: TileSize) {
+        self.tile_sizes.retain(|(id, _)| *id != fixture_id);
+        if size != TileSize::Normal {
+            self.tile_sizes.push((fixture_id, size));
+        }
+    }
+
+    /// Move fixtures to the end of a section, or out of every section with None
+    pub fn move_to_section(&mut self, fixture_ids: &[usize], section: Option<usize>) {
+        for existing in self.sections.iter_mut() {
+            existing.fixture_ids.retain(|id| !fixture_ids.contains(id));
+        }
+        if let Some(section) = section.and_then(|index| self.sections.get_mut(index)) {
+            section.fixture_ids.extend_from_slice(fixture_ids);
+        }
+    }
+
+    /// Move a fixture one place earlier or later within its section
+    pub fn shift_fixture(&mut self, fixture_id: usize, earlier: bool) {
+        for section in self.sections.iter_mut() {
+            if let Some(index) = section.fixture_ids.iter().position(|id| *id == fixture_id) {
+                let target = if earlier {
+                    index.checked_sub(1)
+                } else {
+                    Some(index + 1).filter(|i| *i < section.fixture_ids.len())
+                };
+                if let Some(target) = target {
+                    section.fixture_ids.swap(index, target);
+                }
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrange_sections_then_unplaced() {
+        let mut layout = FixtureGridLayout {
+            sections: vec![FixtureGridSection {
+                name: "Truss".to_string(),
+                fixture_ids: vec![4, 9, 2],
+            }],
+            tile_sizes: Vec::new(),
+        };
+        // Fixture 9 isn't patched any more
+        let arranged = layout.arrange(&[1, 2, 3, 4]);
+        assert_eq!(
+            arranged,
+            vec![(Some("Truss"), vec![4, 2]), (None, vec![1, 3])]
+        );
+
+        layout.shift_fixture(2, true);
+        layout.move_to_section(&[4], None);
+        let arranged = layout.arrange(&[1, 2, 3, 4]);
+        assert_eq!(
+            arranged,
+            vec![(Some("Truss"), vec![2]), (None, vec![1, 3, 4])]
+        );
+    }
+}
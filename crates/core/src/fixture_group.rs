@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// A named set of fixtures that presets and selections can target together
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FixtureGroup {
+    pub id: usize,
+    pub name: String,
+    pub fixture_ids: Vec<usize>,
+}
+
+impl FixtureGroup {
+    pub fn new(id: usize, name: String, fixture_ids: Vec<usize>) -> Self {
+        Self {
+            id,
+            name,
+            fixture_ids,
+        }
+    }
+}
+
+/// A stored selection on a numbered quick-select button, recalled in its original order
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuickSelect {
+    pub number: usize,
+    pub fixture_ids: Vec<usize>,
+}
+
+/// How a group selection combines with the current selection
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GroupSelectMode {
+    /// Replace the selection with the group
+    #[default]
+    Replace,
+    /// Add the group's fixtures to the selection
+    Add,
+    /// Remove the group's fixtures from the selection
+    Remove,
+}
@@ -1,28 +1,56 @@
 pub use ableton_link::AbletonLinkManager;
 pub use artnet::artnet::ArtNetMode;
 pub use artnet::network_config::{ArtNetDestination, NetworkConfig};
+pub use artnet::network_status::{ArtNetNode, DestinationStatus, NetworkStatus, UniverseStatus};
 pub use audio::audio_player::AudioPlayer;
 pub use audio::device_enumerator::{enumerate_audio_devices, AudioDeviceInfo};
+pub use command_line::{parse_command_line, CommandLineContext};
 pub use config::{ConfigError, ConfigManager, ConfigSchema};
 pub use console::{LightingConsole, SyncLightingConsole};
 pub use cue::cue::{
-    Cue, CueList, EffectDistribution, EffectMapping, PixelEffectMapping, StaticValue,
+    Cue, CueList, EffectDistribution, EffectMapping, PixelEffectMapping, PresetReference,
+    SpatialAxis, StaticValue,
 };
-pub use cue::cue_manager::{CueManager, PlaybackState};
+pub use cue::cue_manager::{CueManager, NextCuePreview, PlaybackState};
+pub use cue::cue_resolver::{CueResolver, ResolvedCue};
 pub use effect::effect::{
-    sawtooth_effect, sine_effect, square_effect, Effect, EffectParams, EffectType,
+    sawtooth_effect, sine_effect, square_effect, Chase, ChaseStep, Effect, EffectDirection,
+    EffectMerge, EffectParams, EffectType,
 };
 pub use effect::EffectRelease;
-pub use messages::{ConsoleCommand, ConsoleEvent, Settings};
+pub use fixture_grid::{FixtureGridLayout, FixtureGridSection, TileSize};
+pub use fixture_group::{FixtureGroup, GroupSelectMode, QuickSelect};
+pub use magic_sheet::{MagicSheet, MagicSheetAction, MagicSheetItem, MagicSheetItemKind};
+pub use messages::{
+    ConsoleCommand, ConsoleEvent, DashboardColumn, DashboardLayout, DashboardWidget,
+    ProgrammerEffectSummary, Settings, UiTheme,
+};
+pub use midi::mapping::{
+    learn_mapping, MidiControl, MidiFeedbackState, MidiMapping, MidiMappingAction,
+    MidiMappingContext, RelativeEncoding,
+};
 pub use midi::midi::{MidiAction, MidiMessage, MidiOverride};
+pub use midi::profile::{MidiFeedback, MidiProfile, MIDI_PROFILE_DIR};
+pub use midi::rtp::RTP_MIDI_DEVICE;
 // Async module system exports
 pub use modules::{
     AsyncModule, AudioModule, DmxModule, MidiModule, ModuleEvent, ModuleId, ModuleManager,
     ModuleMessage, SmpteModule,
 };
-pub use pixel::{PixelEffect, PixelEffectParams, PixelEffectScope, PixelEffectType, PixelEngine};
-pub use rhythm::rhythm::{Interval, RhythmState};
-pub use show::show::Show;
+pub use performance::PerformanceMetrics;
+pub use pixel::{
+    PixelBlendMode, PixelEffect, PixelEffectParams, PixelEffectScope, PixelEffectType, PixelEngine,
+    PixelLayer, PixelText, ScrollDirection,
+};
+pub use preset::preset::{
+    BeamPreset, ColorPreset, EffectPreset, EffectPresetType, IntensityPreset, PositionPreset,
+    Preset, PresetType,
+};
+pub use preset::preset_library::PresetLibrary;
+pub use rhythm::rhythm::{
+    Interval, RhythmState, Subdivision, SubdivisionModifier, SubdivisionUnit,
+};
+pub use show::show::{ColorSwatch, Show};
 pub use show::show_manager::ShowManager;
 pub use timecode::timecode::TimeCode;
 pub use tracking_state::TrackingState;
@@ -30,15 +58,21 @@ pub use tracking_state::TrackingState;
 mod ableton_link;
 mod artnet;
 pub mod audio;
+mod command_line;
 mod config;
 mod console;
 
 mod cue;
 mod effect;
+mod fixture_grid;
+mod fixture_group;
+mod magic_sheet;
 pub mod messages;
 mod midi;
 mod modules;
+mod performance;
 mod pixel;
+mod preset;
 mod programmer;
 mod rhythm;
 mod show;
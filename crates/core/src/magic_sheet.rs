@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Buttons and fixture icons placed freely on a canvas, saved with the show
+///
+/// Meant as a simplified operator screen, so someone who doesn't know the console can still run
+/// the show from a handful of labelled buttons.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct MagicSheet {
+    pub items: Vec<MagicSheetItem>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MagicSheetItem {
+    /// Top left corner on the canvas, in points
+    pub position: [f32; 2],
+    pub kind: MagicSheetItemKind,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum MagicSheetItemKind {
+    Button {
+        label: String,
+        action: MagicSheetAction,
+        /// RGB fill for the button
+        color: [u8; 3],
+    },
+    /// Shows the fixture's selection, clicking it toggles the fixture in the selection
+    Fixture { fixture_id: usize },
+}
+
+/// What a magic sheet button does when pressed
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum MagicSheetAction {
+    FireCue {
+        list_index: usize,
+        cue_index: usize,
+    },
+    SelectGroup {
+        group_id: usize,
+    },
+    /// Drive the group to full while the button is held
+    FlashGroup {
+        group_id: usize,
+    },
+    ToggleSmoke,
+}
+
+impl MagicSheetAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MagicSheetAction::FireCue { .. } => "Fire Cue",
+            MagicSheetAction::SelectGroup { .. } => "Select Group",
+            MagicSheetAction::FlashGroup { .. } => "Flash Group",
+            MagicSheetAction::ToggleSmoke => "Toggle Smoke",
+        }
+    }
+}
@@ -1,10 +1,19 @@
 use std::path::PathBuf;
 
-use halo_fixtures::Fixture;
+use halo_fixtures::{ChannelType, Fixture, FixturePosition};
 use serde::{Deserialize, Serialize};
 
 use crate::audio::device_enumerator::AudioDeviceInfo;
-use crate::{CueList, EffectType, MidiOverride, PlaybackState, RhythmState, Show, TimeCode};
+use crate::{
+    ColorSwatch, CueList, Effect, EffectDirection, EffectDistribution, EffectMapping, EffectPreset,
+    EffectType, FixtureGridLayout, FixtureGroup, GroupSelectMode, MagicSheet, MidiMapping,
+    MidiMappingAction, MidiOverride, NextCuePreview, PlaybackState, PresetLibrary, PresetReference,
+    PresetType, QuickSelect, RhythmState, Show, SpatialAxis, TimeCode,
+};
+
+/// A live programmer effect as shown in the UI: (name, effect_type, fixture_ids, channels,
+/// direction)
+pub type ProgrammerEffectSummary = (String, EffectType, Vec<usize>, Vec<String>, EffectDirection);
 
 /// Commands sent from UI to Console
 #[derive(Debug, Clone)]
@@ -58,6 +67,10 @@ pub enum ConsoleCommand {
     ClearPanTiltLimits {
         fixture_id: usize,
     },
+    SetFixturePosition {
+        fixture_id: usize,
+        position: Option<FixturePosition>,
+    },
 
     // Cue management
     SetCueLists {
@@ -71,10 +84,80 @@ pub enum ConsoleCommand {
         timecode: Option<String>,
         is_blocking: bool,
     },
+    /// Set the color of every fixture in a cue, mapped onto each fixture's color channels
+    SetCueColor {
+        list_index: usize,
+        cue_index: usize,
+        red: u8,
+        green: u8,
+        blue: u8,
+    },
+    AddCueEffect {
+        list_index: usize,
+        cue_index: usize,
+        effect: EffectMapping,
+    },
+    UpdateCueEffect {
+        list_index: usize,
+        cue_index: usize,
+        effect_index: usize,
+        effect: EffectMapping,
+    },
     DeleteCue {
         list_index: usize,
         cue_index: usize,
     },
+
+    // Effect presets
+    StoreEffectPreset {
+        name: String,
+        effect: Effect,
+        distribution: EffectDistribution,
+        channel_types: Vec<ChannelType>,
+    },
+    UpdateEffectPreset {
+        preset: EffectPreset,
+    },
+    DeleteEffectPreset {
+        preset_id: usize,
+    },
+    ApplyEffectPreset {
+        preset_id: usize,
+        list_index: usize,
+        cue_index: usize,
+        fixture_ids: Vec<usize>,
+    },
+
+    // Preset pools
+    /// Store the programmer's values for one parameter family into a numbered preset
+    StoreProgrammerPreset {
+        preset_type: PresetType,
+        preset_id: usize,
+        name: String,
+        /// Only store values that differ from the tracked cue values
+        changed_only: bool,
+    },
+    /// Load a preset's values into the programmer for the given fixtures
+    RecallPreset {
+        preset_type: PresetType,
+        preset_id: usize,
+        fixture_ids: Vec<usize>,
+    },
+    DeletePreset {
+        preset_type: PresetType,
+        preset_id: usize,
+    },
+    AddCuePresetReference {
+        list_index: usize,
+        cue_index: usize,
+        reference: PresetReference,
+    },
+    RemoveCuePresetReference {
+        list_index: usize,
+        cue_index: usize,
+        reference_index: usize,
+    },
+
     DeleteCueList {
         list_index: usize,
     },
@@ -144,6 +227,15 @@ pub enum ConsoleCommand {
     RemoveMidiOverride {
         note: u8,
     },
+    /// Map the next control moved on a MIDI device to `action`
+    StartMidiLearn {
+        action: MidiMappingAction,
+    },
+    CancelMidiLearn,
+    QueryMidiProfiles,
+    RemoveMidiMapping {
+        index: usize,
+    },
     ProcessMidiMessage {
         message: Vec<u8>,
     },
@@ -160,6 +252,21 @@ pub enum ConsoleCommand {
     // Ableton Link
     EnableAbletonLink,
     DisableAbletonLink,
+    SetLinkQuantum {
+        quantum: f64,
+    },
+    SetLinkStartStopSync {
+        enabled: bool,
+    },
+    RealignLinkDownbeat,
+
+    // Effect masters (1.0 = 100%, range 0.0 to 2.0)
+    SetFxRateMaster {
+        rate: f64,
+    },
+    SetFxSizeMaster {
+        size: f64,
+    },
 
     // Effects
     ApplyEffect {
@@ -174,6 +281,10 @@ pub enum ConsoleCommand {
         fixture_ids: Vec<usize>,
         channel_type: String,
     },
+    SetProgrammerEffectDirection {
+        effect_index: usize,
+        direction: EffectDirection,
+    },
 
     // Programmer
     SetProgrammerValue {
@@ -181,9 +292,56 @@ pub enum ConsoleCommand {
         channel: String,
         value: u8,
     },
+    /// Copy a fixture's current values into the programmer for other fixtures
+    /// Only the given families are copied (all when empty), and only to channels the target has
+    CopyFixtureParameters {
+        source_fixture_id: usize,
+        target_fixture_ids: Vec<usize>,
+        families: Vec<PresetType>,
+    },
+    /// Invert pan (and optionally tilt) of the right half of the fixtures from the left half
+    /// Fixtures are ordered by stage position when they all have one, otherwise as given
+    MirrorProgrammerPositions {
+        fixture_ids: Vec<usize>,
+        mirror_tilt: bool,
+    },
+    /// Move a channel relative to each fixture's current value, scaled by its encoder sensitivity
+    AdjustProgrammerValue {
+        fixture_ids: Vec<usize>,
+        channel: String,
+        delta: f32,
+    },
+    SetEncoderSensitivity {
+        channel: String,
+        sensitivity: f32,
+    },
+    /// Spread a channel from `start` to `end` across the fixtures in selection order
+    FanProgrammerValue {
+        fixture_ids: Vec<usize>,
+        channel: String,
+        start: u8,
+        end: u8,
+    },
+    /// Set a color on the given fixtures through whichever color channels each one has
+    SetProgrammerColor {
+        fixture_ids: Vec<usize>,
+        red: u8,
+        green: u8,
+        blue: u8,
+    },
+    /// Remove one channel from the programmer for the given fixtures
+    KnockoutProgrammerValue {
+        fixture_ids: Vec<usize>,
+        channel: String,
+    },
     SetProgrammerPreviewMode {
         preview_mode: bool,
     },
+    /// Drive the selected fixtures to open white; lowlight also dims everything else
+    SetProgrammerHighlight {
+        highlight: bool,
+        lowlight: bool,
+    },
     SetSelectedFixtures {
         fixture_ids: Vec<usize>,
     },
@@ -194,11 +352,85 @@ pub enum ConsoleCommand {
         fixture_id: usize,
     },
     ClearSelectedFixtures,
+    ReverseSelectionOrder,
+    /// Isolate the next or previous fixture of the selection for touch-up
+    StepSelection {
+        forward: bool,
+    },
+    /// End stepping and return to the full selection
+    RestoreSelection,
+    SelectFixtureGroup {
+        group_id: usize,
+        mode: GroupSelectMode,
+    },
+    /// Store a selection on a quick-select button, replacing what was there
+    StoreQuickSelect {
+        number: usize,
+        fixture_ids: Vec<usize>,
+    },
+    RecallQuickSelect {
+        number: usize,
+    },
+    DeleteQuickSelect {
+        number: usize,
+    },
+
+    /// Replace the dashboard fixture grid arrangement
+    SetFixtureGridLayout {
+        layout: FixtureGridLayout,
+    },
+
+    // Magic sheet
+    SetMagicSheet {
+        sheet: MagicSheet,
+    },
+    /// Drive fixtures to full intensity on top of the output, e.g. while a flash button is held
+    SetFlash {
+        fixture_ids: Vec<usize>,
+        active: bool,
+    },
+    /// Run smoke machines at full on top of the output
+    SetSmoke {
+        active: bool,
+    },
+
+    // Show color palette
+    StoreColorSwatch {
+        name: String,
+        color: [u8; 3],
+    },
+    DeleteColorSwatch {
+        index: usize,
+    },
+
+    // Fixture groups
+    StoreFixtureGroup {
+        name: String,
+        fixture_ids: Vec<usize>,
+    },
+    DeleteFixtureGroup {
+        group_id: usize,
+    },
     RecordProgrammerToCue {
         cue_name: String,
         list_index: Option<usize>,
+        /// Only record values that differ from the tracked cue values
+        changed_only: bool,
     },
     ClearProgrammer,
+    /// Load the fixtures' current output into the programmer
+    CaptureLiveOutput {
+        fixture_ids: Vec<usize>,
+    },
+    /// Revert the last programmer edit (values, effects or selection)
+    UndoProgrammer,
+    /// Reapply the last undone programmer edit
+    RedoProgrammer,
+    /// Clear intensity, then color, then everything on successive presses
+    ClearProgrammerStaged,
+    ClearProgrammerFamily {
+        family: PresetType,
+    },
     ApplyProgrammerEffect {
         fixture_ids: Vec<usize>,
         channel_types: Vec<String>,
@@ -210,6 +442,8 @@ pub enum ConsoleCommand {
         distribution: u8,
         step_value: Option<usize>,
         wave_offset: Option<f32>,
+        spatial_axis: Option<SpatialAxis>,
+        spatial_spread: Option<f32>,
     },
 
     // Settings commands
@@ -235,6 +469,11 @@ pub enum ConsoleCommand {
     },
     ClearPixelEffects,
 
+    /// Look for Art-Net nodes while the network status is being watched
+    SetNodeDiscovery {
+        enabled: bool,
+    },
+
     // Query commands (request state)
     QueryFixtures,
     QueryCueLists,
@@ -264,6 +503,30 @@ pub struct Settings {
     pub midi_enabled: bool,
     pub midi_device: String,
     pub midi_channel: u8,
+    /// Control port for RTP-MIDI when the device is the network session, data is on the next port
+    #[serde(default = "default_rtp_midi_port")]
+    pub rtp_midi_port: u16,
+    /// Controls bound to console actions with MIDI learn
+    #[serde(default)]
+    pub midi_mappings: Vec<MidiMapping>,
+    /// Name of the controller profile to use, learned mappings take priority over it
+    #[serde(default = "default_midi_profile")]
+    pub midi_profile: String,
+    /// Chase MIDI timecode from the MIDI device instead of the show clock
+    #[serde(default)]
+    pub mtc_input_enabled: bool,
+    /// Added to incoming MIDI timecode, in seconds
+    #[serde(default)]
+    pub mtc_offset_secs: f64,
+    /// How long to keep running when MIDI timecode stops before holding, in seconds
+    #[serde(default = "default_mtc_freewheel_secs")]
+    pub mtc_freewheel_secs: f64,
+    /// Send MIDI timecode from the show clock to the MIDI device
+    #[serde(default)]
+    pub mtc_output_enabled: bool,
+    /// Send MIDI clock at the console tempo, with start and stop following playback
+    #[serde(default)]
+    pub midi_clock_output_enabled: bool,
 
     // Output settings (DMX/Art-Net)
     pub dmx_enabled: bool,
@@ -281,6 +544,166 @@ pub struct Settings {
 
     // Fixture settings
     pub enable_pan_tilt_limits: bool,
+
+    // Keyboard shortcut overrides by action, an empty chord unbinds the action
+    #[serde(default)]
+    pub key_bindings: std::collections::HashMap<String, String>,
+
+    // Appearance settings
+    #[serde(default)]
+    pub theme: UiTheme,
+    #[serde(default = "default_accent_color")]
+    pub accent_color: [u8; 3],
+    // Larger controls, a simplified cue list and an on-screen Go bar for touchscreens
+    #[serde(default)]
+    pub touch_layout: bool,
+    // Frame time, console loop and DMX output timings drawn over the UI
+    #[serde(default)]
+    pub performance_overlay: bool,
+    #[serde(default)]
+    pub dashboard_layout: DashboardLayout,
+}
+
+/// Color scheme for the UI
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UiTheme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl UiTheme {
+    pub fn all() -> [UiTheme; 3] {
+        [UiTheme::Dark, UiTheme::Light, UiTheme::HighContrast]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UiTheme::Dark => "Dark",
+            UiTheme::Light => "Light",
+            UiTheme::HighContrast => "High Contrast",
+        }
+    }
+}
+
+/// A widget that can be placed on the dashboard
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DashboardWidget {
+    Session,
+    NextCue,
+    CueList,
+    Masters,
+    PixelPreview,
+    FixtureGrid,
+}
+
+impl DashboardWidget {
+    pub fn all() -> [DashboardWidget; 6] {
+        [
+            DashboardWidget::Session,
+            DashboardWidget::NextCue,
+            DashboardWidget::CueList,
+            DashboardWidget::Masters,
+            DashboardWidget::PixelPreview,
+            DashboardWidget::FixtureGrid,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DashboardWidget::Session => "Session",
+            DashboardWidget::NextCue => "Next Cue",
+            DashboardWidget::CueList => "Cue List",
+            DashboardWidget::Masters => "Overrides & Masters",
+            DashboardWidget::PixelPreview => "Pixel Preview",
+            DashboardWidget::FixtureGrid => "Fixture Grid",
+        }
+    }
+}
+
+/// Where a dashboard widget is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardColumn {
+    Main,
+    Side,
+}
+
+/// The dashboard widgets in each column, top to bottom; widgets in neither column are hidden
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DashboardLayout {
+    pub main: Vec<DashboardWidget>,
+    pub side: Vec<DashboardWidget>,
+}
+
+impl Default for DashboardLayout {
+    fn default() -> Self {
+        Self {
+            main: vec![
+                DashboardWidget::PixelPreview,
+                DashboardWidget::Masters,
+                DashboardWidget::FixtureGrid,
+            ],
+            side: vec![
+                DashboardWidget::Session,
+                DashboardWidget::NextCue,
+                DashboardWidget::CueList,
+            ],
+        }
+    }
+}
+
+impl DashboardLayout {
+    pub fn column_of(&self, widget: DashboardWidget) -> Option<DashboardColumn> {
+        if self.main.contains(&widget) {
+            Some(DashboardColumn::Main)
+        } else if self.side.contains(&widget) {
+            Some(DashboardColumn::Side)
+        } else {
+            None
+        }
+    }
+
+    /// Move a widget to the bottom of a column, or hide it with `None`
+    pub fn set_column(&mut self, widget: DashboardWidget, column: Option<DashboardColumn>) {
+        if self.column_of(widget) == column {
+            return;
+        }
+        self.main.retain(|w| *w != widget);
+        self.side.retain(|w| *w != widget);
+        match column {
+            Some(DashboardColumn::Main) => self.main.push(widget),
+            Some(DashboardColumn::Side) => self.side.push(widget),
+            None => {}
+        }
+    }
+
+    /// Move a widget up (negative) or down (positive) within its column
+    pub fn shift(&mut self, widget: DashboardWidget, offset: isize) {
+        for column in [&mut self.main, &mut self.side] {
+            if let Some(index) = column.iter().position(|w| *w == widget) {
+                let target = index.saturating_add_signed(offset).min(column.len() - 1);
+                let widget = column.remove(index);
+                column.insert(target, widget);
+            }
+        }
+    }
+}
+
+fn default_accent_color() -> [u8; 3] {
+    [59, 130, 246]
+}
+
+fn default_midi_profile() -> String {
+    "Akai MPK49".to_string()
+}
+
+fn default_rtp_midi_port() -> u16 {
+    5004
+}
+
+fn default_mtc_freewheel_secs() -> f64 {
+    2.0
 }
 
 impl Default for Settings {
@@ -300,6 +723,14 @@ impl Default for Settings {
             midi_enabled: false,
             midi_device: "None".to_string(),
             midi_channel: 1,
+            rtp_midi_port: default_rtp_midi_port(),
+            midi_mappings: Vec::new(),
+            midi_profile: default_midi_profile(),
+            mtc_input_enabled: false,
+            mtc_offset_secs: 0.0,
+            mtc_freewheel_secs: default_mtc_freewheel_secs(),
+            mtc_output_enabled: false,
+            midi_clock_output_enabled: false,
 
             // Output defaults
             dmx_enabled: true,
@@ -317,10 +748,62 @@ impl Default for Settings {
 
             // Fixture defaults
             enable_pan_tilt_limits: true,
+
+            // Shortcut defaults live in the UI
+            key_bindings: std::collections::HashMap::new(),
+
+            // Appearance defaults
+            theme: UiTheme::Dark,
+            accent_color: default_accent_color(),
+            touch_layout: false,
+            performance_overlay: false,
+            dashboard_layout: DashboardLayout::default(),
         }
     }
 }
 
+impl ConsoleCommand {
+    /// Whether the command edits data that is saved in the show file
+    pub fn modifies_show(&self) -> bool {
+        use ConsoleCommand::*;
+        matches!(
+            self,
+            PatchFixture { .. }
+                | UnpatchFixture { .. }
+                | UpdateFixture { .. }
+                | UpdateFixtureChannels { .. }
+                | SetPanTiltLimits { .. }
+                | ClearPanTiltLimits { .. }
+                | SetFixturePosition { .. }
+                | SetCueLists { .. }
+                | UpdateCue { .. }
+                | SetCueColor { .. }
+                | AddCueEffect { .. }
+                | UpdateCueEffect { .. }
+                | DeleteCue { .. }
+                | AddCue { .. }
+                | DeleteCueList { .. }
+                | SetCueListAudioFile { .. }
+                | StoreEffectPreset { .. }
+                | UpdateEffectPreset { .. }
+                | DeleteEffectPreset { .. }
+                | StoreProgrammerPreset { .. }
+                | DeletePreset { .. }
+                | AddCuePresetReference { .. }
+                | RemoveCuePresetReference { .. }
+                | StoreQuickSelect { .. }
+                | DeleteQuickSelect { .. }
+                | SetFixtureGridLayout { .. }
+                | SetMagicSheet { .. }
+                | StoreColorSwatch { .. }
+                | DeleteColorSwatch { .. }
+                | StoreFixtureGroup { .. }
+                | DeleteFixtureGroup { .. }
+                | RecordProgrammerToCue { .. }
+        )
+    }
+}
+
 /// Events sent from Console to UI
 #[derive(Debug, Clone)]
 pub enum ConsoleEvent {
@@ -338,6 +821,33 @@ pub enum ConsoleEvent {
     CueListsUpdated {
         cue_lists: Vec<CueList>,
     },
+    PresetsUpdated {
+        presets: PresetLibrary,
+    },
+    FixtureGroupsUpdated {
+        groups: Vec<FixtureGroup>,
+    },
+    QuickSelectsUpdated {
+        quick_selects: Vec<QuickSelect>,
+    },
+    ColorSwatchesUpdated {
+        swatches: Vec<ColorSwatch>,
+    },
+    FixtureGridLayoutUpdated {
+        layout: FixtureGridLayout,
+    },
+    MagicSheetUpdated {
+        sheet: MagicSheet,
+    },
+    SmokeChanged {
+        active: bool,
+    },
+    NetworkStatusUpdated {
+        status: crate::NetworkStatus,
+    },
+    PerformanceMetricsUpdated {
+        metrics: crate::PerformanceMetrics,
+    },
     PlaybackStateChanged {
         state: PlaybackState,
     },
@@ -347,6 +857,10 @@ pub enum ConsoleEvent {
     TrackingStateUpdated {
         active_effect_count: usize,
     },
+    /// Programmer values that differ from the tracked cue values, sent when the set changes
+    ProgrammerChangedValuesUpdated {
+        changed: Vec<(usize, String)>,
+    },
     TimecodeUpdated {
         timecode: TimeCode,
     },
@@ -361,6 +875,10 @@ pub enum ConsoleEvent {
     ShowSaved {
         path: PathBuf,
     },
+    /// Show data was edited since the last save or load, or has just been saved
+    ShowDirtyChanged {
+        dirty: bool,
+    },
     ShowCreated {
         name: String,
     },
@@ -404,6 +922,9 @@ pub enum ConsoleEvent {
         cue_index: usize,
         progress: f32,
     },
+    NextCuePreviewUpdated {
+        preview: Option<NextCuePreview>,
+    },
 
     // MIDI events
     MidiOverrideAdded {
@@ -412,6 +933,13 @@ pub enum ConsoleEvent {
     MidiOverrideRemoved {
         note: u8,
     },
+    /// The action waiting for a control to be moved, if MIDI learn is armed
+    MidiLearnChanged {
+        action: Option<MidiMappingAction>,
+    },
+    MidiProfilesList {
+        profiles: Vec<String>,
+    },
     MidiMessageReceived {
         message: Vec<u8>,
     },
@@ -430,17 +958,43 @@ pub enum ConsoleEvent {
         enabled: bool,
         num_peers: u64,
     },
+    LinkSessionUpdated {
+        tempo: f64,
+        phase: f64,
+        quantum: f64,
+        start_stop_sync: bool,
+    },
+
+    // Effect master events
+    FxMastersChanged {
+        rate: f64,
+        size: f64,
+    },
 
     // Programmer events
     ProgrammerStateUpdated {
         preview_mode: bool,
         selected_fixtures: Vec<usize>,
     },
+    SelectionStepChanged {
+        /// Index of the isolated fixture and the full selection size, None when not stepping
+        step: Option<(usize, usize)>,
+    },
+    ProgrammerUndoChanged {
+        /// The action the next undo would revert
+        next_undo: Option<String>,
+        /// The action the next redo would reapply
+        next_redo: Option<String>,
+    },
+    ProgrammerHighlightChanged {
+        highlight: bool,
+        lowlight: bool,
+    },
     ProgrammerValuesUpdated {
         values: Vec<(usize, String, u8)>, // (fixture_id, channel, value)
     },
     ProgrammerEffectsUpdated {
-        effects: Vec<(String, EffectType, Vec<usize>)>, // (name, effect_type, fixture_ids)
+        effects: Vec<ProgrammerEffectSummary>,
     },
 
     // Response to queries
@@ -0,0 +1,96 @@
+use std::time::Instant;
+
+const CLOCK: u8 = 0xF8;
+const START: u8 = 0xFA;
+const STOP: u8 = 0xFC;
+/// MIDI clock pulses per quarter note
+pub const PULSES_PER_BEAT: f64 = 24.0;
+// After a stall, don't burst out more than this many late pulses at once
+const MAX_CATCH_UP_PULSES: u64 = 4;
+
+/// Generates MIDI clock locked to the console's beat clock, with start and stop for the transport
+#[derive(Debug, Default)]
+pub struct MidiClockEncoder {
+    // Beat position and tempo when last set, and when that was
+    source: Option<(f64, f64, Instant)>,
+    next_pulse: Option<u64>,
+    playing: bool,
+    // Start waits for the next beat so sequencers begin on the downbeat
+    start_pending: bool,
+}
+
+impl MidiClockEncoder {
+    /// Follow the beat clock, or stop sending clock with None
+    ///
+    /// Returns a Stop message when the transport stops, Start is sent by `tick` on the next beat.
+    pub fn set_clock(&mut self, clock: Option<(f64, f64)>, playing: bool) -> Vec<Vec<u8>> {
+        self.source = clock.map(|(beat, bpm)| (beat, bpm, Instant::now()));
+        if self.source.is_none() {
+            self.next_pulse = None;
+        }
+
+        let mut messages = Vec::new();
+        let playing = playing && self.source.is_some();
+        if playing && !self.playing {
+            self.start_pending = true;
+        } else if !playing && self.playing {
+            self.start_pending = false;
+            messages.push(vec![STOP]);
+        }
+        self.playing = playing;
+        messages
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// The clock pulses due since the last tick, call this often (every millisecond or so)
+    pub fn tick(&mut self) -> Vec<Vec<u8>> {
+        let Some((beat, bpm, set_at)) = self.source else {
+            return Vec::new();
+        };
+
+        let beat = beat + set_at.elapsed().as_secs_f64() * bpm / 60.0;
+        let due = (beat.max(0.0) * PULSES_PER_BEAT) as u64 + 1;
+        let next = match self.next_pulse {
+            Some(next) if next.abs_diff(due) <= MAX_CATCH_UP_PULSES => next,
+            // First pulse, or the beat clock jumped, so carry on from where it is now
+            _ => due - 1,
+        };
+
+        let mut messages = Vec::new();
+        for pulse in next..due {
+            if self.start_pending && pulse % PULSES_PER_BEAT as u64 == 0 {
+                self.start_pending = false;
+                messages.push(vec![START]);
+            }
+            messages.push(vec![CLOCK]);
+        }
+        self.next_pulse = Some(next.max(due));
+        messages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_waits_for_the_beat() {
+        let mut encoder = MidiClockEncoder::default();
+        assert!(encoder.set_clock(Some((0.5, 120.0)), false).is_empty());
+        assert_eq!(encoder.tick(), vec![vec![CLOCK]]);
+
+        // Playing from half way through a beat, the start goes out with the next beat's pulse
+        assert!(encoder.set_clock(Some((0.5, 120.0)), true).is_empty());
+        assert!(encoder.tick().is_empty());
+        encoder.set_clock(Some((1.0, 120.0)), true);
+        assert_eq!(encoder.tick(), vec![vec![START], vec![CLOCK]]);
+
+        assert_eq!(
+            encoder.set_clock(Some((1.5, 120.0)), false),
+            vec![vec![STOP]]
+        );
+    }
+}
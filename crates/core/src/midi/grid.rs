@@ -0,0 +1,99 @@
+use crate::midi::mapping::{MidiControl, MidiMapping, MidiMappingAction};
+use crate::midi::profile::{FeedbackPalette, MidiFeedback, MidiProfile};
+
+// Off, dim white, orange and green in the Novation palette, which the APC40 mkII shares
+const PALETTE: FeedbackPalette = FeedbackPalette {
+    empty: 0,
+    available: 1,
+    active: 9,
+    playing: 21,
+};
+
+/// A pad matrix where each column plays a cue list and each row a cue, top row first
+struct GridController {
+    name: &'static str,
+    columns: usize,
+    rows: usize,
+    /// Note sent by the pad at a column and row, counted from the top left
+    note: fn(usize, usize) -> u8,
+    init: &'static [&'static [u8]],
+}
+
+const GRID_CONTROLLERS: [GridController; 3] = [
+    GridController {
+        name: "Novation Launchpad Mini MK3",
+        columns: 8,
+        rows: 8,
+        note: launchpad_note,
+        // Programmer mode, so the pads send fixed notes and take colors
+        init: &[&[0xF0, 0x00, 0x20, 0x29, 0x02, 0x0D, 0x0E, 0x01, 0xF7]],
+    },
+    GridController {
+        name: "Novation Launchpad X",
+        columns: 8,
+        rows: 8,
+        note: launchpad_note,
+        init: &[&[0xF0, 0x00, 0x20, 0x29, 0x02, 0x0C, 0x0E, 0x01, 0xF7]],
+    },
+    GridController {
+        name: "Akai APC40 mkII",
+        columns: 8,
+        rows: 5,
+        note: apc40_note,
+        // Ableton Live mode, which hands the pad LEDs to the host
+        init: &[&[
+            0xF0, 0x47, 0x7F, 0x29, 0x60, 0x00, 0x04, 0x41, 0x09, 0x07, 0x01, 0xF7,
+        ]],
+    },
+];
+
+// Programmer mode notes are row then column from the bottom left, 11 to 88
+fn launchpad_note(column: usize, row: usize) -> u8 {
+    ((8 - row) * 10 + column + 1) as u8
+}
+
+// Clip launch notes run from 0 at the bottom left to 39 at the top right
+fn apc40_note(column: usize, row: usize) -> u8 {
+    ((4 - row) * 8 + column) as u8
+}
+
+/// Profiles for the supported grid controllers, mapping pads to cues with color feedback
+pub fn grid_profiles() -> Vec<MidiProfile> {
+    GRID_CONTROLLERS
+        .iter()
+        .map(|grid| {
+            let pads: Vec<(MidiControl, MidiMappingAction)> = (0..grid.columns)
+                .flat_map(|column| (0..grid.rows).map(move |row| (column, row)))
+                .map(|(column, row)| {
+                    (
+                        MidiControl::Note((grid.note)(column, row)),
+                        MidiMappingAction::GoToCue {
+                            list_index: column,
+                            cue_index: row,
+                        },
+                    )
+                })
+                .collect();
+
+            MidiProfile {
+                name: grid.name.to_string(),
+                mappings: pads
+                    .iter()
+                    .map(|(control, action)| MidiMapping {
+                        control: *control,
+                        action: action.clone(),
+                    })
+                    .collect(),
+                feedback: pads
+                    .into_iter()
+                    .map(|(control, action)| MidiFeedback {
+                        control,
+                        action,
+                        palette: Some(PALETTE),
+                    })
+                    .collect(),
+                init: grid.init.iter().map(|message| message.to_vec()).collect(),
+            }
+        })
+        .collect()
+}
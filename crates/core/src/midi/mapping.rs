@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::midi::midi::MidiMessage;
+use crate::{ConsoleCommand, FixtureGroup};
+
+/// A physical control on a MIDI device
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum MidiControl {
+    Note(u8),
+    ControlChange(u8),
+}
+
+impl MidiControl {
+    /// The control a message came from, and its value; a note off is the note at zero
+    pub fn from_message(message: &MidiMessage) -> Option<(MidiControl, u8)> {
+        match *message {
+            MidiMessage::NoteOn(note, velocity) => Some((MidiControl::Note(note), velocity)),
+            MidiMessage::NoteOff(note) => Some((MidiControl::Note(note), 0)),
+            MidiMessage::ControlChange(cc, value) => Some((MidiControl::ControlChange(cc), value)),
+            MidiMessage::Clock => None,
+        }
+    }
+
+    /// The message setting this control to `value` on `channel` (1-16), for feedback
+    pub fn message(&self, channel: u8, value: u8) -> Vec<u8> {
+        let channel = channel.clamp(1, 16) - 1;
+        match *self {
+            MidiControl::Note(note) => vec![0x90 | channel, note, value.min(127)],
+            MidiControl::ControlChange(cc) => vec![0xB0 | channel, cc, value.min(127)],
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            MidiControl::Note(note) => format!("Note {note}"),
+            MidiControl::ControlChange(cc) => format!("CC {cc}"),
+        }
+    }
+}
+
+/// A console action a MIDI control can be mapped to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MidiMappingAction {
+    Go,
+    GoBack,
+    Stop,
+    /// Fader across 60-187 BPM
+    Bpm,
+    FxRateMaster,
+    FxSizeMaster,
+    /// Fader for a programmer channel, e.g. "dimmer", on the selected fixtures
+    ProgrammerChannel(String),
+    /// Endless encoder nudging a programmer channel up and down on the selected fixtures
+    ProgrammerEncoder {
+        channel: String,
+        encoding: RelativeEncoding,
+    },
+    RecallQuickSelect(usize),
+    GoToCue {
+        list_index: usize,
+        cue_index: usize,
+    },
+    /// Smoke while the button is held
+    Smoke,
+    /// Drive a group to full while the button is held
+    FlashGroup(usize),
+}
+
+/// How an endless encoder's CC value says which way and how far it turned
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RelativeEncoding {
+    /// 1 upwards for clockwise, 127 downwards for anticlockwise
+    TwosComplement,
+    /// Centred on 64, above for clockwise and below for anticlockwise
+    BinaryOffset,
+}
+
+impl RelativeEncoding {
+    pub fn all() -> [RelativeEncoding; 2] {
+        [
+            RelativeEncoding::TwosComplement,
+            RelativeEncoding::BinaryOffset,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelativeEncoding::TwosComplement => "Two's Complement",
+            RelativeEncoding::BinaryOffset => "64 Centred",
+        }
+    }
+
+    /// Steps turned for a CC value, negative for anticlockwise
+    pub fn delta(&self, value: u8) -> i32 {
+        let value = value.min(127) as i32;
+        match self {
+            RelativeEncoding::TwosComplement if value < 64 => value,
+            RelativeEncoding::TwosComplement => value - 128,
+            RelativeEncoding::BinaryOffset => value - 64,
+        }
+    }
+}
+
+/// Console state a mapping needs to turn a control into commands
+pub struct MidiMappingContext<'a> {
+    pub current_cue_list_index: usize,
+    pub selected_fixtures: &'a [usize],
+    pub fixture_groups: &'a [FixtureGroup],
+}
+
+/// Console state shown back on controllers
+pub struct MidiFeedbackState<'a> {
+    pub playing: bool,
+    pub current_cue_list_index: usize,
+    pub current_cue_index: Option<usize>,
+    /// Number of cues in each cue list
+    pub cue_list_lengths: &'a [usize],
+    pub bpm: f64,
+    pub fx_rate_master: f64,
+    pub fx_size_master: f64,
+    pub smoke_active: bool,
+    pub flashed_fixtures: &'a HashSet<usize>,
+    pub fixture_groups: &'a [FixtureGroup],
+    /// The programmer's value for a channel on the first selected fixture
+    pub programmer_value: &'a dyn Fn(&str) -> Option<u8>,
+}
+
+impl MidiMappingAction {
+    pub fn label(&self) -> String {
+        match self {
+            MidiMappingAction::Go => "Go".to_string(),
+            MidiMappingAction::GoBack => "Go Back".to_string(),
+            MidiMappingAction::Stop => "Stop".to_string(),
+            MidiMappingAction::Bpm => "BPM".to_string(),
+            MidiMappingAction::FxRateMaster => "FX Rate Master".to_string(),
+            MidiMappingAction::FxSizeMaster => "FX Size Master".to_string(),
+            MidiMappingAction::ProgrammerChannel(channel) => format!("Programmer {channel}"),
+            MidiMappingAction::ProgrammerEncoder { channel, .. } => {
+                format!("Programmer {channel} Encoder")
+            }
+            MidiMappingAction::RecallQuickSelect(number) => format!("Quick Select {number}"),
+            MidiMappingAction::GoToCue {
+                list_index,
+                cue_index,
+            } => format!("Cue {} in list {}", cue_index + 1, list_index + 1),
+            MidiMappingAction::Smoke => "Smoke".to_string(),
+            MidiMappingAction::FlashGroup(group_id) => format!("Flash Group {group_id}"),
+        }
+    }
+
+    /// The commands for a control moving to `value`
+    ///
+    /// Buttons fire when pressed past half way, so they ignore the release of a CC button.
+    pub fn commands(&self, value: u8, context: &MidiMappingContext) -> Vec<ConsoleCommand> {
+        let pressed = value >= 64;
+        let fraction = value as f64 / 127.0;
+        match self {
+            MidiMappingAction::Go if pressed => vec![ConsoleCommand::Play],
+            MidiMappingAction::GoBack if pressed => vec![ConsoleCommand::PrevCue {
+                list_index: context.current_cue_list_index,
+            }],
+            MidiMappingAction::Stop if pressed => vec![ConsoleCommand::Stop],
+            MidiMappingAction::RecallQuickSelect(number) if pressed => {
+                vec![ConsoleCommand::RecallQuickSelect { number: *number }]
+            }
+            MidiMappingAction::GoToCue {
+                list_index,
+                cue_index,
+            } if pressed => vec![ConsoleCommand::GoToCue {
+                list_index: *list_index,
+                cue_index: *cue_index,
+            }],
+            MidiMappingAction::Bpm => vec![ConsoleCommand::SetBpm {
+                bpm: 60.0 + fraction * (187.0 - 60.0),
+            }],
+            MidiMappingAction::FxRateMaster => vec![ConsoleCommand::SetFxRateMaster {
+                rate: fraction * 2.0,
+            }],
+            MidiMappingAction::FxSizeMaster => vec![ConsoleCommand::SetFxSizeMaster {
+                size: fraction * 2.0,
+            }],
+            MidiMappingAction::ProgrammerChannel(channel) => context
+                .selected_fixtures
+                .iter()
+                .map(|fixture_id| ConsoleCommand::SetProgrammerValue {
+                    fixture_id: *fixture_id,
+                    channel: channel.clone(),
+                    value: (fraction * 255.0).round() as u8,
+                })
+                .collect(),
+            MidiMappingAction::ProgrammerEncoder { channel, encoding } => {
+                let delta = encoding.delta(value);
+                if delta == 0 || context.selected_fixtures.is_empty() {
+                    return Vec::new();
+                }
+                vec![ConsoleCommand::AdjustProgrammerValue {
+                    fixture_ids: context.selected_fixtures.to_vec(),
+                    channel: channel.clone(),
+                    delta: delta as f32,
+                }]
+            }
+            MidiMappingAction::Smoke => vec![ConsoleCommand::SetSmoke { active: pressed }],
+            MidiMappingAction::FlashGroup(group_id) => context
+                .fixture_groups
+                .iter()
+                .find(|group| group.id == *group_id)
+                .map(|group| ConsoleCommand::SetFlash {
+                    fixture_ids: group.fixture_ids.clone(),
+                    active: pressed,
+                })
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The value to send a control showing this action, the reverse of `commands`
+    ///
+    /// Buttons light while their action is active, faders move to the current level.
+    pub fn feedback_value(&self, state: &MidiFeedbackState) -> u8 {
+        let lit = |on: bool| if on { 127 } else { 0 };
+        let fader = |fraction: f64| (fraction.clamp(0.0, 1.0) * 127.0).round() as u8;
+        match self {
+            MidiMappingAction::Go => lit(state.playing),
+            MidiMappingAction::Stop => lit(!state.playing),
+            MidiMappingAction::GoBack | MidiMappingAction::RecallQuickSelect(_) => 0,
+            MidiMappingAction::Bpm => fader((state.bpm - 60.0) / (187.0 - 60.0)),
+            MidiMappingAction::FxRateMaster => fader(state.fx_rate_master / 2.0),
+            MidiMappingAction::FxSizeMaster => fader(state.fx_size_master / 2.0),
+            // An encoder's LED ring shows the level like a fader would
+            MidiMappingAction::ProgrammerChannel(channel)
+            | MidiMappingAction::ProgrammerEncoder { channel, .. } => {
+                fader((state.programmer_value)(channel).unwrap_or(0) as f64 / 255.0)
+            }
+            MidiMappingAction::GoToCue {
+                list_index,
+                cue_index,
+            } => lit(state.current_cue_list_index == *list_index
+                && state.current_cue_index == Some(*cue_index)),
+            MidiMappingAction::Smoke => lit(state.smoke_active),
+            MidiMappingAction::FlashGroup(group_id) => lit(state
+                .fixture_groups
+                .iter()
+                .find(|group| group.id == *group_id)
+                .is_some_and(|group| {
+                    group
+                        .fixture_ids
+                        .iter()
+                        .any(|id| state.flashed_fixtures.contains(id))
+                })),
+        }
+    }
+}
+
+/// A learned mapping from a control to an action, saved in the user's settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MidiMapping {
+    pub control: MidiControl,
+    pub action: MidiMappingAction,
+}
+
+/// Store a learned mapping, replacing whatever the control was mapped to before
+///
+/// Other controls mapped to the same action keep it, so a pad and a footswitch can both fire Go.
+pub fn learn_mapping(mappings: &mut Vec<MidiMapping>, mapping: MidiMapping) {
+    mappings.retain(|m| m.control != mapping.control);
+    mappings.push(mapping);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_encodings() {
+        let twos = RelativeEncoding::TwosComplement;
+        assert_eq!((twos.delta(1), twos.delta(3), twos.delta(127)), (1, 3, -1));
+        assert_eq!(twos.delta(64), -64);
+        let offset = RelativeEncoding::BinaryOffset;
+        assert_eq!(
+            (offset.delta(65), offset.delta(64), offset.delta(62)),
+            (1, 0, -2)
+        );
+    }
+}
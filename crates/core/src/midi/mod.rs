@@ -1 +1,6 @@
+pub mod clock;
+pub mod grid;
+pub mod mapping;
 pub mod midi;
+pub mod profile;
+pub mod rtp;
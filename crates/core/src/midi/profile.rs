@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::midi::grid;
+use crate::midi::mapping::{MidiControl, MidiFeedbackState, MidiMapping, MidiMappingAction};
+
+/// Directory user profiles are loaded from, next to config.json
+pub const MIDI_PROFILE_DIR: &str = "midi_profiles";
+
+// Profiles shipped with Halo, a user profile with the same name replaces one of these
+const BUILT_IN_PROFILES: [&str; 2] = [
+    include_str!("profiles/akai_mpk49.json"),
+    include_str!("profiles/korg_nanokontrol2.json"),
+];
+
+/// Console state reflected back on a controller, lighting a button's LED or moving a motor fader
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MidiFeedback {
+    pub control: MidiControl,
+    /// The action whose state the control shows, e.g. Go lights while playing
+    pub action: MidiMappingAction,
+    /// Colors for an RGB pad, without one the LED is just on or off
+    #[serde(default)]
+    pub palette: Option<FeedbackPalette>,
+}
+
+impl MidiFeedback {
+    pub fn value(&self, state: &MidiFeedbackState) -> u8 {
+        let Some(palette) = &self.palette else {
+            return self.action.feedback_value(state);
+        };
+        match self.action {
+            MidiMappingAction::GoToCue {
+                list_index,
+                cue_index,
+            } => {
+                let exists = state
+                    .cue_list_lengths
+                    .get(list_index)
+                    .is_some_and(|len| cue_index < *len);
+                let current = state.current_cue_list_index == list_index
+                    && state.current_cue_index == Some(cue_index);
+                match (exists, current, state.playing) {
+                    (false, _, _) => palette.empty,
+                    (true, false, _) => palette.available,
+                    (true, true, false) => palette.active,
+                    (true, true, true) => palette.playing,
+                }
+            }
+            _ if self.action.feedback_value(state) >= 64 => palette.playing,
+            _ => palette.available,
+        }
+    }
+}
+
+/// Velocities picking colors from a controller's palette for each state of a pad
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FeedbackPalette {
+    /// Nothing mapped here, e.g. past the end of the cue list
+    pub empty: u8,
+    pub available: u8,
+    /// The current cue while stopped
+    pub active: u8,
+    /// The current cue while playing, or an active button
+    pub playing: u8,
+}
+
+/// The mappings for a model of MIDI controller
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MidiProfile {
+    pub name: String,
+    pub mappings: Vec<MidiMapping>,
+    #[serde(default)]
+    pub feedback: Vec<MidiFeedback>,
+    /// Raw messages sent when the profile is selected, e.g. to switch a grid into a mode that
+    /// lets us light its pads
+    #[serde(default)]
+    pub init: Vec<Vec<u8>>,
+}
+
+impl MidiProfile {
+    /// The built in profiles, plus any `.json` profiles in `user_dir`
+    pub fn load_all(user_dir: &Path) -> Vec<MidiProfile> {
+        let mut profiles: Vec<MidiProfile> = BUILT_IN_PROFILES
+            .iter()
+            .map(|json| serde_json::from_str(json).expect("built in MIDI profile is valid"))
+            .chain(grid::grid_profiles())
+            .collect();
+
+        let Ok(entries) = fs::read_dir(user_dir) else {
+            return profiles;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let profile = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| {
+                    serde_json::from_str::<MidiProfile>(&json).map_err(|e| e.to_string())
+                });
+            match profile {
+                Ok(profile) => {
+                    log::info!(
+                        "Loaded MIDI profile {} from {}",
+                        profile.name,
+                        path.display()
+                    );
+                    profiles.retain(|p| p.name != profile.name);
+                    profiles.push(profile);
+                }
+                Err(e) => log::warn!("Skipping MIDI profile {}: {e}", path.display()),
+            }
+        }
+        profiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_profiles_load() {
+        let profiles = MidiProfile::load_all(Path::new("no_such_dir"));
+        assert_eq!(
+            profiles.len(),
+            BUILT_IN_PROFILES.len() + grid::grid_profiles().len()
+        );
+        assert!(profiles.iter().any(|p| p.name == "Akai MPK49"));
+        assert!(profiles.iter().all(|p| !p.mappings.is_empty()));
+    }
+}
@@ -0,0 +1,315 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Name of the MIDI device that means "listen for RTP-MIDI on the network"
+pub const RTP_MIDI_DEVICE: &str = "Network (RTP-MIDI)";
+const SESSION_NAME: &str = "Halo";
+
+const SIGNATURE: [u8; 2] = [0xFF, 0xFF];
+const PROTOCOL_VERSION: u32 = 2;
+const RTP_PAYLOAD_TYPE: u8 = 0x61;
+// The long command section header has a 12 bit length
+const MAX_MESSAGE_LEN: usize = 0x0FFF;
+// Pause after a failed receive, e.g. ICMP port unreachable from a peer that went away, so the
+// loop doesn't spin on an error that comes straight back
+const RECEIVE_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// An AppleMIDI (RTP-MIDI) session listener, the way macOS Network MIDI and rtpMIDI connect
+///
+/// Halo doesn't advertise itself over Bonjour, so peers add it by address and port. The control
+/// port is the configured port, and the data port the one after it.
+pub struct RtpMidiSession {
+    data_socket: Arc<UdpSocket>,
+    peer: Arc<Mutex<Option<SocketAddr>>>,
+    ssrc: u32,
+    started: Instant,
+    sequence: u16,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl RtpMidiSession {
+    /// Start listening, received MIDI messages are sent raw to `tx`
+    pub async fn listen(port: u16, tx: mpsc::Sender<Vec<u8>>) -> std::io::Result<Self> {
+        let data_port = port.checked_add(1).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("RTP-MIDI port {port} leaves no room for the data port"),
+            )
+        })?;
+        let control_socket = Arc::new(UdpSocket::bind(("0.0.0.0", port)).await?);
+        let data_socket = Arc::new(UdpSocket::bind(("0.0.0.0", data_port)).await?);
+        let peer = Arc::new(Mutex::new(None));
+        let started = Instant::now();
+        let ssrc = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0x4841_4C4F);
+
+        let tasks = vec![
+            tokio::spawn(receive(control_socket, None, peer.clone(), ssrc, started)),
+            tokio::spawn(receive(
+                data_socket.clone(),
+                Some(tx),
+                peer.clone(),
+                ssrc,
+                started,
+            )),
+        ];
+
+        Ok(Self {
+            data_socket,
+            peer,
+            ssrc,
+            started,
+            sequence: 0,
+            tasks,
+        })
+    }
+
+    /// Send a MIDI message to the connected peer, dropped if nobody is connected
+    /// Messages too long for one command section (over 4095 bytes) are dropped
+    pub fn send(&mut self, message: &[u8]) {
+        let Some(peer) = *self.peer.lock().unwrap() else {
+            return;
+        };
+        if message.len() > MAX_MESSAGE_LEN {
+            log::warn!(
+                "Dropped {} byte MIDI message, RTP-MIDI sends at most {MAX_MESSAGE_LEN}",
+                message.len()
+            );
+            return;
+        }
+        self.sequence = self.sequence.wrapping_add(1);
+
+        let mut packet = Vec::with_capacity(14 + message.len());
+        packet.extend_from_slice(&[0x80, 0x80 | RTP_PAYLOAD_TYPE]);
+        packet.extend_from_slice(&self.sequence.to_be_bytes());
+        packet.extend_from_slice(&(timestamp(self.started) as u32).to_be_bytes());
+        packet.extend_from_slice(&self.ssrc.to_be_bytes());
+        // Command section header, the long form (B flag) when it won't fit in four bits
+        if message.len() < 16 {
+            packet.push(message.len() as u8);
+        } else {
+            packet.push(0x80 | ((message.len() >> 8) as u8 & 0x0F));
+            packet.push(message.len() as u8);
+        }
+        packet.extend_from_slice(message);
+
+        if let Err(e) = self.data_socket.try_send_to(&packet, peer) {
+            log::warn!("Failed to send RTP-MIDI: {}", e);
+        }
+    }
+}
+
+impl Drop for RtpMidiSession {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+// RTP-MIDI timestamps count in units of 100 microseconds
+fn timestamp(started: Instant) -> u64 {
+    (started.elapsed().as_micros() / 100) as u64
+}
+
+async fn receive(
+    socket: Arc<UdpSocket>,
+    midi_tx: Option<mpsc::Sender<Vec<u8>>>,
+    peer: Arc<Mutex<Option<SocketAddr>>>,
+    ssrc: u32,
+    started: Instant,
+) {
+    let mut buffer = [0u8; 1500];
+    // Only the first of a run of receive errors is logged
+    let mut failing = false;
+    loop {
+        let (len, from) = match socket.recv_from(&mut buffer).await {
+            Ok(received) => {
+                failing = false;
+                received
+            }
+            Err(e) => {
+                if !failing {
+                    log::warn!("RTP-MIDI receive failed: {e}");
+                    failing = true;
+                }
+                tokio::time::sleep(RECEIVE_ERROR_BACKOFF).await;
+                continue;
+            }
+        };
+        let packet = &buffer[..len];
+
+        if packet.starts_with(&SIGNATURE) {
+            match packet.get(2..4) {
+                // Invited on both ports, the data port is where MIDI goes back to
+                Some(b"IN") if midi_tx.is_some() => *peer.lock().unwrap() = Some(from),
+                Some(b"BY") => *peer.lock().unwrap() = None,
+                _ => {}
+            }
+            if let Some(reply) = session_reply(packet, from, ssrc, started) {
+                let _ = socket.send_to(&reply, from).await;
+            }
+        } else if let Some(tx) = &midi_tx {
+            for message in parse_rtp_midi(packet) {
+                if let Err(e) = tx.try_send(message) {
+                    log::warn!("Failed to send RTP-MIDI message: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Answer an AppleMIDI session packet, accepting invitations and taking part in clock sync
+fn session_reply(packet: &[u8], from: SocketAddr, ssrc: u32, started: Instant) -> Option<Vec<u8>> {
+    match packet.get(2..4)? {
+        b"IN" => {
+            let token = packet.get(8..12)?;
+            let name = String::from_utf8_lossy(packet.get(16..)?.split(|b| *b == 0).next()?);
+            log::info!("RTP-MIDI invitation from {} at {}", name, from);
+
+            let mut reply = Vec::new();
+            reply.extend_from_slice(&SIGNATURE);
+            reply.extend_from_slice(b"OK");
+            reply.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+            reply.extend_from_slice(token);
+            reply.extend_from_slice(&ssrc.to_be_bytes());
+            reply.extend_from_slice(SESSION_NAME.as_bytes());
+            reply.push(0);
+            Some(reply)
+        }
+        b"CK" => {
+            // Count 0 and 2 come from the initiator, we fill in our timestamp and pass it back
+            let count = *packet.get(8)?;
+            if count != 0 {
+                return None;
+            }
+            let mut reply = packet.get(..36)?.to_vec();
+            reply[4..8].copy_from_slice(&ssrc.to_be_bytes());
+            reply[8] = 1;
+            reply[20..28].copy_from_slice(&timestamp(started).to_be_bytes());
+            Some(reply)
+        }
+        b"BY" => {
+            log::info!("RTP-MIDI session with {} ended", from);
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Data bytes after a status byte, None for SysEx which runs until F7
+fn data_len(status: u8) -> Option<usize> {
+    match status {
+        0x80..=0xBF | 0xE0..=0xEF | 0xF2 => Some(2),
+        0xC0..=0xDF | 0xF1 | 0xF3 => Some(1),
+        0xF0 => None,
+        _ => Some(0),
+    }
+}
+
+/// The MIDI messages in an RTP-MIDI packet, the recovery journal is ignored
+pub fn parse_rtp_midi(packet: &[u8]) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    if packet.len() < 13 || packet[1] & 0x7F != RTP_PAYLOAD_TYPE {
+        return messages;
+    }
+
+    let flags = packet[12];
+    let (len, start) = if flags & 0x80 != 0 {
+        let Some(low) = packet.get(13) else {
+            return messages;
+        };
+        (((flags as usize & 0x0F) << 8) | *low as usize, 14)
+    } else {
+        (flags as usize & 0x0F, 13)
+    };
+    let Some(list) = packet.get(start..start + len) else {
+        return messages;
+    };
+
+    // Z flag: the first command has a delta time, later ones always do
+    let mut has_delta = flags & 0x20 != 0;
+    let mut running_status = None;
+    let mut i = 0;
+    while i < list.len() {
+        if has_delta {
+            while i < list.len() && list[i] & 0x80 != 0 {
+                i += 1;
+            }
+            i += 1;
+        }
+        has_delta = true;
+        let Some(&byte) = list.get(i) else {
+            break;
+        };
+
+        let status = if byte & 0x80 != 0 {
+            i += 1;
+            byte
+        } else if let Some(status) = running_status {
+            status
+        } else {
+            break;
+        };
+
+        let end = match data_len(status) {
+            Some(len) => i + len,
+            None => match list[i..].iter().position(|b| *b == 0xF7) {
+                Some(pos) => i + pos + 1,
+                None => break,
+            },
+        };
+        let Some(data) = list.get(i..end) else {
+            break;
+        };
+
+        let mut message = vec![status];
+        message.extend_from_slice(data);
+        messages.push(message);
+        if status < 0xF0 {
+            running_status = Some(status);
+        }
+        i = end;
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(command_section: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0x80, RTP_PAYLOAD_TYPE, 0, 1, 0, 0, 0, 0, 1, 2, 3, 4];
+        packet.extend_from_slice(command_section);
+        packet
+    }
+
+    #[test]
+    fn test_parse_with_running_status_and_delta_times() {
+        // Note on, then a second note on using running status after a delta time
+        let packet = packet(&[0x06, 0x90, 60, 100, 0x00, 62, 90]);
+        assert_eq!(
+            parse_rtp_midi(&packet),
+            vec![vec![0x90, 60, 100], vec![0x90, 62, 90]]
+        );
+    }
+
+    #[test]
+    fn test_parse_long_command_section_with_sysex() {
+        let sysex = [0xF0, 0x7F, 0x7F, 0x01, 0x01, 1, 2, 3, 4, 0xF7];
+        let mut section = vec![0x80, (sysex.len() + 3) as u8];
+        section.extend_from_slice(&sysex);
+        section.extend_from_slice(&[0x00, 0xF1, 0x12]);
+        assert_eq!(
+            parse_rtp_midi(&packet(&section)),
+            vec![sysex.to_vec(), vec![0xF1, 0x12]]
+        );
+    }
+}
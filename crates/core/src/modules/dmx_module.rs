@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use async_trait::async_trait;
 use tokio::sync::mpsc;
@@ -7,6 +7,13 @@ use tokio::time::{interval, Duration, Instant};
 use super::traits::{AsyncModule, ModuleEvent, ModuleId, ModuleMessage};
 use crate::artnet::artnet::ArtNet;
 use crate::artnet::network_config::NetworkConfig;
+use crate::artnet::network_status::{
+    ArtNetDiscovery, DestinationStatus, NetworkStatus, UniverseStatus,
+};
+
+const MAX_RECENT_ERRORS: usize = 10;
+// Polls go out every few status reports, as the Art-Net spec expects of controllers
+const POLL_EVERY_REPORTS: u64 = 3;
 
 pub struct DmxModule {
     artnet_connections: Vec<Option<ArtNet>>, // Multiple ArtNet instances
@@ -15,6 +22,13 @@ pub struct DmxModule {
     frames_sent: u64,
     target_fps: f64,
     status: HashMap<String, String>,
+    // Packets sent per universe since the last network status report
+    packet_counts: HashMap<u8, u64>,
+    frames_at_last_report: u64,
+    socket_errors: u64,
+    recent_errors: VecDeque<String>,
+    discovery: Option<ArtNetDiscovery>,
+    discovery_error: Option<String>,
 }
 
 impl DmxModule {
@@ -32,12 +46,131 @@ impl DmxModule {
             frames_sent: 0,
             target_fps: 44.0, // DMX standard 44Hz
             status: HashMap::new(),
+            packet_counts: HashMap::new(),
+            frames_at_last_report: 0,
+            socket_errors: 0,
+            recent_errors: VecDeque::new(),
+            discovery: None,
+            discovery_error: None,
         }
     }
 
     pub fn set_target_fps(&mut self, fps: f64) {
         self.target_fps = fps;
     }
+
+    /// Start or stop node discovery, which is only for the network status panel so output
+    /// carries on either way
+    fn set_discovery(&mut self, enabled: bool) {
+        if !enabled {
+            self.discovery = None;
+            self.discovery_error = None;
+            self.status.remove("discovery");
+            return;
+        }
+        if self.discovery.is_some() {
+            return;
+        }
+
+        match ArtNetDiscovery::new() {
+            Ok(discovery) => {
+                if let Err(e) = discovery.poll() {
+                    log::debug!("ArtPoll failed: {e}");
+                }
+                self.discovery = Some(discovery);
+                self.discovery_error = None;
+                self.status
+                    .insert("discovery".to_string(), "running".to_string());
+            }
+            Err(e) => {
+                log::warn!("Art-Net node discovery unavailable: {e}");
+                self.discovery_error = Some(format!("Discovery unavailable: {e}"));
+                self.status
+                    .insert("discovery".to_string(), "degraded".to_string());
+            }
+        }
+    }
+
+    fn record_send_error(&mut self, universe: u8, destination_index: usize, error: anyhow::Error) {
+        self.socket_errors += 1;
+        let destination = &self.network_config.destinations[destination_index].name;
+        let message = format!("Universe {universe} to {destination}: {error}");
+        // Errors repeat every frame while a network is down, only log when something changes
+        if self.recent_errors.back() != Some(&message) {
+            log::warn!("Art-Net send failed: {message}");
+            if self.recent_errors.len() == MAX_RECENT_ERRORS {
+                self.recent_errors.pop_front();
+            }
+            self.recent_errors.push_back(message);
+        }
+    }
+
+    fn network_status(
+        &mut self,
+        active_universes: &HashMap<u8, Vec<u8>>,
+        elapsed: Duration,
+    ) -> NetworkStatus {
+        let config = &self.network_config;
+        let destinations = config
+            .destinations
+            .iter()
+            .enumerate()
+            .map(|(index, destination)| {
+                let mut universes: Vec<u8> = config
+                    .universe_routing
+                    .iter()
+                    .filter(|(_, dest)| **dest == index)
+                    .map(|(universe, _)| *universe)
+                    .collect();
+                universes.sort_unstable();
+                DestinationStatus {
+                    name: destination.name.clone(),
+                    address: config.get_destination_string(&destination.mode),
+                    universes,
+                }
+            })
+            .collect();
+
+        let mut universe_ids: Vec<u8> = active_universes
+            .keys()
+            .chain(config.universe_routing.keys())
+            .copied()
+            .collect();
+        universe_ids.sort_unstable();
+        universe_ids.dedup();
+        let seconds = elapsed.as_secs_f32().max(f32::EPSILON);
+        let universes = universe_ids
+            .into_iter()
+            .map(|universe| UniverseStatus {
+                universe,
+                destination: config
+                    .get_destination_for_universe(universe)
+                    .and_then(|index| config.destinations.get(index))
+                    .map(|destination| destination.name.clone()),
+                packets_per_sec: self.packet_counts.get(&universe).copied().unwrap_or(0) as f32
+                    / seconds,
+            })
+            .collect();
+        self.packet_counts.clear();
+        let output_fps = (self.frames_sent - self.frames_at_last_report) as f32 / seconds;
+        self.frames_at_last_report = self.frames_sent;
+
+        let nodes = self
+            .discovery
+            .as_mut()
+            .map(|discovery| discovery.receive())
+            .unwrap_or_default();
+
+        NetworkStatus {
+            destinations,
+            universes,
+            nodes,
+            output_fps,
+            discovery_error: self.discovery_error.clone(),
+            socket_errors: self.socket_errors,
+            recent_errors: self.recent_errors.iter().cloned().collect(),
+        }
+    }
 }
 
 #[async_trait]
@@ -99,6 +232,9 @@ impl AsyncModule for DmxModule {
         let mut frame_interval = interval(frame_duration);
 
         let mut last_dmx_data: HashMap<u8, Vec<u8>> = HashMap::new();
+        let mut status_interval = interval(Duration::from_secs(1));
+        let mut last_report = Instant::now();
+        let mut reports_sent: u64 = 0;
         let mut shutdown = false;
 
         log::info!(
@@ -124,6 +260,7 @@ impl AsyncModule for DmxModule {
                         ModuleEvent::DmxOutput(universe, data) => {
                             last_dmx_data.insert(universe, data);
                         }
+                        ModuleEvent::NodeDiscovery(enabled) => self.set_discovery(enabled),
                         ModuleEvent::Shutdown => {
                             log::info!("DMX module received shutdown signal");
                             shutdown = true;
@@ -143,7 +280,10 @@ impl AsyncModule for DmxModule {
                     for (universe, data) in &last_dmx_data {
                         if let Some(dest_index) = self.network_config.get_destination_for_universe(*universe) {
                             if let Some(Some(artnet)) = self.artnet_connections.get(dest_index) {
-                                artnet.send_data(*universe, data.clone());
+                                match artnet.send_data(*universe, data.clone()) {
+                                    Ok(()) => *self.packet_counts.entry(*universe).or_insert(0) += 1,
+                                    Err(e) => self.record_send_error(*universe, dest_index, e),
+                                }
                             } else {
                                 log::warn!("No ArtNet connection found for destination index {}", dest_index);
                             }
@@ -169,6 +309,20 @@ impl AsyncModule for DmxModule {
                         ))).await;
                     }
                 }
+
+                // Report network health for the status panel
+                _ = status_interval.tick() => {
+                    if reports_sent.is_multiple_of(POLL_EVERY_REPORTS) {
+                        if let Some(Err(e)) = self.discovery.as_ref().map(|d| d.poll()) {
+                            log::debug!("ArtPoll failed: {e}");
+                        }
+                    }
+                    reports_sent += 1;
+
+                    let status = self.network_status(&last_dmx_data, last_report.elapsed());
+                    last_report = Instant::now();
+                    let _ = tx.send(ModuleMessage::NetworkStatus(status)).await;
+                }
             }
         }
 
@@ -3,12 +3,63 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use midir::{MidiInput, MidiInputConnection, MidiOutput, MidiOutputConnection};
 use tokio::sync::mpsc;
+use tokio::time::{interval, Duration, MissedTickBehavior};
 
 use super::traits::{AsyncModule, ModuleEvent, ModuleId, ModuleMessage};
+use crate::midi::clock::MidiClockEncoder;
 use crate::midi::midi::MidiMessage;
+use crate::midi::rtp::{RtpMidiSession, RTP_MIDI_DEVICE};
+use crate::timecode::mtc::{MtcDecoder, MtcEncoder};
+
+/// Turn a raw MIDI message into a module event, MIDI timecode is assembled across messages
+fn parse_input(mtc_decoder: &mut MtcDecoder, message: &[u8]) -> Option<ModuleEvent> {
+    // MIDI timecode for chasing a playback rig
+    if let Some(timecode) = mtc_decoder.decode(message) {
+        return Some(ModuleEvent::SmpteSync { timecode });
+    }
+
+    if message.len() < 3 {
+        return None;
+    }
+    let midi_msg = match message[0] & 0xF0 {
+        0xF8 => MidiMessage::Clock,
+        0x90 => {
+            // Note On
+            if message[2] > 0 {
+                MidiMessage::NoteOn(message[1], message[2])
+            } else {
+                MidiMessage::NoteOff(message[1])
+            }
+        }
+        0x80 => MidiMessage::NoteOff(message[1]),
+        0xB0 => MidiMessage::ControlChange(message[1], message[2]),
+        _ => return None,
+    };
+    Some(ModuleEvent::MidiInput(midi_msg))
+}
+
+/// Where MIDI output goes, a local port or the peer of a network session
+enum MidiOutputTarget {
+    Port(MidiOutputConnection),
+    Network(RtpMidiSession),
+}
+
+impl MidiOutputTarget {
+    fn send(&mut self, message: &[u8]) {
+        match self {
+            MidiOutputTarget::Port(conn) => {
+                if let Err(e) = conn.send(message) {
+                    log::warn!("Failed to send MIDI output: {}", e);
+                }
+            }
+            MidiOutputTarget::Network(session) => session.send(message),
+        }
+    }
+}
 
 pub struct MidiModule {
     device_name: String,
+    rtp_port: u16,
     midi_sender: Option<mpsc::Sender<ModuleMessage>>,
     status: HashMap<String, String>,
 }
@@ -17,11 +68,18 @@ impl MidiModule {
     pub fn new(device_name: String) -> Self {
         Self {
             device_name,
+            rtp_port: 5004,
             midi_sender: None,
             status: HashMap::new(),
         }
     }
 
+    /// Control port to listen on when the device is the RTP-MIDI network session
+    pub fn with_rtp_port(mut self, rtp_port: u16) -> Self {
+        self.rtp_port = rtp_port;
+        self
+    }
+
     fn connect_midi(
         &mut self,
         tx: mpsc::Sender<ModuleMessage>,
@@ -45,35 +103,17 @@ impl MidiModule {
             .ok_or_else(|| format!("{} input not found", self.device_name))?;
 
         let tx_clone = tx.clone();
+        let mut mtc_decoder = MtcDecoder::default();
         let connection = midi_in
             .connect(
                 &in_port,
                 "async-midi-input",
                 move |_timestamp, message, _| {
-                    if message.len() >= 3 {
-                        let midi_msg = match message[0] & 0xF0 {
-                            0xF8 => Some(MidiMessage::Clock),
-                            0x90 => {
-                                // Note On
-                                if message[2] > 0 {
-                                    Some(MidiMessage::NoteOn(message[1], message[2]))
-                                } else {
-                                    Some(MidiMessage::NoteOff(message[1]))
-                                }
-                            }
-                            0x80 => Some(MidiMessage::NoteOff(message[1])),
-                            0xB0 => Some(MidiMessage::ControlChange(message[1], message[2])),
-                            _ => None,
-                        };
-
-                        if let Some(midi_msg) = midi_msg {
-                            let event = ModuleEvent::MidiInput(midi_msg);
-
-                            // Since we're in a callback, we need to use try_send
-                            // to avoid blocking if the channel is full
-                            if let Err(e) = tx_clone.try_send(ModuleMessage::Event(event)) {
-                                log::warn!("Failed to send MIDI message: {}", e);
-                            }
+                    if let Some(event) = parse_input(&mut mtc_decoder, message) {
+                        // Since we're in a callback, we need to use try_send
+                        // to avoid blocking if the channel is full
+                        if let Err(e) = tx_clone.try_send(ModuleMessage::Event(event)) {
+                            log::warn!("Failed to send MIDI message: {}", e);
                         }
                     }
                 },
@@ -108,6 +148,15 @@ impl MidiModule {
 
         Ok((connection, output_connection))
     }
+
+    fn send_output(output: Option<&mut MidiOutputTarget>, messages: Vec<Vec<u8>>) {
+        let Some(output) = output else {
+            return;
+        };
+        for message in messages {
+            output.send(&message);
+        }
+    }
 }
 
 #[async_trait]
@@ -138,13 +187,35 @@ impl AsyncModule for MidiModule {
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         log::info!("MIDI module starting for device: {}", self.device_name);
 
-        // Connect to MIDI device
-        let _input_conn;
-        let _output_conn;
-        match self.connect_midi(tx.clone()) {
-            Ok((input, output)) => {
-                _input_conn = input;
-                _output_conn = output;
+        // Local ports deliver input from midir's callback, a network session through here
+        let mut network_rx = None;
+        let mut mtc_decoder = MtcDecoder::default();
+        let mut _input_conn = None;
+        let mut output = None;
+        let connected: Result<(), Box<dyn std::error::Error + Send + Sync>> =
+            if self.device_name == RTP_MIDI_DEVICE {
+                let (network_tx, receiver) = mpsc::channel(256);
+                match RtpMidiSession::listen(self.rtp_port, network_tx).await {
+                    Ok(session) => {
+                        network_rx = Some(receiver);
+                        output = Some(MidiOutputTarget::Network(session));
+                        self.status
+                            .insert("input_connected".to_string(), "true".to_string());
+                        self.status
+                            .insert("output_connected".to_string(), "true".to_string());
+                        Ok(())
+                    }
+                    Err(e) => Err(format!("can't listen on port {}: {}", self.rtp_port, e).into()),
+                }
+            } else {
+                self.connect_midi(tx.clone()).map(|(input, output_conn)| {
+                    _input_conn = Some(input);
+                    output = Some(MidiOutputTarget::Port(output_conn));
+                })
+            };
+
+        match connected {
+            Ok(()) => {
                 log::info!("MIDI device '{}' connected successfully", self.device_name);
                 let _ = tx
                     .send(ModuleMessage::Status(format!(
@@ -166,17 +237,58 @@ impl AsyncModule for MidiModule {
             }
         }
 
+        // Quarter frames go out far faster than the console updates, so MTC is timed here
+        let mut mtc_encoder = MtcEncoder::default();
+        let mut quarter_frames = interval(mtc_encoder.quarter_frame_period());
+        quarter_frames.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        // Checked every millisecond, so clock pulses are at most a millisecond late
+        let mut clock_encoder = MidiClockEncoder::default();
+        let mut clock_ticks = interval(Duration::from_millis(1));
+        clock_ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
         // Main event loop
-        while let Some(event) = rx.recv().await {
-            match event {
-                ModuleEvent::Shutdown => {
-                    log::info!("MIDI module received shutdown signal");
-                    break;
+        loop {
+            tokio::select! {
+                event = rx.recv() => match event {
+                    Some(ModuleEvent::Shutdown) | None => {
+                        log::info!("MIDI module received shutdown signal");
+                        break;
+                    }
+                    Some(ModuleEvent::MtcOutput(timecode)) => {
+                        let period = mtc_encoder.quarter_frame_period();
+                        mtc_encoder.set_timecode(timecode);
+                        if mtc_encoder.quarter_frame_period() != period {
+                            quarter_frames = interval(mtc_encoder.quarter_frame_period());
+                            quarter_frames.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                        }
+                    }
+                    Some(ModuleEvent::MidiOutput(message)) => {
+                        Self::send_output(output.as_mut(), vec![message]);
+                    }
+                    Some(ModuleEvent::MidiClockOutput { clock, playing }) => {
+                        let messages = clock_encoder.set_clock(clock, playing);
+                        Self::send_output(output.as_mut(), messages);
+                    }
+                    Some(_) => {
+                        // MIDI input arrives from the device, other events aren't for us
+                    }
+                },
+                Some(message) = async {
+                    match network_rx.as_mut() {
+                        Some(rx) => rx.recv().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    if let Some(event) = parse_input(&mut mtc_decoder, &message) {
+                        let _ = tx.send(ModuleMessage::Event(event)).await;
+                    }
+                }
+                _ = quarter_frames.tick(), if mtc_encoder.is_running() => {
+                    Self::send_output(output.as_mut(), mtc_encoder.tick());
                 }
-                _ => {
-                    // MIDI module primarily handles input via the callback
-                    // Other events are ignored for now, but could be extended
-                    // to handle MIDI output commands in the future
+                _ = clock_ticks.tick(), if clock_encoder.is_running() => {
+                    Self::send_output(output.as_mut(), clock_encoder.tick());
                 }
             }
         }
@@ -34,6 +34,17 @@ pub enum ModuleEvent {
     },
     /// MIDI input events
     MidiInput(crate::midi::midi::MidiMessage),
+    /// Raw message to send to the MIDI device, e.g. controller feedback
+    MidiOutput(Vec<u8>),
+    /// Timecode to send as MTC, None while the transport is stopped
+    MtcOutput(Option<crate::timecode::timecode::TimeCode>),
+    /// Beat position and tempo to send as MIDI clock, None to stop sending clock
+    MidiClockOutput {
+        clock: Option<(f64, f64)>,
+        playing: bool,
+    },
+    /// Start or stop Art-Net node discovery, which holds port 6454 while it runs
+    NodeDiscovery(bool),
     /// System events
     Shutdown,
 }
@@ -44,6 +55,8 @@ pub enum ModuleMessage {
     Event(ModuleEvent),
     Status(String),
     Error(String),
+    /// Art-Net output health from the DMX module
+    NetworkStatus(crate::artnet::network_status::NetworkStatus),
 }
 
 /// Trait that all async modules must implement
@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Console timing for the performance overlay, reported about once a second
+#[derive(Clone, Debug, Default)]
+pub struct PerformanceMetrics {
+    /// Average time spent in one console update, in milliseconds
+    pub update_time_ms: f32,
+    /// Worst difference between the actual and target time between updates, in milliseconds
+    pub update_jitter_ms: f32,
+    /// UI commands waiting to be processed by the console
+    pub command_queue_depth: usize,
+    /// Module messages waiting to be processed by the console
+    pub module_queue_depth: usize,
+    /// DMX frames actually sent per second, as measured by the DMX module
+    pub dmx_fps: f32,
+}
+
+/// Collects update loop timings between reports
+pub struct PerformanceTracker {
+    target_interval: Duration,
+    window_start: Instant,
+    last_tick: Option<Instant>,
+    updates: u32,
+    update_time: Duration,
+    max_jitter: Duration,
+}
+
+impl PerformanceTracker {
+    pub fn new(target_interval: Duration) -> Self {
+        Self {
+            target_interval,
+            window_start: Instant::now(),
+            last_tick: None,
+            updates: 0,
+            update_time: Duration::ZERO,
+            max_jitter: Duration::ZERO,
+        }
+    }
+
+    /// Record an update that started at `started` and took `elapsed`
+    pub fn record_update(&mut self, started: Instant, elapsed: Duration) {
+        if let Some(last_tick) = self.last_tick {
+            let interval = started.duration_since(last_tick);
+            let jitter = interval.abs_diff(self.target_interval);
+            self.max_jitter = self.max_jitter.max(jitter);
+        }
+        self.last_tick = Some(started);
+        self.updates += 1;
+        self.update_time += elapsed;
+    }
+
+    /// Loop metrics for the window so far once a report is due, starting a new window
+    pub fn take_report(&mut self, now: Instant) -> Option<PerformanceMetrics> {
+        if now.duration_since(self.window_start) < REPORT_INTERVAL {
+            return None;
+        }
+
+        let metrics = PerformanceMetrics {
+            update_time_ms: if self.updates == 0 {
+                0.0
+            } else {
+                self.update_time.as_secs_f32() * 1000.0 / self.updates as f32
+            },
+            update_jitter_ms: self.max_jitter.as_secs_f32() * 1000.0,
+            ..PerformanceMetrics::default()
+        };
+
+        self.window_start = now;
+        self.updates = 0;
+        self.update_time = Duration::ZERO;
+        self.max_jitter = Duration::ZERO;
+        Some(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_worst_jitter_and_average_update_time() {
+        let start = Instant::now();
+        let mut tracker = PerformanceTracker::new(Duration::from_millis(20));
+        tracker.record_update(start, Duration::from_millis(2));
+        tracker.record_update(start + Duration::from_millis(20), Duration::from_millis(4));
+        // A late tick, 15ms behind
+        tracker.record_update(start + Duration::from_millis(55), Duration::from_millis(6));
+
+        assert!(tracker.take_report(start).is_none());
+        let metrics = tracker.take_report(start + Duration::from_secs(2)).unwrap();
+        assert!((metrics.update_time_ms - 4.0).abs() < 0.01);
+        assert!((metrics.update_jitter_ms - 15.0).abs() < 0.01);
+    }
+}
@@ -1,5 +1,9 @@
-pub use pixel_effects::{PixelEffect, PixelEffectParams, PixelEffectScope, PixelEffectType};
+pub use pixel_effects::{
+    PixelBlendMode, PixelEffect, PixelEffectParams, PixelEffectScope, PixelEffectType, PixelLayer,
+    PixelText, ScrollDirection,
+};
 pub use pixel_engine::PixelEngine;
 
 mod pixel_effects;
 mod pixel_engine;
+mod pixel_font;
@@ -2,7 +2,8 @@ use std::f64::consts::PI;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Interval, RhythmState};
+use super::pixel_font::{TextBitmap, GLYPH_HEIGHT};
+use crate::{Interval, RhythmState, Subdivision};
 
 /// Pixel-specific effect types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -11,6 +12,8 @@ pub enum PixelEffectType {
     Wave,
     Strobe,
     ColorCycle,
+    /// Scrolling text across the pixel matrix
+    Text,
 }
 
 impl PixelEffectType {
@@ -20,6 +23,7 @@ impl PixelEffectType {
             PixelEffectType::Wave => "Wave",
             PixelEffectType::Strobe => "Strobe",
             PixelEffectType::ColorCycle => "ColorCycle",
+            PixelEffectType::Text => "Text",
         }
     }
 
@@ -29,6 +33,7 @@ impl PixelEffectType {
             PixelEffectType::Wave,
             PixelEffectType::Strobe,
             PixelEffectType::ColorCycle,
+            PixelEffectType::Text,
         ]
     }
 }
@@ -42,6 +47,98 @@ pub enum PixelEffectScope {
     Individual,
 }
 
+/// Direction text travels across the pixel matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScrollDirection {
+    /// Text enters on the right and travels left
+    #[default]
+    Left,
+    /// Text enters on the left and travels right
+    Right,
+}
+
+/// Text scroller configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PixelText {
+    pub content: String,
+    pub direction: ScrollDirection,
+}
+
+/// How a pixel effect layer combines with the layers beneath it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PixelBlendMode {
+    /// Sum with the layers below (clamped to full)
+    #[default]
+    Add,
+    /// Multiply the layers below, useful for masking
+    Multiply,
+    /// Take the brighter of this layer and the layers below per channel
+    Max,
+}
+
+impl PixelBlendMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PixelBlendMode::Add => "Add",
+            PixelBlendMode::Multiply => "Multiply",
+            PixelBlendMode::Max => "Max",
+        }
+    }
+
+    pub fn all() -> Vec<PixelBlendMode> {
+        vec![
+            PixelBlendMode::Add,
+            PixelBlendMode::Multiply,
+            PixelBlendMode::Max,
+        ]
+    }
+
+    /// Blend a single normalized channel
+    fn blend(&self, base: f64, layer: f64) -> f64 {
+        match self {
+            PixelBlendMode::Add => base + layer,
+            PixelBlendMode::Multiply => base * layer,
+            PixelBlendMode::Max => base.max(layer),
+        }
+    }
+}
+
+/// Layer settings controlling how an effect is composited by the PixelEngine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PixelLayer {
+    /// Stacking order, lower layers are composited first
+    pub order: i32,
+    /// Layer opacity from 0.0 to 1.0
+    pub opacity: f64,
+    pub blend_mode: PixelBlendMode,
+}
+
+impl Default for PixelLayer {
+    fn default() -> Self {
+        Self {
+            order: 0,
+            opacity: 1.0,
+            blend_mode: PixelBlendMode::Add,
+        }
+    }
+}
+
+impl PixelLayer {
+    /// Composite an RGB layer colour onto a normalized (0.0 to 1.0) base colour
+    pub fn composite(&self, base: (f64, f64, f64), layer: (u8, u8, u8)) -> (f64, f64, f64) {
+        let opacity = self.opacity.clamp(0.0, 1.0);
+        let channel = |base: f64, value: u8| {
+            let blended = self.blend_mode.blend(base, value as f64 / 255.0);
+            (base + (blended - base) * opacity).clamp(0.0, 1.0)
+        };
+        (
+            channel(base.0, layer.0),
+            channel(base.1, layer.1),
+            channel(base.2, layer.2),
+        )
+    }
+}
+
 /// Pixel effect parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PixelEffectParams {
@@ -49,6 +146,9 @@ pub struct PixelEffectParams {
     pub interval_ratio: f64,
     pub phase: f64,
     pub speed: f64,
+    /// Musical cycle length; when set this replaces interval, interval_ratio and speed
+    #[serde(default)]
+    pub subdivision: Option<Subdivision>,
 }
 
 impl Default for PixelEffectParams {
@@ -58,6 +158,7 @@ impl Default for PixelEffectParams {
             interval_ratio: 1.0,
             phase: 0.0,
             speed: 1.0,
+            subdivision: None,
         }
     }
 }
@@ -69,6 +170,11 @@ pub struct PixelEffect {
     pub scope: PixelEffectScope,
     pub color: (u8, u8, u8),
     pub params: PixelEffectParams,
+    /// Message for Text effects
+    #[serde(default)]
+    pub text: Option<PixelText>,
+    #[serde(default)]
+    pub layer: PixelLayer,
 }
 
 impl Default for PixelEffect {
@@ -78,6 +184,8 @@ impl Default for PixelEffect {
             scope: PixelEffectScope::Individual,
             color: (255, 255, 255),
             params: PixelEffectParams::default(),
+            text: None,
+            layer: PixelLayer::default(),
         }
     }
 }
@@ -161,7 +269,7 @@ impl PixelEffect {
                     0.0
                 }
             }
-            PixelEffectType::ColorCycle => {
+            PixelEffectType::ColorCycle | PixelEffectType::Text => {
                 // Always on for color cycle (color changes in bar mode)
                 1.0
             }
@@ -193,15 +301,59 @@ impl PixelEffect {
                     0.0
                 }
             }
-            PixelEffectType::ColorCycle => {
+            PixelEffectType::ColorCycle | PixelEffectType::Text => {
                 // Always full intensity for color cycle (color changes, not intensity)
                 1.0
             }
         }
     }
 
+    /// Render a text effect pixel
+    /// Fixtures in the effect are stacked as rows of the matrix (in effect order) and each
+    /// pixel is one column. One phase cycle scrolls the whole message across the matrix.
+    pub fn render_text_pixel(
+        &self,
+        bitmap: &TextBitmap,
+        pixel_idx: usize,
+        pixel_count: usize,
+        fixture_idx: usize,
+        total_fixtures: usize,
+        phase: f64,
+    ) -> (u8, u8, u8) {
+        let direction = self
+            .text
+            .as_ref()
+            .map(|text| text.direction)
+            .unwrap_or_default();
+
+        // Text starts fully off the matrix and ends fully off the other side
+        let travel = (bitmap.width() + pixel_count) as f64;
+        let offset = match direction {
+            ScrollDirection::Left => phase * travel,
+            ScrollDirection::Right => (1.0 - phase) * travel,
+        } as isize;
+        let column = pixel_idx as isize + offset - pixel_count as isize;
+
+        // Map this fixture's share of the matrix height onto glyph rows
+        let rows = total_fixtures.max(1);
+        let row_start = fixture_idx * GLYPH_HEIGHT / rows;
+        let row_end = ((fixture_idx + 1) * GLYPH_HEIGHT).div_ceil(rows);
+
+        if bitmap.is_lit(column, row_start, row_end) {
+            self.color
+        } else {
+            (0, 0, 0)
+        }
+    }
+
     /// Get effect phase from rhythm state
     pub fn get_phase(&self, rhythm: &RhythmState) -> f64 {
+        // A subdivision is already the cycle length, so speed doesn't apply on top of it
+        if let Some(subdivision) = &self.params.subdivision {
+            let phase = (subdivision.phase(rhythm) + self.params.phase) % 1.0;
+            return phase.clamp(0.0, 0.9999999);
+        }
+
         let base_phase = match self.params.interval {
             Interval::Beat => rhythm.beat_phase,
             Interval::Bar => rhythm.bar_phase,
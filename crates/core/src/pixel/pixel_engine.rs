@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use halo_fixtures::{Fixture, FixtureType};
 
-use super::pixel_effects::PixelEffect;
+use super::pixel_effects::{PixelEffect, PixelEffectType};
+use super::pixel_font::TextBitmap;
 use crate::rhythm::rhythm::RhythmState;
 use crate::EffectDistribution;
 
@@ -18,6 +19,8 @@ pub struct PixelEngine {
     sequential_packing: bool,
     /// Fixture mapping: fixture_id -> (universe, start_address, channels_needed)
     fixture_mapping: HashMap<usize, (u8, u16, usize)>,
+    /// Global FX size master applied to every layer's output (1.0 = 100%)
+    fx_size: f64,
 }
 
 impl PixelEngine {
@@ -28,6 +31,7 @@ impl PixelEngine {
             active_effects: HashMap::new(),
             sequential_packing: false,
             fixture_mapping: HashMap::new(),
+            fx_size: 1.0,
         }
     }
 
@@ -176,6 +180,11 @@ impl PixelEngine {
         self.active_effects.clear();
     }
 
+    /// Set the FX size master, scaling the brightness of every pixel effect
+    pub fn set_fx_size(&mut self, size: f64) {
+        self.fx_size = size.max(0.0);
+    }
+
     /// Render all pixel fixtures and return DMX data per universe
     pub fn render(&self, fixtures: &[Fixture], rhythm_state: &RhythmState) -> HashMap<u8, Vec<u8>> {
         if !self.enabled {
@@ -202,7 +211,7 @@ impl PixelEngine {
             }
 
             // Calculate RGB values for each pixel
-            let pixel_data = self.render_fixture(fixture, pixel_count, rhythm_state);
+            let pixel_data = self.render_fixture(fixture, fixtures, pixel_count, rhythm_state);
             let channels_needed = pixel_count * 3; // RGB per pixel
 
             // Determine universe and start address (use sequential mapping if enabled)
@@ -253,41 +262,90 @@ impl PixelEngine {
     fn render_fixture(
         &self,
         fixture: &Fixture,
+        fixtures: &[Fixture],
         pixel_count: usize,
         rhythm_state: &RhythmState,
     ) -> Vec<u8> {
         let mut pixel_data = vec![0u8; pixel_count * 3]; // RGB per pixel
 
-        // Find effects that apply to this fixture
-        let applicable_effects: Vec<(&PixelEffect, &EffectDistribution, usize, usize)> = self
+        // Find effects that apply to this fixture, ordered bottom layer first
+        // Names break ties so compositing order is stable between frames
+        let mut applicable_effects: Vec<(
+            &String,
+            &PixelEffect,
+            &EffectDistribution,
+            usize,
+            usize,
+        )> = self
             .active_effects
-            .values()
-            .filter_map(|(fixture_ids, effect, distribution)| {
+            .iter()
+            .filter_map(|(name, (fixture_ids, effect, distribution))| {
                 fixture_ids
                     .iter()
                     .position(|&id| id == fixture.id)
-                    .map(|idx| (effect, distribution, idx, fixture_ids.len()))
+                    .map(|idx| (name, effect, distribution, idx, fixture_ids.len()))
             })
             .collect();
+        applicable_effects.sort_by(|a, b| {
+            a.1.layer
+                .order
+                .cmp(&b.1.layer.order)
+                .then_with(|| a.0.cmp(b.0))
+        });
 
         if applicable_effects.is_empty() {
             // No effects, return black (all zeros)
             return pixel_data;
         }
 
+        // Phase offsets for position-based distributions
+        let spatial_offsets: Vec<f64> = applicable_effects
+            .iter()
+            .map(|(name, _, distribution, fixture_idx, _)| {
+                let positions: Vec<_> = self.active_effects[*name]
+                    .0
+                    .iter()
+                    .map(|id| {
+                        fixtures
+                            .iter()
+                            .find(|f| f.id == *id)
+                            .and_then(|f| f.position)
+                    })
+                    .collect();
+                distribution
+                    .spatial_offsets(&positions)
+                    .map_or(0.0, |offsets| offsets[*fixture_idx])
+            })
+            .collect();
+
+        // Render text once per fixture rather than per pixel
+        let text_bitmaps: Vec<Option<TextBitmap>> = applicable_effects
+            .iter()
+            .map(|(_, effect, ..)| {
+                if effect.effect_type == PixelEffectType::Text {
+                    let content = effect.text.as_ref().map_or("", |text| &text.content);
+                    Some(TextBitmap::render(content))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
         // Render each pixel
         for pixel_idx in 0..pixel_count {
             let position = (pixel_idx as f64 + 0.5) / pixel_count as f64;
-            let mut r = 0u16;
-            let mut g = 0u16;
-            let mut b = 0u16;
+            let mut composite = (0.0, 0.0, 0.0);
 
-            // Accumulate all applicable effects
-            for (effect, distribution, fixture_idx, _total_fixtures) in &applicable_effects {
+            // Composite all applicable effects as layers
+            for (effect_idx, (_, effect, distribution, fixture_idx, total_fixtures)) in
+                applicable_effects.iter().enumerate()
+            {
                 let base_phase = effect.get_phase(rhythm_state);
 
-                // Apply distribution to offset phase across fixtures
+                // Apply distribution to offset phase across fixtures. Text already lays
+                // itself out across the rows, so every row scrolls together.
                 let phase = match distribution {
+                    _ if effect.effect_type == PixelEffectType::Text => base_phase,
                     EffectDistribution::All => base_phase,
                     EffectDistribution::Step(step) => {
                         let step_offset = (fixture_idx % step) as f64 / (*step).max(1) as f64;
@@ -297,24 +355,44 @@ impl PixelEngine {
                         let wave_offset = *fixture_idx as f64 * offset;
                         (base_phase + wave_offset) % 1.0
                     }
+                    EffectDistribution::Linear { .. } | EffectDistribution::Radial { .. } => {
+                        (base_phase + spatial_offsets[effect_idx]).rem_euclid(1.0)
+                    }
                 };
 
-                let (pr, pg, pb) = effect.render_pixel(position, phase);
-                r += pr as u16;
-                g += pg as u16;
-                b += pb as u16;
+                let layer_color = if let Some(bitmap) = &text_bitmaps[effect_idx] {
+                    effect.render_text_pixel(
+                        bitmap,
+                        pixel_idx,
+                        pixel_count,
+                        *fixture_idx,
+                        *total_fixtures,
+                        phase,
+                    )
+                } else {
+                    effect.render_pixel(position, phase)
+                };
+
+                composite = effect
+                    .layer
+                    .composite(composite, Self::scale_color(layer_color, self.fx_size));
             }
 
-            // Clamp to 255
             let base = pixel_idx * 3;
-            pixel_data[base] = r.min(255) as u8;
-            pixel_data[base + 1] = g.min(255) as u8;
-            pixel_data[base + 2] = b.min(255) as u8;
+            pixel_data[base] = (composite.0 * 255.0).round() as u8;
+            pixel_data[base + 1] = (composite.1 * 255.0).round() as u8;
+            pixel_data[base + 2] = (composite.2 * 255.0).round() as u8;
         }
 
         pixel_data
     }
 
+    /// Scale an RGB colour, clamping each channel to full
+    fn scale_color(color: (u8, u8, u8), scale: f64) -> (u8, u8, u8) {
+        let channel = |value: u8| (value as f64 * scale).clamp(0.0, 255.0) as u8;
+        (channel(color.0), channel(color.1), channel(color.2))
+    }
+
     /// Write pixel data with automatic spillover across universe boundaries
     /// Ensures splits happen only on pixel boundaries (multiples of 3 channels)
     fn write_with_spillover(
@@ -0,0 +1,2 @@
+pub mod preset;
+pub mod preset_library;
@@ -1,7 +1,7 @@
 use halo_fixtures::ChannelType;
 use serde::{Deserialize, Serialize};
 
-use crate::{Effect, PixelEffect};
+use crate::{Effect, EffectDistribution, FixtureGroup, PixelEffect, StaticValue};
 
 /// Represents different types of presets
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -13,6 +13,50 @@ pub enum PresetType {
     Effect,
 }
 
+impl PresetType {
+    /// Preset families that hold static values and can be stored from the programmer
+    pub fn value_types() -> [PresetType; 4] {
+        [
+            PresetType::Intensity,
+            PresetType::Color,
+            PresetType::Position,
+            PresetType::Beam,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PresetType::Color => "Color",
+            PresetType::Position => "Position",
+            PresetType::Intensity => "Intensity",
+            PresetType::Beam => "Beam",
+            PresetType::Effect => "Effect",
+        }
+    }
+
+    /// The preset family a channel belongs to
+    pub fn for_channel(channel_type: &ChannelType) -> PresetType {
+        match channel_type {
+            ChannelType::Dimmer => PresetType::Intensity,
+            ChannelType::Red
+            | ChannelType::Green
+            | ChannelType::Blue
+            | ChannelType::White
+            | ChannelType::Amber
+            | ChannelType::UV
+            | ChannelType::Cyan
+            | ChannelType::Magenta
+            | ChannelType::Yellow
+            | ChannelType::Color
+            | ChannelType::PixelRed(_)
+            | ChannelType::PixelGreen(_)
+            | ChannelType::PixelBlue(_) => PresetType::Color,
+            ChannelType::Pan | ChannelType::Tilt => PresetType::Position,
+            _ => PresetType::Beam,
+        }
+    }
+}
+
 /// A generic preset that can be one of several types
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -55,6 +99,78 @@ impl Preset {
         }
     }
 
+    /// Build a value preset from programmer values, keeping only channels in its family
+    /// Returns None for effect presets or when no values belong to the family
+    ///
+    /// Each fixture's values are kept so fans and mirrored positions survive, and the lowest
+    /// numbered fixture's values become the shared values for other fixtures in its groups.
+    pub fn from_values(
+        preset_type: &PresetType,
+        id: usize,
+        name: String,
+        values: &[StaticValue],
+        fixture_groups: &[FixtureGroup],
+    ) -> Option<Preset> {
+        let fixture_values: Vec<StaticValue> = values
+            .iter()
+            .filter(|v| PresetType::for_channel(&v.channel_type) == *preset_type)
+            .cloned()
+            .collect();
+        let first_fixture = fixture_values.iter().map(|v| v.fixture_id).min()?;
+        let shared: Vec<&StaticValue> = fixture_values
+            .iter()
+            .filter(|v| v.fixture_id == first_fixture)
+            .collect();
+        let groups: Vec<usize> = fixture_groups
+            .iter()
+            .filter(|group| {
+                group
+                    .fixture_ids
+                    .iter()
+                    .any(|id| fixture_values.iter().any(|v| v.fixture_id == *id))
+            })
+            .map(|group| group.id)
+            .collect();
+
+        match preset_type {
+            PresetType::Color => {
+                let mut preset = ColorPreset::new(id, name, groups);
+                for value in shared {
+                    preset.add_value(value.channel_type.clone(), value.value);
+                }
+                preset.fixture_values = fixture_values;
+                Some(Preset::Color(preset))
+            }
+            PresetType::Position => {
+                let mut preset = PositionPreset::new(id, name, groups);
+                for value in shared {
+                    match value.channel_type {
+                        ChannelType::Pan => preset.pan = Some(value.value),
+                        ChannelType::Tilt => preset.tilt = Some(value.value),
+                        _ => {}
+                    }
+                }
+                preset.fixture_values = fixture_values;
+                Some(Preset::Position(preset))
+            }
+            PresetType::Intensity => {
+                let dimmer = shared.first().map(|v| v.value).unwrap_or(0);
+                let mut preset = IntensityPreset::new(id, name, groups, dimmer);
+                preset.fixture_values = fixture_values;
+                Some(Preset::Intensity(preset))
+            }
+            PresetType::Beam => {
+                let mut preset = BeamPreset::new(id, name, groups);
+                for value in shared {
+                    preset.add_value(value.channel_type.clone(), value.value);
+                }
+                preset.fixture_values = fixture_values;
+                Some(Preset::Beam(preset))
+            }
+            PresetType::Effect => None,
+        }
+    }
+
     pub fn fixture_groups(&self) -> &[usize] {
         match self {
             Preset::Color(p) => &p.fixture_groups,
@@ -64,6 +180,17 @@ impl Preset {
             Preset::Effect(p) => &p.fixture_groups,
         }
     }
+
+    /// Values stored for individual fixtures, which replace the shared values on those fixtures
+    pub fn fixture_values(&self) -> &[StaticValue] {
+        match self {
+            Preset::Color(p) => &p.fixture_values,
+            Preset::Position(p) => &p.fixture_values,
+            Preset::Intensity(p) => &p.fixture_values,
+            Preset::Beam(p) => &p.fixture_values,
+            Preset::Effect(_) => &[],
+        }
+    }
 }
 
 /// A preset for color values (RGB, RGBW, color wheels, etc.)
@@ -73,6 +200,9 @@ pub struct ColorPreset {
     pub name: String,
     pub fixture_groups: Vec<usize>,
     pub values: Vec<ColorValue>,
+    /// Per-fixture values when stored from the programmer
+    #[serde(default)]
+    pub fixture_values: Vec<StaticValue>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -88,6 +218,7 @@ impl ColorPreset {
             name,
             fixture_groups,
             values: Vec::new(),
+            fixture_values: Vec::new(),
         }
     }
 
@@ -109,6 +240,9 @@ pub struct PositionPreset {
     pub fixture_groups: Vec<usize>,
     pub pan: Option<u8>,
     pub tilt: Option<u8>,
+    /// Per-fixture values when stored from the programmer
+    #[serde(default)]
+    pub fixture_values: Vec<StaticValue>,
 }
 
 impl PositionPreset {
@@ -119,6 +253,7 @@ impl PositionPreset {
             fixture_groups,
             pan: None,
             tilt: None,
+            fixture_values: Vec::new(),
         }
     }
 
@@ -140,6 +275,9 @@ pub struct IntensityPreset {
     pub name: String,
     pub fixture_groups: Vec<usize>,
     pub dimmer: u8,
+    /// Per-fixture values when stored from the programmer
+    #[serde(default)]
+    pub fixture_values: Vec<StaticValue>,
 }
 
 impl IntensityPreset {
@@ -149,6 +287,7 @@ impl IntensityPreset {
             name,
             fixture_groups,
             dimmer,
+            fixture_values: Vec::new(),
         }
     }
 }
@@ -160,6 +299,9 @@ pub struct BeamPreset {
     pub name: String,
     pub fixture_groups: Vec<usize>,
     pub values: Vec<BeamValue>,
+    /// Per-fixture values when stored from the programmer
+    #[serde(default)]
+    pub fixture_values: Vec<StaticValue>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -175,6 +317,7 @@ impl BeamPreset {
             name,
             fixture_groups,
             values: Vec::new(),
+            fixture_values: Vec::new(),
         }
     }
 
@@ -195,6 +338,11 @@ pub struct EffectPreset {
     pub name: String,
     pub fixture_groups: Vec<usize>,
     pub effect: EffectPresetType,
+    #[serde(default)]
+    pub distribution: EffectDistribution,
+    /// Channels the effect drives when applied; empty means Dimmer
+    #[serde(default)]
+    pub channel_types: Vec<ChannelType>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -215,6 +363,8 @@ impl EffectPreset {
             name,
             fixture_groups,
             effect: EffectPresetType::Standard(effect),
+            distribution: EffectDistribution::All,
+            channel_types: Vec::new(),
         }
     }
 
@@ -229,6 +379,17 @@ impl EffectPreset {
             name,
             fixture_groups,
             effect: EffectPresetType::Pixel(effect),
+            distribution: EffectDistribution::All,
+            channel_types: Vec::new(),
+        }
+    }
+
+    /// The channels this preset drives, falling back to Dimmer
+    pub fn target_channels(&self) -> Vec<ChannelType> {
+        if self.channel_types.is_empty() {
+            vec![ChannelType::Dimmer]
+        } else {
+            self.channel_types.clone()
         }
     }
 }
@@ -35,6 +35,13 @@ impl PresetLibrary {
         }
     }
 
+    /// Store a preset, replacing any existing preset of the same type and ID
+    pub fn store_preset(&mut self, preset: Preset) {
+        if !self.update_preset(preset.clone()) {
+            self.add_preset(preset);
+        }
+    }
+
     /// Get a preset by ID and type
     pub fn get_preset(&self, preset_type: &PresetType, id: usize) -> Option<Preset> {
         match preset_type {
@@ -1,6 +1,10 @@
-use halo_fixtures::ChannelType;
+use std::time::{Duration, Instant};
 
-use crate::{EffectMapping, StaticValue};
+use halo_fixtures::{ChannelType, Fixture};
+
+use crate::{
+    EffectDirection, EffectMapping, FixtureGroup, GroupSelectMode, PresetType, StaticValue,
+};
 
 #[derive(Clone)]
 pub struct Programmer {
@@ -9,8 +13,46 @@ pub struct Programmer {
     preview_mode: bool,
     collapsed: bool,
     selected_fixtures: Vec<usize>,
+    highlight: bool,
+    lowlight: bool,
+    // How many staged clears have happened since the programmer last changed
+    clear_stage: usize,
+    // Programmer state before each edit, newest last
+    undo_stack: Vec<ProgrammerSnapshot>,
+    // Undone states, cleared by the next edit
+    redo_stack: Vec<ProgrammerSnapshot>,
+    last_checkpoint: Option<(&'static str, Instant)>,
+    // Relative encoder scaling per channel, and the fractional steps not yet applied
+    encoder_sensitivity: Vec<(ChannelType, f32)>,
+    encoder_remainders: Vec<(ChannelType, f32)>,
+    // Next/previous stepping: the full selection, the isolated index and the highlight state
+    // to restore when stepping ends
+    step: Option<SelectionStep>,
+}
+
+#[derive(Clone)]
+struct SelectionStep {
+    selection: Vec<usize>,
+    index: usize,
+    highlight: bool,
+}
+
+/// The parts of the programmer that undo restores
+#[derive(Clone)]
+struct ProgrammerSnapshot {
+    action: &'static str,
+    values: Vec<StaticValue>,
+    effects: Vec<EffectMapping>,
+    selected_fixtures: Vec<usize>,
 }
 
+const UNDO_LIMIT: usize = 100;
+/// Repeated edits of the same kind within this window (e.g. an encoder turn) undo together
+const UNDO_COALESCE: Duration = Duration::from_millis(750);
+
+/// Intensity cap for unselected fixtures while lowlight is on
+const LOWLIGHT_LEVEL: u8 = 25;
+
 impl Programmer {
     pub fn new() -> Self {
         Self {
@@ -19,6 +61,15 @@ impl Programmer {
             preview_mode: false,
             collapsed: false,
             selected_fixtures: Vec::new(),
+            highlight: false,
+            lowlight: false,
+            clear_stage: 0,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            last_checkpoint: None,
+            encoder_sensitivity: Vec::new(),
+            encoder_remainders: Vec::new(),
+            step: None,
         }
     }
 
@@ -33,20 +84,161 @@ impl Programmer {
             channel_type,
             value,
         });
+        self.clear_stage = 0;
+    }
+
+    /// Knock a channel out of the programmer for the given fixtures, leaving their other values
+    /// Returns true if anything was removed
+    pub fn remove_values(&mut self, fixture_ids: &[usize], channel_type: &ChannelType) -> bool {
+        let len_before = self.values.len();
+        self.values
+            .retain(|v| !(fixture_ids.contains(&v.fixture_id) && v.channel_type == *channel_type));
+        self.values.len() < len_before
+    }
+
+    /// Whether the programmer has a value or live effect on the channel for any of the fixtures
+    pub fn targets(&self, fixture_ids: &[usize], channel_type: &ChannelType) -> bool {
+        self.values
+            .iter()
+            .any(|v| fixture_ids.contains(&v.fixture_id) && v.channel_type == *channel_type)
+            || self.effects.iter().any(|e| {
+                e.channel_types.contains(channel_type)
+                    && e.fixture_ids.iter().any(|id| fixture_ids.contains(id))
+            })
+    }
+
+    pub fn set_encoder_sensitivity(&mut self, channel_type: ChannelType, sensitivity: f32) {
+        self.encoder_sensitivity.retain(|(c, _)| *c != channel_type);
+        self.encoder_sensitivity.push((channel_type, sensitivity));
+    }
+
+    /// Sensitivity for relative adjustments on a channel, 1.0 unless set
+    pub fn get_encoder_sensitivity(&self, channel_type: &ChannelType) -> f32 {
+        self.encoder_sensitivity
+            .iter()
+            .find(|(c, _)| c == channel_type)
+            .map(|(_, s)| *s)
+            .unwrap_or(1.0)
+    }
+
+    /// Turn a relative encoder movement into whole steps for a channel
+    /// Fractions are carried over, so low sensitivities still move after enough ticks
+    pub fn encoder_steps(&mut self, channel_type: &ChannelType, delta: f32) -> i32 {
+        let scaled = delta * self.get_encoder_sensitivity(channel_type);
+        let remainder = match self
+            .encoder_remainders
+            .iter_mut()
+            .find(|(c, _)| c == channel_type)
+        {
+            Some((_, remainder)) => remainder,
+            None => {
+                self.encoder_remainders.push((channel_type.clone(), 0.0));
+                &mut self.encoder_remainders.last_mut().unwrap().1
+            }
+        };
+        let total = *remainder + scaled;
+        let steps = total.trunc();
+        *remainder = total - steps;
+        steps as i32
+    }
+
+    pub fn get_value(&self, fixture_id: usize, channel_type: &ChannelType) -> Option<u8> {
+        self.values
+            .iter()
+            .find(|v| v.fixture_id == fixture_id && v.channel_type == *channel_type)
+            .map(|v| v.value)
     }
 
     pub fn get_values(&self) -> &Vec<StaticValue> {
         &self.values
     }
 
+    /// Staged values that differ from what tracking would output on its own
+    pub fn changed_values(&self, tracked: &[StaticValue]) -> Vec<StaticValue> {
+        self.values
+            .iter()
+            .filter(|value| {
+                !tracked.iter().any(|t| {
+                    t.fixture_id == value.fixture_id
+                        && t.channel_type == value.channel_type
+                        && t.value == value.value
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn add_effect(&mut self, effect: EffectMapping) {
         self.effects.push(effect);
+        self.clear_stage = 0;
     }
 
     pub fn get_effects(&self) -> &Vec<EffectMapping> {
         &self.effects
     }
 
+    /// Change which way a live effect runs
+    /// Returns false if there's no effect at the index
+    pub fn set_effect_direction(&mut self, index: usize, direction: EffectDirection) -> bool {
+        match self.effects.get_mut(index) {
+            Some(effect) => {
+                effect.direction = direction;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop effects on a channel for the given fixtures
+    /// Other fixtures and channels driven by the same effects keep running
+    /// Returns true if any effect was changed
+    pub fn clear_effect_targets(
+        &mut self,
+        fixture_ids: &[usize],
+        channel_type: &ChannelType,
+    ) -> bool {
+        let mut changed = false;
+        let mut remaining = Vec::with_capacity(self.effects.len());
+
+        for mut effect in self.effects.drain(..) {
+            let targeted = effect.channel_types.contains(channel_type)
+                && effect.fixture_ids.iter().any(|id| fixture_ids.contains(id));
+            if !targeted {
+                remaining.push(effect);
+                continue;
+            }
+            changed = true;
+
+            let (cleared, kept): (Vec<usize>, Vec<usize>) = effect
+                .fixture_ids
+                .iter()
+                .copied()
+                .partition(|id| fixture_ids.contains(id));
+
+            // Cleared fixtures keep running on the effect's other channels
+            let other_channels: Vec<ChannelType> = effect
+                .channel_types
+                .iter()
+                .filter(|c| *c != channel_type)
+                .cloned()
+                .collect();
+            if !other_channels.is_empty() {
+                let mut split = effect.clone();
+                split.fixture_ids = cleared;
+                split.channel_types = other_channels;
+                remaining.push(split);
+            }
+
+            if !kept.is_empty() {
+                effect.fixture_ids = kept;
+                remaining.push(effect);
+            }
+        }
+
+        self.effects = remaining;
+        changed
+    }
+
     pub fn set_preview_mode(&mut self, preview_mode: bool) {
         self.preview_mode = preview_mode;
     }
@@ -55,9 +247,155 @@ impl Programmer {
         self.preview_mode
     }
 
+    /// Record the current state before an edit so it can be undone
+    pub fn checkpoint(&mut self, action: &'static str) {
+        self.redo_stack.clear();
+        let now = Instant::now();
+        if let Some((last_action, at)) = self.last_checkpoint {
+            if last_action == action && now.duration_since(at) < UNDO_COALESCE {
+                self.last_checkpoint = Some((action, now));
+                return;
+            }
+        }
+        self.last_checkpoint = Some((action, now));
+
+        self.undo_stack.push(self.snapshot(action));
+        if self.undo_stack.len() > UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Restore the state before the last edit
+    /// Returns the undone action, or None if there's nothing to undo
+    pub fn undo(&mut self) -> Option<&'static str> {
+        let snapshot = self.undo_stack.pop()?;
+        self.redo_stack.push(self.snapshot(snapshot.action));
+        Some(self.restore(snapshot))
+    }
+
+    /// Reapply the last undone edit
+    /// Returns the redone action, or None if there's nothing to redo
+    pub fn redo(&mut self) -> Option<&'static str> {
+        let snapshot = self.redo_stack.pop()?;
+        self.undo_stack.push(self.snapshot(snapshot.action));
+        Some(self.restore(snapshot))
+    }
+
+    /// The action the next undo would revert
+    pub fn undo_action(&self) -> Option<&'static str> {
+        self.undo_stack.last().map(|s| s.action)
+    }
+
+    /// The action the next redo would reapply
+    pub fn redo_action(&self) -> Option<&'static str> {
+        self.redo_stack.last().map(|s| s.action)
+    }
+
+    fn snapshot(&self, action: &'static str) -> ProgrammerSnapshot {
+        ProgrammerSnapshot {
+            action,
+            values: self.values.clone(),
+            effects: self.effects.clone(),
+            selected_fixtures: self.selected_fixtures.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: ProgrammerSnapshot) -> &'static str {
+        self.restore_selection();
+        self.values = snapshot.values;
+        self.effects = snapshot.effects;
+        self.selected_fixtures = snapshot.selected_fixtures;
+        self.last_checkpoint = None;
+        snapshot.action
+    }
+
+    pub fn set_highlight(&mut self, highlight: bool, lowlight: bool) {
+        self.highlight = highlight;
+        self.lowlight = lowlight;
+    }
+
+    pub fn get_highlight(&self) -> (bool, bool) {
+        (self.highlight, self.lowlight)
+    }
+
+    /// DMX output for a fixture while highlight is active, or None to output its own values
+    ///
+    /// Selected fixtures go to full open white with the shutter open (the Strobe channel, where 0
+    /// is open with no strobe); with lowlight on, the rest are dimmed. Only the
+    /// output is changed, so programmed and played back values come back when highlight is off.
+    pub fn highlight_values(&self, fixture: &Fixture) -> Option<Vec<u8>> {
+        if !self.highlight {
+            return None;
+        }
+
+        let selected = self.selected_fixtures.contains(&fixture.id);
+        if !selected && !self.lowlight {
+            return None;
+        }
+
+        let has_dimmer = fixture
+            .channels
+            .iter()
+            .any(|c| c.channel_type == ChannelType::Dimmer);
+        let values = fixture
+            .channels
+            .iter()
+            .map(|channel| match (&channel.channel_type, selected) {
+                (ChannelType::Dimmer, true) => 255,
+                (ChannelType::Red | ChannelType::Green | ChannelType::Blue, true) => 255,
+                (ChannelType::White, true) => 255,
+                (ChannelType::Amber | ChannelType::UV, true) => 0,
+                (ChannelType::Cyan | ChannelType::Magenta | ChannelType::Yellow, true) => 0,
+                (ChannelType::Color | ChannelType::Gobo, true) => 0,
+                (ChannelType::Strobe, true) => 0,
+                (ChannelType::Dimmer, false) => channel.value.min(LOWLIGHT_LEVEL),
+                (
+                    ChannelType::Red
+                    | ChannelType::Green
+                    | ChannelType::Blue
+                    | ChannelType::White
+                    | ChannelType::Amber
+                    | ChannelType::UV,
+                    false,
+                ) if !has_dimmer => channel.value.min(LOWLIGHT_LEVEL),
+                _ => channel.value,
+            })
+            .collect();
+        Some(values)
+    }
+
     pub fn clear(&mut self) {
         self.values.clear();
         self.effects.clear();
+        self.clear_stage = 0;
+    }
+
+    /// Clear values and effects in one parameter family, leaving the rest of the look in place
+    pub fn clear_family(&mut self, family: &PresetType) {
+        self.values
+            .retain(|v| PresetType::for_channel(&v.channel_type) != *family);
+        self.effects.retain_mut(|effect| {
+            effect
+                .channel_types
+                .retain(|c| PresetType::for_channel(c) != *family);
+            !effect.channel_types.is_empty()
+        });
+    }
+
+    /// Clear in stages: intensity first, then color, then everything
+    /// Returns the family that was cleared, or None when the whole programmer was cleared
+    pub fn staged_clear(&mut self) -> Option<PresetType> {
+        let family = match self.clear_stage {
+            0 => PresetType::Intensity,
+            1 => PresetType::Color,
+            _ => {
+                self.clear();
+                return None;
+            }
+        };
+        self.clear_family(&family);
+        self.clear_stage += 1;
+        Some(family)
     }
 
     pub fn set_collapsed(&mut self, collapsed: bool) {
@@ -69,24 +407,182 @@ impl Programmer {
     }
 
     pub fn set_selected_fixtures(&mut self, fixtures: Vec<usize>) {
+        self.restore_selection();
         self.selected_fixtures = fixtures;
     }
 
+    /// Isolate the next (or previous) fixture of the selection, highlighting it
+    /// Returns false if there's nothing selected to step through
+    pub fn step_selection(&mut self, forward: bool) -> bool {
+        let step = match self.step.take() {
+            Some(mut step) => {
+                let len = step.selection.len();
+                step.index = if forward {
+                    (step.index + 1) % len
+                } else {
+                    (step.index + len - 1) % len
+                };
+                step
+            }
+            None => {
+                if self.selected_fixtures.is_empty() {
+                    return false;
+                }
+                let index = if forward {
+                    0
+                } else {
+                    self.selected_fixtures.len() - 1
+                };
+                SelectionStep {
+                    selection: self.selected_fixtures.clone(),
+                    index,
+                    highlight: self.highlight,
+                }
+            }
+        };
+
+        self.selected_fixtures = vec![step.selection[step.index]];
+        self.highlight = true;
+        self.step = Some(step);
+        true
+    }
+
+    /// Stop stepping and bring back the full selection and previous highlight state
+    ///
+    /// Every selection change goes through this first, so edits apply to the full selection
+    /// and stepping never leaves highlight forced on.
+    pub fn restore_selection(&mut self) {
+        if let Some(step) = self.step.take() {
+            self.selected_fixtures = step.selection;
+            self.highlight = step.highlight;
+        }
+    }
+
+    /// Position of the isolated fixture and the size of the full selection while stepping
+    pub fn get_selection_step(&self) -> Option<(usize, usize)> {
+        self.step
+            .as_ref()
+            .map(|step| (step.index, step.selection.len()))
+    }
+
     pub fn add_selected_fixture(&mut self, fixture_id: usize) {
+        self.restore_selection();
         if !self.selected_fixtures.contains(&fixture_id) {
             self.selected_fixtures.push(fixture_id);
         }
     }
 
     pub fn remove_selected_fixture(&mut self, fixture_id: usize) {
+        self.restore_selection();
         self.selected_fixtures.retain(|&id| id != fixture_id);
     }
 
+    /// Select a group's fixtures, replacing, extending or reducing the selection
+    pub fn select_group(&mut self, group: &FixtureGroup, mode: GroupSelectMode) {
+        self.restore_selection();
+        match mode {
+            GroupSelectMode::Replace => self.selected_fixtures = group.fixture_ids.clone(),
+            GroupSelectMode::Add => {
+                for &fixture_id in &group.fixture_ids {
+                    self.add_selected_fixture(fixture_id);
+                }
+            }
+            GroupSelectMode::Remove => self
+                .selected_fixtures
+                .retain(|id| !group.fixture_ids.contains(id)),
+        }
+    }
+
     pub fn clear_selected_fixtures(&mut self) {
+        self.restore_selection();
         self.selected_fixtures.clear();
     }
 
+    /// Flip the selection order, which reverses fans and effect distributions
+    pub fn reverse_selection(&mut self) {
+        self.restore_selection();
+        self.selected_fixtures.reverse();
+    }
+
+    /// Spread a channel evenly from `start` to `end` across fixtures in the order given
+    pub fn fan_value(
+        &mut self,
+        fixture_ids: &[usize],
+        channel_type: &ChannelType,
+        start: u8,
+        end: u8,
+    ) {
+        let last = fixture_ids.len().saturating_sub(1).max(1) as f32;
+        for (index, fixture_id) in fixture_ids.iter().enumerate() {
+            let t = index as f32 / last;
+            let value = start as f32 + (end as f32 - start as f32) * t;
+            self.add_value(*fixture_id, channel_type.clone(), value.round() as u8);
+        }
+    }
+
+    /// Selected fixtures in the order they were selected
     pub fn get_selected_fixtures(&self) -> &Vec<usize> {
         &self.selected_fixtures
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_opens_shutter() {
+        use halo_fixtures::Channel;
+
+        let channel = |channel_type: ChannelType, value: u8| Channel {
+            name: channel_type.to_string(),
+            channel_type,
+            value,
+        };
+        let fixture = Fixture {
+            id: 1,
+            name: "Spot".to_string(),
+            profile_id: String::new(),
+            profile: Default::default(),
+            channels: vec![
+                channel(ChannelType::Dimmer, 0),
+                channel(ChannelType::Strobe, 180),
+                channel(ChannelType::Pan, 90),
+            ],
+            universe: 1,
+            start_address: 1,
+            pan_tilt_limits: None,
+            position: None,
+        };
+
+        let mut programmer = Programmer::new();
+        programmer.set_selected_fixtures(vec![1]);
+        assert_eq!(programmer.highlight_values(&fixture), None);
+
+        programmer.set_highlight(true, false);
+        assert_eq!(
+            programmer.highlight_values(&fixture),
+            Some(vec![255, 0, 90])
+        );
+    }
+
+    #[test]
+    fn test_selection_change_ends_step_and_restores_highlight() {
+        let mut programmer = Programmer::new();
+        programmer.set_selected_fixtures(vec![1, 2, 3]);
+
+        assert!(programmer.step_selection(true));
+        assert_eq!(programmer.get_selected_fixtures(), &vec![1]);
+        assert!(programmer.get_highlight().0);
+
+        programmer.add_selected_fixture(4);
+        assert_eq!(programmer.get_selection_step(), None);
+        assert!(!programmer.get_highlight().0);
+        assert_eq!(programmer.get_selected_fixtures(), &vec![1, 2, 3, 4]);
+
+        programmer.step_selection(false);
+        programmer.reverse_selection();
+        assert!(!programmer.get_highlight().0);
+        assert_eq!(programmer.get_selected_fixtures(), &vec![4, 3, 2, 1]);
+    }
+}
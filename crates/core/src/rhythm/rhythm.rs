@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 // Assuming we have access to these from our rhythm engine
 #[derive(Debug, Clone)]
 pub struct RhythmState {
-    pub beat_phase: f64,   // 0.0 to 1.0, resets each beat
-    pub bar_phase: f64,    // 0.0 to 1.0, resets each bar
+    pub beat_time: f64, // Absolute beat position, used for subdivisions longer than a beat
+    pub beat_phase: f64, // 0.0 to 1.0, resets each beat
+    pub bar_phase: f64, // 0.0 to 1.0, resets each bar
     pub phrase_phase: f64, // 0.0 to 1.0, resets each phrase
     pub beats_per_bar: u32,
     pub bars_per_phrase: u32,
@@ -14,9 +15,156 @@ pub struct RhythmState {
     pub tap_count: u32,
 }
 
+impl RhythmState {
+    /// Set the beat, bar and phrase phases from an absolute beat position
+    pub fn set_beat_time(&mut self, beat_time: f64) {
+        self.beat_time = beat_time;
+        self.beat_phase = beat_time.fract();
+        self.bar_phase = (beat_time / self.beats_per_bar as f64).fract();
+        self.phrase_phase =
+            (beat_time / (self.beats_per_bar * self.bars_per_phrase) as f64).fract();
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Interval {
     Beat,
     Bar,
     Phrase,
 }
+
+/// Base unit of a subdivision
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubdivisionUnit {
+    Beat,
+    Bar,
+}
+
+/// Dotted and triplet variants of a subdivision
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubdivisionModifier {
+    #[default]
+    Straight,
+    /// One and a half times the straight length
+    Dotted,
+    /// Two thirds of the straight length (three in the space of two)
+    Triplet,
+}
+
+impl SubdivisionModifier {
+    pub fn all() -> Vec<SubdivisionModifier> {
+        vec![
+            SubdivisionModifier::Straight,
+            SubdivisionModifier::Dotted,
+            SubdivisionModifier::Triplet,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubdivisionModifier::Straight => "Straight",
+            SubdivisionModifier::Dotted => "Dotted",
+            SubdivisionModifier::Triplet => "Triplet",
+        }
+    }
+
+    fn factor(&self) -> f64 {
+        match self {
+            SubdivisionModifier::Straight => 1.0,
+            SubdivisionModifier::Dotted => 1.5,
+            SubdivisionModifier::Triplet => 2.0 / 3.0,
+        }
+    }
+}
+
+/// Musical length of one effect cycle, from 1/32 of a beat up to 32 bars
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Subdivision {
+    pub numerator: u32,
+    pub denominator: u32,
+    pub unit: SubdivisionUnit,
+    #[serde(default)]
+    pub modifier: SubdivisionModifier,
+}
+
+impl Subdivision {
+    pub const fn beats(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator,
+            unit: SubdivisionUnit::Beat,
+            modifier: SubdivisionModifier::Straight,
+        }
+    }
+
+    pub const fn bars(count: u32) -> Self {
+        Self {
+            numerator: count,
+            denominator: 1,
+            unit: SubdivisionUnit::Bar,
+            modifier: SubdivisionModifier::Straight,
+        }
+    }
+
+    pub fn with_modifier(mut self, modifier: SubdivisionModifier) -> Self {
+        self.modifier = modifier;
+        self
+    }
+
+    /// Straight subdivisions offered by the subdivision picker, shortest first
+    pub fn presets() -> Vec<Subdivision> {
+        vec![
+            Subdivision::beats(1, 32),
+            Subdivision::beats(1, 16),
+            Subdivision::beats(1, 8),
+            Subdivision::beats(1, 4),
+            Subdivision::beats(1, 2),
+            Subdivision::beats(1, 1),
+            Subdivision::beats(2, 1),
+            Subdivision::bars(1),
+            Subdivision::bars(2),
+            Subdivision::bars(4),
+            Subdivision::bars(8),
+            Subdivision::bars(16),
+            Subdivision::bars(32),
+        ]
+    }
+
+    /// Length of one cycle in beats
+    pub fn length_in_beats(&self, beats_per_bar: u32) -> f64 {
+        let unit_beats = match self.unit {
+            SubdivisionUnit::Beat => 1.0,
+            SubdivisionUnit::Bar => beats_per_bar.max(1) as f64,
+        };
+        unit_beats * self.numerator as f64 / self.denominator.max(1) as f64 * self.modifier.factor()
+    }
+
+    /// Phase (0.0 to 1.0) of this subdivision at the rhythm's current beat position
+    pub fn phase(&self, rhythm: &RhythmState) -> f64 {
+        let length = self.length_in_beats(rhythm.beats_per_bar);
+        if length <= 0.0 {
+            return 0.0;
+        }
+        (rhythm.beat_time / length).rem_euclid(1.0)
+    }
+
+    /// Short label such as "1/16 beat", "2 bars" or "1/8 beat triplet"
+    pub fn label(&self) -> String {
+        let length = if self.denominator > 1 {
+            format!("{}/{}", self.numerator, self.denominator)
+        } else {
+            self.numerator.to_string()
+        };
+        let unit = match (self.unit, self.numerator > 1 && self.denominator == 1) {
+            (SubdivisionUnit::Beat, false) => "beat",
+            (SubdivisionUnit::Beat, true) => "beats",
+            (SubdivisionUnit::Bar, false) => "bar",
+            (SubdivisionUnit::Bar, true) => "bars",
+        };
+        match self.modifier {
+            SubdivisionModifier::Straight => format!("{length} {unit}"),
+            SubdivisionModifier::Dotted => format!("{length} {unit} dotted"),
+            SubdivisionModifier::Triplet => format!("{length} {unit} triplet"),
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::time::SystemTime;
 use halo_fixtures::Fixture;
 use serde::{Deserialize, Serialize};
 
-use crate::CueList;
+use crate::{CueList, FixtureGridLayout, FixtureGroup, MagicSheet, PresetLibrary, QuickSelect};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Show {
@@ -12,6 +12,18 @@ pub struct Show {
     pub modified_at: SystemTime,
     pub fixtures: Vec<Fixture>,
     pub cue_lists: Vec<CueList>,
+    #[serde(default)]
+    pub presets: PresetLibrary,
+    #[serde(default)]
+    pub fixture_groups: Vec<FixtureGroup>,
+    #[serde(default)]
+    pub quick_selects: Vec<QuickSelect>,
+    #[serde(default)]
+    pub color_swatches: Vec<ColorSwatch>,
+    #[serde(default)]
+    pub fixture_grid: FixtureGridLayout,
+    #[serde(default)]
+    pub magic_sheet: MagicSheet,
     pub version: String, // Schema version for future compatibility
 }
 
@@ -24,7 +36,20 @@ impl Show {
             modified_at: now,
             fixtures: Vec::new(),
             cue_lists: Vec::new(),
+            presets: PresetLibrary::default(),
+            fixture_groups: Vec::new(),
+            quick_selects: Vec::new(),
+            color_swatches: Vec::new(),
+            fixture_grid: FixtureGridLayout::default(),
+            magic_sheet: MagicSheet::default(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         }
     }
 }
+
+/// A named color saved with the show so it can be reused from any color picker
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ColorSwatch {
+    pub name: String,
+    pub color: [u8; 3],
+}
@@ -1 +1,2 @@
+pub mod mtc;
 pub mod timecode;
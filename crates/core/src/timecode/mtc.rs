@@ -0,0 +1,240 @@
+use std::time::{Duration, Instant};
+
+use crate::timecode::timecode::TimeCode;
+
+const QUARTER_FRAME: u8 = 0xF1;
+
+/// Frame rate for the two rate bits in the hours byte, 29.97 drop frame is counted as 30
+fn frame_rate(rate_bits: u8) -> u8 {
+    match rate_bits & 0x03 {
+        0 => 24,
+        1 => 25,
+        _ => 30,
+    }
+}
+
+/// Rate bits for a frame rate, 30 is sent as non-drop
+fn rate_bits(frame_rate: u8) -> u8 {
+    match frame_rate {
+        24 => 0,
+        25 => 1,
+        _ => 3,
+    }
+}
+
+fn timecode_from_frames(total: u32, frame_rate: u8) -> TimeCode {
+    let rate = frame_rate as u32;
+    TimeCode::new(
+        (total / (3600 * rate) % 24) as u8,
+        (total / (60 * rate) % 60) as u8,
+        (total / rate % 60) as u8,
+        (total % rate) as u8,
+        frame_rate,
+    )
+}
+
+/// Assembles MIDI timecode from quarter frame and full frame messages
+#[derive(Debug, Default)]
+pub struct MtcDecoder {
+    pieces: [u8; 8],
+    // Bit n is set once quarter frame piece n has arrived
+    received: u8,
+}
+
+impl MtcDecoder {
+    /// Feed a raw MIDI message, returning the timecode once a complete one is known
+    pub fn decode(&mut self, message: &[u8]) -> Option<TimeCode> {
+        match message {
+            [QUARTER_FRAME, data] => self.quarter_frame(*data),
+            // Universal real time SysEx full frame message
+            [0xF0, 0x7F, _, 0x01, 0x01, hours, minutes, seconds, frames, 0xF7] => {
+                // A locate, so the quarter frames so far are stale
+                self.received = 0;
+                Some(TimeCode::new(
+                    hours & 0x1F,
+                    *minutes,
+                    *seconds,
+                    *frames,
+                    frame_rate(hours >> 5),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn quarter_frame(&mut self, data: u8) -> Option<TimeCode> {
+        let piece = (data >> 4) as usize & 0x07;
+        if piece == 0 {
+            self.received = 0;
+        }
+        self.pieces[piece] = data & 0x0F;
+        self.received |= 1 << piece;
+
+        if piece != 7 || self.received != 0xFF {
+            return None;
+        }
+        self.received = 0;
+
+        let p = &self.pieces;
+        let rate = frame_rate(p[7] >> 1);
+        let hours = (p[6] | (p[7] & 0x01) << 4) as u32;
+        let minutes = (p[4] | (p[5] & 0x03) << 4) as u32;
+        let seconds = (p[2] | (p[3] & 0x03) << 4) as u32;
+        let frames = (p[0] | (p[1] & 0x01) << 4) as u32;
+
+        // The eight quarter frames take two frames to send, so the sender is two frames on
+        let total = ((hours * 60 + minutes) * 60 + seconds) * rate as u32 + frames + 2;
+        Some(timecode_from_frames(total, rate))
+    }
+}
+
+/// Generates MIDI timecode following the console's timecode
+#[derive(Debug, Default)]
+pub struct MtcEncoder {
+    // Seconds and frame rate of the timecode when it was last set, and when that was
+    source: Option<(f64, u8, Instant)>,
+    next_piece: u8,
+    // Frame the current run of eight quarter frames describes, counted from midnight
+    frame: u32,
+    last_frame: Option<u32>,
+}
+
+impl MtcEncoder {
+    /// Follow `timecode`, or stop sending with None
+    pub fn set_timecode(&mut self, timecode: Option<TimeCode>) {
+        self.source = timecode.map(|tc| (tc.to_seconds(), tc.frame_rate, Instant::now()));
+        if self.source.is_none() {
+            self.next_piece = 0;
+            self.last_frame = None;
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Time between quarter frames, four per frame
+    pub fn quarter_frame_period(&self) -> Duration {
+        let frame_rate = self.source.map(|(_, rate, _)| rate).unwrap_or(30);
+        Duration::from_secs_f64(1.0 / (4.0 * frame_rate as f64))
+    }
+
+    /// The messages to send this quarter frame, a full frame is sent first after a jump
+    pub fn tick(&mut self) -> Vec<Vec<u8>> {
+        let Some((seconds, rate, set_at)) = self.source else {
+            return Vec::new();
+        };
+
+        let mut messages = Vec::new();
+        if self.next_piece == 0 {
+            let seconds = seconds + set_at.elapsed().as_secs_f64();
+            let frame = (seconds * rate as f64) as u32;
+            // Each run of quarter frames moves on two frames, anything else is a locate
+            let expected = self.last_frame.map(|last| last + 2);
+            if expected.is_none_or(|expected| expected.abs_diff(frame) > 2) {
+                messages.push(full_frame(&timecode_from_frames(frame, rate)).to_vec());
+            }
+            self.frame = frame;
+            self.last_frame = Some(frame);
+        }
+
+        let tc = timecode_from_frames(self.frame, rate);
+        let nibble = match self.next_piece {
+            0 => tc.frames & 0x0F,
+            1 => tc.frames >> 4,
+            2 => tc.seconds & 0x0F,
+            3 => tc.seconds >> 4,
+            4 => tc.minutes & 0x0F,
+            5 => tc.minutes >> 4,
+            6 => tc.hours & 0x0F,
+            _ => tc.hours >> 4 | rate_bits(rate) << 1,
+        };
+        messages.push(vec![QUARTER_FRAME, self.next_piece << 4 | nibble]);
+        self.next_piece = (self.next_piece + 1) % 8;
+        messages
+    }
+}
+
+/// Full frame SysEx, sent so receivers locate straight to a new position
+fn full_frame(tc: &TimeCode) -> [u8; 10] {
+    [
+        0xF0,
+        0x7F,
+        0x7F,
+        0x01,
+        0x01,
+        tc.hours | rate_bits(tc.frame_rate) << 5,
+        tc.minutes,
+        tc.seconds,
+        tc.frames,
+        0xF7,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quarter_frames(hours: u8, minutes: u8, seconds: u8, frames: u8, rate_bits: u8) -> [u8; 8] {
+        [
+            frames & 0x0F,
+            frames >> 4,
+            seconds & 0x0F,
+            seconds >> 4,
+            minutes & 0x0F,
+            minutes >> 4,
+            hours & 0x0F,
+            hours >> 4 | rate_bits << 1,
+        ]
+    }
+
+    #[test]
+    fn test_quarter_frames() {
+        let mut decoder = MtcDecoder::default();
+        let pieces = quarter_frames(1, 2, 3, 4, 1);
+        for (piece, nibble) in pieces.iter().enumerate().take(7) {
+            let data = (piece as u8) << 4 | nibble;
+            assert!(decoder.decode(&[QUARTER_FRAME, data]).is_none());
+        }
+        let timecode = decoder.decode(&[QUARTER_FRAME, 0x70 | pieces[7]]).unwrap();
+        assert_eq!(timecode.frame_rate, 25);
+        assert_eq!(timecode.to_string(), "01:02:03:06");
+    }
+
+    #[test]
+    fn test_incomplete_quarter_frames_are_ignored() {
+        let mut decoder = MtcDecoder::default();
+        assert!(decoder.decode(&[QUARTER_FRAME, 0x70]).is_none());
+    }
+
+    #[test]
+    fn test_encoder_round_trip() {
+        let mut encoder = MtcEncoder::default();
+        encoder.set_timecode(Some(TimeCode::new(1, 0, 0, 0, 25)));
+
+        // A locate to the start, then eight quarter frames
+        let messages: Vec<Vec<u8>> = (0..8).flat_map(|_| encoder.tick()).collect();
+        assert_eq!(messages.len(), 9);
+
+        let mut decoder = MtcDecoder::default();
+        let located = decoder.decode(&messages[0]).unwrap();
+        assert_eq!(located.to_string(), "01:00:00:00");
+        let decoded: Vec<TimeCode> = messages[1..]
+            .iter()
+            .filter_map(|message| decoder.decode(message))
+            .collect();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].frame_rate, 25);
+        assert_eq!(decoded[0].to_string(), "01:00:00:02");
+    }
+
+    #[test]
+    fn test_full_frame() {
+        let mut decoder = MtcDecoder::default();
+        let hours = 10 | 3 << 5;
+        let message = [0xF0, 0x7F, 0x7F, 0x01, 0x01, hours, 20, 30, 15, 0xF7];
+        let timecode = decoder.decode(&message).unwrap();
+        assert_eq!(timecode.frame_rate, 30);
+        assert_eq!(timecode.to_string(), "10:20:30:15");
+    }
+}
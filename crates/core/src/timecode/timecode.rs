@@ -24,6 +24,17 @@ impl Default for TimeCode {
 }
 
 impl TimeCode {
+    pub fn new(hours: u8, minutes: u8, seconds: u8, frames: u8, frame_rate: u8) -> Self {
+        Self {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            frame_rate,
+            last_update: Instant::now(),
+        }
+    }
+
     pub fn update(&mut self) {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_update);
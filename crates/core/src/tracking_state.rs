@@ -8,8 +8,8 @@ use crate::{Cue, EffectMapping, PixelEffectMapping, StaticValue};
 pub struct TrackingState {
     /// Accumulated fixture channel values
     accumulated_values: Vec<StaticValue>,
-    /// Active effects that continue to run
-    active_effects: HashMap<String, EffectMapping>,
+    /// Active effects that continue to run, in the order they were started
+    active_effects: Vec<EffectMapping>,
     /// Active pixel effects that continue to run
     active_pixel_effects: HashMap<String, PixelEffectMapping>,
 }
@@ -19,7 +19,7 @@ impl TrackingState {
     pub fn new() -> Self {
         Self {
             accumulated_values: Vec::new(),
-            active_effects: HashMap::new(),
+            active_effects: Vec::new(),
             active_pixel_effects: HashMap::new(),
         }
     }
@@ -43,8 +43,7 @@ impl TrackingState {
         // Process effects based on release behavior
         for effect_mapping in &cue.effects {
             // Add or update the effect in tracking state
-            self.active_effects
-                .insert(effect_mapping.name.clone(), effect_mapping.clone());
+            self.add_effect(effect_mapping.clone());
         }
 
         // Process pixel effects based on release behavior
@@ -71,9 +70,11 @@ impl TrackingState {
         self.accumulated_values.clone()
     }
 
-    /// Get all active effects
+    /// Get all active effects in application order: by priority, then oldest first
     pub fn get_effects(&self) -> Vec<EffectMapping> {
-        self.active_effects.values().cloned().collect()
+        let mut effects = self.active_effects.clone();
+        effects.sort_by_key(|e| e.priority);
+        effects
     }
 
     /// Get all active pixel effects
@@ -101,9 +102,11 @@ impl TrackingState {
     }
 
     /// Add or update an effect in the tracking state
+    /// The effect becomes the most recently started, so it wins over others at the same priority
     pub fn add_effect(&mut self, effect_mapping: EffectMapping) {
         self.active_effects
-            .insert(effect_mapping.name.clone(), effect_mapping);
+            .retain(|e| e.name != effect_mapping.name);
+        self.active_effects.push(effect_mapping);
     }
 }
 
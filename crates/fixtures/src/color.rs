@@ -0,0 +1,227 @@
+use crate::{ChannelType, Fixture, FixtureProfile};
+
+/// A slot on a color wheel and the color it produces
+#[derive(Clone, Copy, Debug)]
+pub struct ColorWheelSlot {
+    pub value: u8,
+    pub color: [u8; 3],
+}
+
+/// Slot layout used for color wheel channels when the profile doesn't describe its wheel
+/// This follows the common open/red/orange/yellow/green/cyan/blue/magenta layout of budget spots
+pub const DEFAULT_COLOR_WHEEL: [ColorWheelSlot; 8] = [
+    ColorWheelSlot {
+        value: 0,
+        color: [255, 255, 255],
+    },
+    ColorWheelSlot {
+        value: 10,
+        color: [255, 0, 0],
+    },
+    ColorWheelSlot {
+        value: 20,
+        color: [255, 127, 0],
+    },
+    ColorWheelSlot {
+        value: 30,
+        color: [255, 255, 0],
+    },
+    ColorWheelSlot {
+        value: 40,
+        color: [0, 255, 0],
+    },
+    ColorWheelSlot {
+        value: 50,
+        color: [0, 255, 255],
+    },
+    ColorWheelSlot {
+        value: 60,
+        color: [0, 0, 255],
+    },
+    ColorWheelSlot {
+        value: 70,
+        color: [255, 0, 255],
+    },
+];
+
+impl FixtureProfile {
+    /// The profile's color wheel, or the default layout when it doesn't define one
+    pub fn color_wheel_slots(&self) -> &[ColorWheelSlot] {
+        if self.color_wheel.is_empty() {
+            &DEFAULT_COLOR_WHEEL
+        } else {
+            &self.color_wheel
+        }
+    }
+}
+
+impl Fixture {
+    /// Channel values that make this fixture produce an RGB color as closely as it can
+    ///
+    /// RGB emitters get the color directly, with the shared white part moved to a white emitter
+    /// when there is one. CMY flags subtract from white and color wheels snap to the nearest slot.
+    pub fn color_values(&self, color: [u8; 3]) -> Vec<(ChannelType, u8)> {
+        let has = |channel_type: &ChannelType| {
+            self.channels
+                .iter()
+                .any(|channel| channel.channel_type == *channel_type)
+        };
+        let [red, green, blue] = color;
+        let mut values = Vec::new();
+
+        if has(&ChannelType::Red) || has(&ChannelType::Green) || has(&ChannelType::Blue) {
+            let white = if has(&ChannelType::White) {
+                red.min(green).min(blue)
+            } else {
+                0
+            };
+            values.push((ChannelType::Red, red - white));
+            values.push((ChannelType::Green, green - white));
+            values.push((ChannelType::Blue, blue - white));
+            if has(&ChannelType::White) {
+                values.push((ChannelType::White, white));
+            }
+            if has(&ChannelType::Amber) {
+                values.push((ChannelType::Amber, 0));
+            }
+            if has(&ChannelType::UV) {
+                values.push((ChannelType::UV, 0));
+            }
+        }
+
+        if has(&ChannelType::Cyan) || has(&ChannelType::Magenta) || has(&ChannelType::Yellow) {
+            values.push((ChannelType::Cyan, 255 - red));
+            values.push((ChannelType::Magenta, 255 - green));
+            values.push((ChannelType::Yellow, 255 - blue));
+        }
+
+        if has(&ChannelType::Color) {
+            if let Some(slot) = nearest_wheel_slot(self.profile.color_wheel_slots(), color) {
+                values.push((ChannelType::Color, slot.value));
+            }
+        }
+
+        values.retain(|(channel_type, _)| has(channel_type));
+        values
+    }
+}
+
+fn nearest_wheel_slot(slots: &[ColorWheelSlot], color: [u8; 3]) -> Option<ColorWheelSlot> {
+    let distance = |slot: &ColorWheelSlot| -> i32 {
+        slot.color
+            .iter()
+            .zip(color.iter())
+            .map(|(a, b)| (*a as i32 - *b as i32).pow(2))
+            .sum()
+    };
+    slots.iter().copied().min_by_key(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Channel;
+
+    fn fixture(channel_types: &[ChannelType]) -> Fixture {
+        Fixture {
+            id: 1,
+            name: "Test".to_string(),
+            profile_id: String::new(),
+            profile: FixtureProfile::default(),
+            channels: channel_types
+                .iter()
+                .map(|channel_type| Channel {
+                    name: channel_type.to_string(),
+                    channel_type: channel_type.clone(),
+                    value: 0,
+                })
+                .collect(),
+            universe: 1,
+            start_address: 1,
+            pan_tilt_limits: None,
+            position: None,
+        }
+    }
+
+    #[test]
+    fn test_color_values_rgb() {
+        let rgb = fixture(&[
+            ChannelType::Dimmer,
+            ChannelType::Red,
+            ChannelType::Green,
+            ChannelType::Blue,
+        ]);
+        assert_eq!(
+            rgb.color_values([255, 128, 0]),
+            vec![
+                (ChannelType::Red, 255),
+                (ChannelType::Green, 128),
+                (ChannelType::Blue, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_color_values_rgbw_moves_shared_white() {
+        let rgbw = fixture(&[
+            ChannelType::Red,
+            ChannelType::Green,
+            ChannelType::Blue,
+            ChannelType::White,
+        ]);
+        assert_eq!(
+            rgbw.color_values([255, 200, 100]),
+            vec![
+                (ChannelType::Red, 155),
+                (ChannelType::Green, 100),
+                (ChannelType::Blue, 0),
+                (ChannelType::White, 100)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_color_values_cmy() {
+        let cmy = fixture(&[ChannelType::Cyan, ChannelType::Magenta, ChannelType::Yellow]);
+        assert_eq!(
+            cmy.color_values([255, 0, 64]),
+            vec![
+                (ChannelType::Cyan, 0),
+                (ChannelType::Magenta, 255),
+                (ChannelType::Yellow, 191)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_color_values_wheel_uses_profile_slots() {
+        let mut spot = fixture(&[ChannelType::Color]);
+        // Pure blue lands on the default wheel's blue slot
+        assert_eq!(
+            spot.color_values([0, 0, 250]),
+            vec![(ChannelType::Color, 60)]
+        );
+
+        spot.profile.color_wheel = vec![
+            ColorWheelSlot {
+                value: 0,
+                color: [255, 255, 255],
+            },
+            ColorWheelSlot {
+                value: 100,
+                color: [0, 0, 255],
+            },
+        ];
+        assert_eq!(
+            spot.color_values([0, 0, 250]),
+            vec![(ChannelType::Color, 100)]
+        );
+    }
+
+    #[test]
+    fn test_nearest_wheel_slot() {
+        let slot = nearest_wheel_slot(&DEFAULT_COLOR_WHEEL, [240, 120, 10]).unwrap();
+        assert_eq!(slot.value, 20);
+        assert!(nearest_wheel_slot(&[], [0, 0, 0]).is_none());
+    }
+}
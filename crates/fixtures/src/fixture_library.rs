@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{channel_layout, FixtureType};
+use crate::{channel_layout, ColorWheelSlot, FixtureType};
 
 #[derive(Clone, Debug, Default)]
 pub struct FixtureProfile {
@@ -11,6 +11,8 @@ pub struct FixtureProfile {
     pub manufacturer: String,
     pub model: String,
     pub channel_layout: Vec<Channel>,
+    /// Slots of the color wheel, empty when the profile doesn't describe its wheel
+    pub color_wheel: Vec<ColorWheelSlot>,
 }
 
 impl std::fmt::Display for FixtureProfile {
@@ -78,6 +80,7 @@ impl FixtureLibrary {
                         value: 0,
                     },
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -135,6 +138,7 @@ impl FixtureLibrary {
                         value: 0,
                     },
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -198,6 +202,7 @@ impl FixtureLibrary {
                         value: 0,
                     },
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -225,6 +230,7 @@ impl FixtureLibrary {
                     // From slow to fast
                     ("Speed", ChannelType::Other("FunctionSpeed".to_string())),
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -278,6 +284,7 @@ impl FixtureLibrary {
                         value: 0,
                     },
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -313,6 +320,7 @@ impl FixtureLibrary {
                     ("Blue", ChannelType::Blue),
                     ("White", ChannelType::White),
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -377,6 +385,7 @@ impl FixtureLibrary {
                     ("Blue", ChannelType::Blue),
                     ("White", ChannelType::White),
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -397,6 +406,7 @@ impl FixtureLibrary {
                     ("Function", ChannelType::Function),
                     ("Function Speed", ChannelType::FunctionSpeed),
                 ],
+                color_wheel: Vec::new(),
             },
         );
 
@@ -409,6 +419,7 @@ impl FixtureLibrary {
                 manufacturer: "Generic".to_string(),
                 model: "RGB Pixel Bar 30 Pixels".to_string(),
                 channel_layout: Self::create_pixel_bar_channels(30),
+                color_wheel: Vec::new(),
             },
         );
 
@@ -420,6 +431,7 @@ impl FixtureLibrary {
                 manufacturer: "Generic".to_string(),
                 model: "RGB Pixel Bar 60 Pixels".to_string(),
                 channel_layout: Self::create_pixel_bar_channels(60),
+                color_wheel: Vec::new(),
             },
         );
 
@@ -431,6 +443,7 @@ impl FixtureLibrary {
                 manufacturer: "Generic".to_string(),
                 model: "RGB Pixel Bar 144 Pixels".to_string(),
                 channel_layout: Self::create_pixel_bar_channels(144),
+                color_wheel: Vec::new(),
             },
         );
 
@@ -442,6 +455,7 @@ impl FixtureLibrary {
                 manufacturer: "Clen".to_string(),
                 model: "LED Pixel Bar 64 Pixels RGB".to_string(),
                 channel_layout: Self::create_pixel_bar_channels(64),
+                color_wheel: Vec::new(),
             },
         );
 
@@ -490,6 +504,9 @@ pub enum ChannelType {
     White,
     Amber,
     UV,
+    Cyan,
+    Magenta,
+    Yellow,
     Strobe,
     Pan,
     Tilt,
@@ -517,6 +534,9 @@ impl std::fmt::Display for ChannelType {
             ChannelType::White => write!(f, "White"),
             ChannelType::Amber => write!(f, "Amber"),
             ChannelType::UV => write!(f, "UV"),
+            ChannelType::Cyan => write!(f, "Cyan"),
+            ChannelType::Magenta => write!(f, "Magenta"),
+            ChannelType::Yellow => write!(f, "Yellow"),
             ChannelType::Strobe => write!(f, "Strobe"),
             ChannelType::Pan => write!(f, "Pan"),
             ChannelType::Tilt => write!(f, "Tilt"),
@@ -1,6 +1,8 @@
+pub use color::{ColorWheelSlot, DEFAULT_COLOR_WHEEL};
 pub use fixture_library::{Channel, ChannelType, FixtureLibrary, FixtureProfile};
 use serde::{Deserialize, Serialize};
 
+mod color;
 mod fixture_library;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,6 +13,22 @@ pub struct PanTiltLimits {
     pub tilt_max: u8,
 }
 
+/// Physical location of a fixture on stage, in meters
+/// X runs stage left to right, Y runs downstage to upstage and Z is height
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FixturePosition {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl FixturePosition {
+    pub fn distance(&self, other: &FixturePosition) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2))
+            .sqrt()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Fixture {
     pub id: usize,
@@ -24,6 +42,8 @@ pub struct Fixture {
     pub start_address: u16,
     #[serde(default)]
     pub pan_tilt_limits: Option<PanTiltLimits>,
+    #[serde(default)]
+    pub position: Option<FixturePosition>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
@@ -57,6 +77,7 @@ impl Fixture {
             universe,
             start_address,
             pan_tilt_limits: None,
+            position: None,
         }
     }
 
@@ -100,6 +121,14 @@ impl Fixture {
     pub fn get_pan_tilt_limits(&self) -> Option<&PanTiltLimits> {
         self.pan_tilt_limits.as_ref()
     }
+
+    pub fn set_position(&mut self, position: FixturePosition) {
+        self.position = Some(position);
+    }
+
+    pub fn clear_position(&mut self) {
+        self.position = None;
+    }
 }
 
 #[macro_export]
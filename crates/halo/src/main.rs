@@ -65,6 +65,7 @@ fn parse_ip(s: &str) -> Result<IpAddr, String> {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    let log_rx = halo_ui::init_logging();
 
     // Load configuration before initializing anything else
     println!("Loading configuration...");
@@ -297,7 +298,13 @@ async fn main() -> anyhow::Result<()> {
     // Run the UI with the channels (this will block until UI closes)
     log::info!("Starting UI...");
     let show_path = show_file_path.map(std::path::PathBuf::from);
-    let ui_result = halo_ui::run_ui(command_tx.clone(), ui_event_rx, show_path, config_manager);
+    let ui_result = halo_ui::run_ui(
+        command_tx.clone(),
+        ui_event_rx,
+        show_path,
+        config_manager,
+        log_rx,
+    );
     log::info!("UI completed");
 
     // Send shutdown command
@@ -0,0 +1,84 @@
+use eframe::egui::{self, Color32, Sense, Stroke, Vec2};
+use halo_core::ConsoleCommand;
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+
+const SWATCH_SIZE: f32 = 24.0;
+
+/// Color picker with the show's saved swatch palette, shared by the programmer and cue editor
+#[derive(Default)]
+pub struct ColorPicker {
+    new_swatch_name: String,
+}
+
+impl ColorPicker {
+    /// Returns true when the color was changed by the picker or a swatch
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        color: &mut Color32,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) -> bool {
+        let mut changed =
+            egui::color_picker::color_picker_color32(ui, color, egui::color_picker::Alpha::Opaque);
+
+        ui.add_space(5.0);
+        ui.label("Swatches");
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing = Vec2::splat(4.0);
+            if state.color_swatches.is_empty() {
+                ui.weak("None saved");
+            }
+            for (index, swatch) in state.color_swatches.iter().enumerate() {
+                let [r, g, b] = swatch.color;
+                let swatch_color = Color32::from_rgb(r, g, b);
+                let (rect, response) =
+                    ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::click());
+                ui.painter().rect_filled(rect, 4.0, swatch_color);
+                let stroke = if swatch_color == *color {
+                    Stroke::new(2.0, ui.visuals().selection.bg_fill)
+                } else {
+                    Stroke::new(1.0, Color32::from_gray(100))
+                };
+                ui.painter()
+                    .rect_stroke(rect, 4.0, stroke, egui::StrokeKind::Inside);
+
+                let response = response.on_hover_text(&swatch.name);
+                if response.clicked() {
+                    *color = swatch_color;
+                    changed = true;
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Delete Swatch").clicked() {
+                        let _ = console_tx.send(ConsoleCommand::DeleteColorSwatch { index });
+                        ui.close();
+                    }
+                });
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_swatch_name)
+                    .hint_text("Swatch name")
+                    .desired_width(110.0),
+            );
+            let name = self.new_swatch_name.trim();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("Save"))
+                .on_hover_text("Save the current color to the show palette")
+                .clicked()
+            {
+                let _ = console_tx.send(ConsoleCommand::StoreColorSwatch {
+                    name: name.to_string(),
+                    color: [color.r(), color.g(), color.b()],
+                });
+                self.new_swatch_name.clear();
+            }
+        });
+
+        changed
+    }
+}
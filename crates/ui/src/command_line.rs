@@ -0,0 +1,213 @@
+use eframe::egui::{self, Color32, Key, RichText};
+use halo_core::{parse_command_line, CommandLineContext, ConsoleCommand};
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+
+const HISTORY_LIMIT: usize = 50;
+const KEY_SIZE: [f32; 2] = [64.0, 48.0];
+
+/// Keypad rows; digits join up into numbers, everything else is typed as its own word
+const KEYPAD: [[&str; 4]; 5] = [
+    ["group", "thru", "-", "+"],
+    ["7", "8", "9", "@"],
+    ["4", "5", "6", "full"],
+    ["1", "2", "3", "out"],
+    ["0", ".", "cue", "time"],
+];
+
+/// Footer command input with up/down history
+#[derive(Default)]
+pub struct CommandLine {
+    input: String,
+    history: Vec<String>,
+    // Position while browsing history, None when editing a new line
+    history_index: Option<usize>,
+    feedback: Option<Result<String, String>>,
+    keypad_open: bool,
+}
+
+impl CommandLine {
+    pub fn render(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(">").monospace().strong());
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.input)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text("1 thru 5 @ 50, group 2, cue 3, /goto cue 5, /patch …")
+                    .desired_width(420.0),
+            );
+
+            if response.has_focus() {
+                if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
+                    self.browse_history(true);
+                }
+                if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
+                    self.browse_history(false);
+                }
+            }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                self.submit(state, console_tx);
+                response.request_focus();
+            }
+
+            egui::ComboBox::from_id_salt("command_line_history")
+                .selected_text("History")
+                .width(80.0)
+                .show_ui(ui, |ui| {
+                    for line in self.history.iter().rev() {
+                        if ui.selectable_label(false, line).clicked() {
+                            self.input = line.clone();
+                        }
+                    }
+                });
+
+            if ui
+                .selectable_label(self.keypad_open, "⌨ Keypad")
+                .on_hover_text("On-screen keypad for touchscreens")
+                .clicked()
+            {
+                self.keypad_open = !self.keypad_open;
+            }
+
+            match &self.feedback {
+                Some(Ok(message)) => {
+                    ui.label(RichText::new(message).color(Color32::from_gray(150)));
+                }
+                Some(Err(message)) => {
+                    ui.label(RichText::new(message).color(Color32::from_rgb(255, 100, 100)));
+                }
+                None => {}
+            }
+        });
+
+        if self.keypad_open {
+            self.render_keypad(ui.ctx(), state, console_tx);
+        }
+    }
+
+    fn render_keypad(
+        &mut self,
+        ctx: &egui::Context,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let mut open = self.keypad_open;
+        egui::Window::new("Keypad")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .default_pos(ctx.content_rect().right_bottom() - egui::vec2(320.0, 480.0))
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("> {}", self.input))
+                        .monospace()
+                        .size(16.0),
+                );
+                ui.add_space(4.0);
+
+                egui::Grid::new("command_keypad")
+                    .spacing([4.0, 4.0])
+                    .show(ui, |ui| {
+                        for row in KEYPAD {
+                            for key in row {
+                                let label = RichText::new(key.to_uppercase()).size(18.0);
+                                if ui.add_sized(KEY_SIZE, egui::Button::new(label)).clicked() {
+                                    self.press_key(key);
+                                }
+                            }
+                            ui.end_row();
+                        }
+
+                        if ui
+                            .add_sized(KEY_SIZE, egui::Button::new(RichText::new("⌫").size(18.0)))
+                            .clicked()
+                        {
+                            let trimmed = self.input.trim_end().len();
+                            self.input.truncate(trimmed);
+                            self.input.pop();
+                        }
+                        if ui
+                            .add_sized(KEY_SIZE, egui::Button::new(RichText::new("CLR").size(18.0)))
+                            .clicked()
+                        {
+                            self.input.clear();
+                        }
+                        let enter = egui::Button::new(RichText::new("ENTER").size(18.0).strong());
+                        if ui
+                            .add_sized([KEY_SIZE[0] * 2.0 + 4.0, KEY_SIZE[1]], enter)
+                            .clicked()
+                        {
+                            self.submit(state, console_tx);
+                        }
+                        ui.end_row();
+                    });
+            });
+        self.keypad_open = open;
+    }
+
+    /// Type a keypad key into the line, joining digits and spacing out words
+    fn press_key(&mut self, key: &str) {
+        let joins_number = key.chars().all(|c| c.is_ascii_digit() || c == '.')
+            && self
+                .input
+                .ends_with(|c: char| c.is_ascii_digit() || c == '.');
+        if !joins_number && !self.input.is_empty() && !self.input.ends_with(' ') {
+            self.input.push(' ');
+        }
+        self.input.push_str(key);
+        self.history_index = None;
+    }
+
+    fn submit(&mut self, state: &ConsoleState, console_tx: &mpsc::UnboundedSender<ConsoleCommand>) {
+        let line = self.input.trim().to_string();
+        if line.is_empty() {
+            return;
+        }
+
+        let context = CommandLineContext {
+            current_cue_list_index: state.current_cue_list_index,
+            selected_fixtures: &state.selected_fixtures,
+            fixture_groups: &state.fixture_groups,
+            cue_lists: &state.cue_lists,
+        };
+        self.feedback = Some(match parse_command_line(&line, &context) {
+            Ok(commands) => {
+                let count = commands.len();
+                for command in commands {
+                    let _ = console_tx.send(command);
+                }
+                self.input.clear();
+                Ok(format!("{line} ({count} command(s))"))
+            }
+            Err(e) => Err(e),
+        });
+
+        self.history.retain(|l| *l != line);
+        self.history.push(line);
+        if self.history.len() > HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.history_index = None;
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match (self.history_index, older) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i + 1 < self.history.len() => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.history_index = index;
+        self.input = index.map(|i| self.history[i].clone()).unwrap_or_default();
+    }
+}
@@ -4,6 +4,7 @@ use eframe::egui;
 use halo_core::{ConsoleCommand, PlaybackState};
 use tokio::sync::mpsc;
 
+use crate::file_drop;
 use crate::state::ConsoleState;
 
 /// A panel that shows the list of cues.
@@ -26,7 +27,7 @@ impl CuePanel {
         // Cue UI with margin
         let frame = eframe::egui::Frame::default().inner_margin(10.0);
 
-        frame.show(ui, |ui| {
+        let panel = frame.show(ui, |ui| {
             ui.heading("Cues");
 
             let cue_lists = &state.cue_lists;
@@ -222,6 +223,9 @@ impl CuePanel {
                 });
             }
         });
+
+        // Audio dropped anywhere on the panel goes to the current list
+        file_drop::audio_drop_target(ui, panel.response.rect, state.current_cue_list_index);
     }
 
     pub fn set_playback_state(&mut self, state: PlaybackState) {
@@ -1,7 +1,14 @@
 use eframe::egui;
-use halo_core::{ConsoleCommand, CueList};
+use halo_core::{
+    Chase, ChaseStep, ConsoleCommand, Cue, CueList, Effect, EffectDirection, EffectDistribution,
+    EffectMapping, EffectMerge, EffectParams, EffectPresetType, EffectType, Subdivision,
+    SubdivisionModifier,
+};
+use halo_fixtures::ChannelType;
 use tokio::sync::mpsc;
 
+use crate::color_picker::ColorPicker;
+use crate::file_drop;
 use crate::state::ConsoleState;
 
 pub struct CueEditor {
@@ -17,6 +24,10 @@ pub struct CueEditor {
     show_delete_cue_list_dialog: bool,
     cue_to_delete: Option<(usize, usize)>, // (list_index, cue_index)
     cue_list_to_delete: Option<usize>,
+
+    // Color applied to the selected cue's fixtures
+    cue_color: egui::Color32,
+    color_picker: ColorPicker,
 }
 
 impl Default for CueEditor {
@@ -32,6 +43,8 @@ impl Default for CueEditor {
             show_delete_cue_list_dialog: false,
             cue_to_delete: None,
             cue_list_to_delete: None,
+            cue_color: egui::Color32::WHITE,
+            color_picker: ColorPicker::default(),
         }
     }
 }
@@ -41,6 +54,12 @@ impl CueEditor {
         Self::default()
     }
 
+    /// Show a cue list, and optionally one of its cues, e.g. when jumping from search
+    pub fn select(&mut self, list_index: usize, cue_index: Option<usize>) {
+        self.selected_cue_list_index = Some(list_index);
+        self.selected_cue_index = cue_index;
+    }
+
     pub fn render(
         &mut self,
         ctx: &egui::Context,
@@ -183,7 +202,7 @@ impl CueEditor {
                 for (idx, cue_list) in cue_lists.iter().enumerate() {
                     let is_selected = self.selected_cue_list_index == Some(idx);
 
-                    ui.horizontal(|ui| {
+                    let row = ui.horizontal(|ui| {
                         // Fixed width for cue list name
                         ui.allocate_ui_with_layout(
                             egui::Vec2::new(ui.available_width() - 30.0, 0.0),
@@ -209,6 +228,7 @@ impl CueEditor {
                             },
                         );
                     });
+                    file_drop::audio_drop_target(ui, row.response.rect, idx);
                 }
             });
 
@@ -229,7 +249,7 @@ impl CueEditor {
                             let label = ui.label(format!("📁 {}", filename));
                             label.on_hover_text(audio_file);
                         } else {
-                            ui.label("No audio file selected");
+                            ui.label("No audio file selected, drop one on the cue list");
                         }
                     });
 
@@ -296,6 +316,14 @@ impl CueEditor {
 
                     // Cue table
                     self.render_cue_table(ui, cue_list, cue_list_idx, console_tx);
+
+                    // Effect timing for the selected cue
+                    if let Some(cue) = self.selected_cue_index.and_then(|i| cue_list.cues.get(i)) {
+                        ui.separator();
+                        self.render_cue_effects(ui, state, cue, cue_list_idx, console_tx);
+                        ui.separator();
+                        self.render_effect_presets(ui, state, cue_list_idx, console_tx);
+                    }
                 }
             } else {
                 ui.label("Please select a cue list from the right panel");
@@ -337,7 +365,7 @@ impl CueEditor {
                         |ui| ui.label("Blocking"),
                     );
                     ui.allocate_ui_with_layout(
-                        egui::Vec2::new(60.0, 0.0),
+                        egui::Vec2::new(80.0, 0.0),
                         egui::Layout::left_to_right(egui::Align::Center),
                         |ui| ui.label("Actions"),
                     );
@@ -445,9 +473,18 @@ impl CueEditor {
 
                         // Actions column
                         ui.allocate_ui_with_layout(
-                            egui::Vec2::new(60.0, 0.0),
+                            egui::Vec2::new(80.0, 0.0),
                             egui::Layout::left_to_right(egui::Align::Center),
                             |ui| {
+                                let is_selected = self.selected_cue_index == Some(idx);
+                                if ui
+                                    .selectable_label(is_selected, "FX")
+                                    .on_hover_text("Edit effect timing")
+                                    .clicked()
+                                {
+                                    self.selected_cue_index =
+                                        if is_selected { None } else { Some(idx) };
+                                }
                                 if ui.button("🗑").clicked() {
                                     self.cue_to_delete = Some((cue_list_idx, idx));
                                     self.show_delete_cue_dialog = true;
@@ -460,4 +497,490 @@ impl CueEditor {
                 });
         });
     }
+
+    fn render_cue_effects(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        cue: &Cue,
+        cue_list_idx: usize,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let Some(cue_idx) = self.selected_cue_index else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.heading(format!("Effects: {}", cue.name));
+
+            // New chases start with one step per selected fixture, in selection order
+            let has_selection = !state.selected_fixtures.is_empty();
+            if ui
+                .add_enabled(has_selection, egui::Button::new("Add Chase"))
+                .on_disabled_hover_text("Select fixtures to chase")
+                .clicked()
+            {
+                let effect = EffectMapping {
+                    name: format!("Chase {}", cue.effects.len() + 1),
+                    effect: Effect {
+                        effect_type: EffectType::Chase,
+                        params: EffectParams {
+                            subdivision: Some(Subdivision::bars(1)),
+                            ..Default::default()
+                        },
+                        chase: Some(Chase::single_fixture_steps(&state.selected_fixtures)),
+                        ..Default::default()
+                    },
+                    fixture_ids: state.selected_fixtures.clone(),
+                    channel_types: vec![ChannelType::Dimmer],
+                    distribution: EffectDistribution::All,
+                    release: Default::default(),
+                    preset_id: None,
+                    priority: 0,
+                    merge: Default::default(),
+                    direction: Default::default(),
+                };
+                let _ = console_tx.send(ConsoleCommand::AddCueEffect {
+                    list_index: cue_list_idx,
+                    cue_index: cue_idx,
+                    effect,
+                });
+            }
+        });
+
+        egui::CollapsingHeader::new("Cue Color")
+            .id_salt(("cue_color", cue_list_idx, cue_idx))
+            .show(ui, |ui| {
+                if cue.static_values.is_empty() {
+                    ui.label("Record fixtures into this cue to set their color");
+                    return;
+                }
+                self.color_picker
+                    .show(ui, &mut self.cue_color, state, console_tx);
+                if ui
+                    .button("Apply to Cue")
+                    .on_hover_text("Set every fixture in this cue to the color")
+                    .clicked()
+                {
+                    let _ = console_tx.send(ConsoleCommand::SetCueColor {
+                        list_index: cue_list_idx,
+                        cue_index: cue_idx,
+                        red: self.cue_color.r(),
+                        green: self.cue_color.g(),
+                        blue: self.cue_color.b(),
+                    });
+                }
+            });
+
+        if cue.effects.is_empty() {
+            ui.label("This cue has no effects");
+            return;
+        }
+
+        egui::Grid::new("cue_effects_table")
+            .num_columns(8)
+            .spacing([20.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Name");
+                ui.label("Type");
+                ui.label("Subdivision");
+                ui.label("Preset");
+                ui.label("Priority");
+                ui.label("Merge");
+                ui.label("Direction");
+                ui.label("Preview");
+                ui.end_row();
+
+                for (effect_idx, mapping) in cue.effects.iter().enumerate() {
+                    ui.label(&mapping.name);
+                    ui.label(mapping.effect.effect_type.as_str());
+
+                    let current = mapping.effect.params.subdivision;
+                    let selected = Self::render_subdivision_picker(
+                        ui,
+                        ("cue_effect_subdivision", effect_idx),
+                        &mapping.effect.params,
+                    );
+
+                    match &mapping.preset_id {
+                        Some(preset_id) => {
+                            let preset_name = state
+                                .presets
+                                .effect
+                                .iter()
+                                .find(|p| p.id == *preset_id)
+                                .map(|p| p.name.as_str())
+                                .unwrap_or("?");
+                            ui.label(format!("🔗 {preset_name}"));
+                        }
+                        None => {
+                            if ui.button("Save as Preset").clicked() {
+                                let _ = console_tx.send(ConsoleCommand::StoreEffectPreset {
+                                    name: mapping.name.clone(),
+                                    effect: mapping.effect.clone(),
+                                    distribution: mapping.distribution.clone(),
+                                    channel_types: mapping.channel_types.clone(),
+                                });
+                            }
+                        }
+                    }
+
+                    // Priority, merge and direction belong to this use of the effect, not to its
+                    // preset
+                    let mut priority = mapping.priority;
+                    let mut merge = mapping.merge;
+                    let mut direction = mapping.direction;
+                    ui.add(egui::DragValue::new(&mut priority).range(-99..=99));
+                    egui::ComboBox::from_id_salt(("cue_effect_merge", effect_idx))
+                        .selected_text(merge.as_str())
+                        .show_ui(ui, |ui| {
+                            for mode in EffectMerge::all() {
+                                ui.selectable_value(&mut merge, mode, mode.as_str());
+                            }
+                        });
+                    egui::ComboBox::from_id_salt(("cue_effect_direction", effect_idx))
+                        .selected_text(direction.as_str())
+                        .show_ui(ui, |ui| {
+                            for value in EffectDirection::all() {
+                                ui.selectable_value(&mut direction, value, value.as_str());
+                            }
+                        });
+                    Self::render_effect_preview(ui, state, mapping);
+                    if priority != mapping.priority
+                        || merge != mapping.merge
+                        || direction != mapping.direction
+                    {
+                        let mut updated = mapping.clone();
+                        updated.priority = priority;
+                        updated.merge = merge;
+                        updated.direction = direction;
+                        let _ = console_tx.send(ConsoleCommand::UpdateCueEffect {
+                            list_index: cue_list_idx,
+                            cue_index: cue_idx,
+                            effect_index: effect_idx,
+                            effect: updated,
+                        });
+                    }
+                    ui.end_row();
+
+                    if selected != current {
+                        let mut updated = mapping.clone();
+                        updated.effect.params.subdivision = selected;
+                        Self::send_effect_update(
+                            state,
+                            updated,
+                            cue_list_idx,
+                            cue_idx,
+                            effect_idx,
+                            console_tx,
+                        );
+                    }
+                }
+            });
+
+        // Step grids for chase effects
+        for (effect_idx, mapping) in cue.effects.iter().enumerate() {
+            let Some(chase) = &mapping.effect.chase else {
+                continue;
+            };
+
+            ui.separator();
+            ui.label(egui::RichText::new(&mapping.name).strong());
+            if let Some(chase) = Self::render_chase_steps(ui, state, mapping, chase, effect_idx) {
+                let mut updated = mapping.clone();
+                updated.effect.chase = Some(chase);
+                Self::send_effect_update(
+                    state,
+                    updated,
+                    cue_list_idx,
+                    cue_idx,
+                    effect_idx,
+                    console_tx,
+                );
+            }
+        }
+    }
+
+    /// Animated dots, one per fixture, following the effect's waveform and distribution on the
+    /// current beat so it can be judged without live output
+    fn render_effect_preview(ui: &mut egui::Ui, state: &ConsoleState, mapping: &EffectMapping) {
+        const DOT_SPACING: f32 = 14.0;
+        const MAX_WIDTH: f32 = 180.0;
+
+        if mapping.fixture_ids.is_empty() {
+            ui.weak("No fixtures");
+            return;
+        }
+
+        let count = mapping.fixture_ids.len();
+        let spacing = DOT_SPACING.min(MAX_WIDTH / count as f32);
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(spacing * count as f32, 16.0),
+            egui::Sense::hover(),
+        );
+
+        let phase = mapping.phase(&state.rhythm_state);
+        let levels = mapping.levels(phase, |id| {
+            state
+                .fixtures
+                .values()
+                .find(|f| f.id == id)
+                .and_then(|f| f.position)
+        });
+
+        // Dots show the effect's min to max travel, not just the raw waveform
+        let min = mapping.effect.min as f32 / 255.0;
+        let max = mapping.effect.max as f32 / 255.0;
+        let accent = ui.visuals().selection.bg_fill;
+        let off = ui.visuals().extreme_bg_color;
+        for (index, level) in levels.iter().enumerate() {
+            let value = (min + (max - min) * *level as f32).clamp(0.0, 1.0);
+            let center = egui::pos2(rect.min.x + spacing * (index as f32 + 0.5), rect.center().y);
+            let radius = (spacing / 2.0 - 1.0).clamp(1.5, 6.0);
+            let color: egui::Color32 =
+                egui::lerp(egui::Rgba::from(off)..=egui::Rgba::from(accent), value).into();
+            ui.painter().circle_filled(center, radius, color);
+        }
+
+        response.on_hover_text(format!("{count} fixtures"));
+        ui.ctx().request_repaint();
+    }
+
+    /// Renders a subdivision picker for an effect's timing, returning the selected subdivision
+    fn render_subdivision_picker(
+        ui: &mut egui::Ui,
+        id_salt: impl std::hash::Hash,
+        params: &EffectParams,
+    ) -> Option<Subdivision> {
+        let mut selected = params.subdivision;
+        ui.horizontal(|ui| {
+            let straight = selected.map(|s| s.with_modifier(Default::default()));
+            egui::ComboBox::from_id_salt(id_salt)
+                .selected_text(match straight {
+                    Some(subdivision) => subdivision.label(),
+                    None => format!("{:?} x{}", params.interval, params.interval_ratio),
+                })
+                .show_ui(ui, |ui| {
+                    let modifier = selected.map(|s| s.modifier).unwrap_or_default();
+                    for preset in Subdivision::presets() {
+                        if ui
+                            .selectable_label(straight == Some(preset), preset.label())
+                            .clicked()
+                        {
+                            selected = Some(preset.with_modifier(modifier));
+                        }
+                    }
+                });
+
+            if let Some(subdivision) = selected.as_mut() {
+                for modifier in SubdivisionModifier::all() {
+                    ui.radio_value(&mut subdivision.modifier, modifier, modifier.as_str());
+                }
+                if ui
+                    .small_button("✖")
+                    .on_hover_text("Use the effect's interval instead")
+                    .clicked()
+                {
+                    selected = None;
+                }
+            }
+        });
+        selected
+    }
+
+    /// Sends an edited cue effect to the console. Effects linked to a preset update the preset
+    /// instead, so the change reaches every cue that uses it.
+    fn send_effect_update(
+        state: &ConsoleState,
+        effect: EffectMapping,
+        list_index: usize,
+        cue_index: usize,
+        effect_index: usize,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let preset = effect
+            .preset_id
+            .and_then(|id| state.presets.effect.iter().find(|p| p.id == id));
+        if let Some(preset) = preset {
+            let mut preset = preset.clone();
+            preset.effect = EffectPresetType::Standard(effect.effect);
+            preset.distribution = effect.distribution;
+            let _ = console_tx.send(ConsoleCommand::UpdateEffectPreset { preset });
+        } else {
+            let _ = console_tx.send(ConsoleCommand::UpdateCueEffect {
+                list_index,
+                cue_index,
+                effect_index,
+                effect,
+            });
+        }
+    }
+
+    fn render_effect_presets(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        cue_list_idx: usize,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let Some(cue_idx) = self.selected_cue_index else {
+            return;
+        };
+
+        ui.heading("Effect Presets");
+
+        if state.presets.effect.is_empty() {
+            ui.label("No effect presets. Use \"Save as Preset\" on a cue effect to create one.");
+            return;
+        }
+
+        let has_selection = !state.selected_fixtures.is_empty();
+        egui::Grid::new("effect_presets_table")
+            .num_columns(5)
+            .spacing([20.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Name");
+                ui.label("Waveform");
+                ui.label("Range");
+                ui.label("Subdivision");
+                ui.label("Actions");
+                ui.end_row();
+
+                for preset in &state.presets.effect {
+                    let EffectPresetType::Standard(effect) = &preset.effect else {
+                        continue;
+                    };
+                    let mut edited = effect.clone();
+
+                    ui.label(&preset.name);
+
+                    egui::ComboBox::from_id_salt(("effect_preset_waveform", preset.id))
+                        .selected_text(edited.effect_type.as_str())
+                        .show_ui(ui, |ui| {
+                            for effect_type in [
+                                EffectType::Sine,
+                                EffectType::Square,
+                                EffectType::Sawtooth,
+                                EffectType::Triangle,
+                                EffectType::Chase,
+                            ] {
+                                ui.selectable_value(
+                                    &mut edited.effect_type,
+                                    effect_type,
+                                    effect_type.as_str(),
+                                );
+                            }
+                        });
+
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut edited.min).prefix("min "));
+                        ui.add(egui::DragValue::new(&mut edited.max).prefix("max "));
+                    });
+
+                    edited.params.subdivision = Self::render_subdivision_picker(
+                        ui,
+                        ("effect_preset_subdivision", preset.id),
+                        &edited.params,
+                    );
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(has_selection, egui::Button::new("Apply to Selection"))
+                            .on_hover_text("Add this preset to the selected cue")
+                            .clicked()
+                        {
+                            let _ = console_tx.send(ConsoleCommand::ApplyEffectPreset {
+                                preset_id: preset.id,
+                                list_index: cue_list_idx,
+                                cue_index: cue_idx,
+                                fixture_ids: state.selected_fixtures.clone(),
+                            });
+                        }
+                        if ui.button("🗑").clicked() {
+                            let _ = console_tx.send(ConsoleCommand::DeleteEffectPreset {
+                                preset_id: preset.id,
+                            });
+                        }
+                    });
+                    ui.end_row();
+
+                    let changed = edited.effect_type != effect.effect_type
+                        || edited.min != effect.min
+                        || edited.max != effect.max
+                        || edited.params.subdivision != effect.params.subdivision;
+                    if changed {
+                        let mut preset = preset.clone();
+                        preset.effect = EffectPresetType::Standard(edited);
+                        let _ = console_tx.send(ConsoleCommand::UpdateEffectPreset { preset });
+                    }
+                }
+            });
+    }
+
+    /// Renders the step grid for a chase, returning the edited chase if anything changed
+    fn render_chase_steps(
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        mapping: &EffectMapping,
+        chase: &Chase,
+        effect_idx: usize,
+    ) -> Option<Chase> {
+        let mut edited = chase.clone();
+
+        ui.horizontal(|ui| {
+            ui.label("Direction:");
+            for direction in EffectDirection::all() {
+                ui.radio_value(&mut edited.direction, direction, direction.as_str());
+            }
+            ui.label("Crossfade:");
+            ui.add(egui::Slider::new(&mut edited.crossfade, 0.0..=1.0));
+        });
+
+        egui::Grid::new(("chase_steps", effect_idx))
+            .num_columns(mapping.fixture_ids.len() + 2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Step");
+                for fixture_id in &mapping.fixture_ids {
+                    let name = state
+                        .fixtures
+                        .values()
+                        .find(|f| f.id == *fixture_id)
+                        .map(|f| f.name.clone())
+                        .unwrap_or_else(|| format!("#{fixture_id}"));
+                    ui.label(name);
+                }
+                ui.label("");
+                ui.end_row();
+
+                let mut step_to_remove = None;
+                for (step_idx, step) in edited.steps.iter_mut().enumerate() {
+                    ui.label(format!("{}", step_idx + 1));
+                    for fixture_id in &mapping.fixture_ids {
+                        let mut on = step.fixture_ids.contains(fixture_id);
+                        if ui.checkbox(&mut on, "").changed() {
+                            if on {
+                                step.fixture_ids.push(*fixture_id);
+                            } else {
+                                step.fixture_ids.retain(|id| id != fixture_id);
+                            }
+                        }
+                    }
+                    if ui.small_button("🗑").clicked() {
+                        step_to_remove = Some(step_idx);
+                    }
+                    ui.end_row();
+                }
+
+                if let Some(step_idx) = step_to_remove {
+                    edited.steps.remove(step_idx);
+                }
+            });
+
+        if ui.button("Add Step").clicked() {
+            edited.steps.push(ChaseStep::default());
+        }
+
+        (edited != *chase).then_some(edited)
+    }
 }
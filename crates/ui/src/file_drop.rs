@@ -0,0 +1,88 @@
+use eframe::egui::{self, Align2, Color32, FontId, Rect, Stroke};
+use halo_core::ConsoleCommand;
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+use crate::unsaved::{ShowAction, UnsavedChanges};
+
+const AUDIO_EXTENSIONS: [&str; 7] = ["wav", "mp3", "flac", "ogg", "aiff", "aif", "m4a"];
+
+fn drop_target_id() -> egui::Id {
+    egui::Id::new("audio_drop_target")
+}
+
+fn is_audio(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Let audio files dropped over `rect` become the audio track of a cue list
+///
+/// Call this while rendering the cue list, the drop itself is handled by `handle_dropped_files`.
+pub fn audio_drop_target(ui: &egui::Ui, rect: Rect, list_index: usize) {
+    let dragging = ui
+        .ctx()
+        .input(|i| !i.raw.hovered_files.is_empty() || !i.raw.dropped_files.is_empty());
+    if dragging && ui.rect_contains_pointer(rect) {
+        ui.ctx()
+            .data_mut(|data| data.insert_temp(drop_target_id(), list_index));
+        ui.painter().rect_stroke(
+            rect,
+            4.0,
+            Stroke::new(2.0, ui.visuals().selection.bg_fill),
+            egui::StrokeKind::Inside,
+        );
+    }
+}
+
+/// Load dropped show files, and set dropped audio files as a cue list's track
+///
+/// Audio goes to the cue list it was dropped on, or the current cue list otherwise. Call this
+/// after the rest of the UI has been rendered so the drop targets have been registered.
+pub fn handle_dropped_files(
+    ctx: &egui::Context,
+    state: &ConsoleState,
+    unsaved_changes: &mut UnsavedChanges,
+    console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+) {
+    let target = ctx.data_mut(|data| data.remove_temp::<usize>(drop_target_id()));
+    let (hovering, dropped) =
+        ctx.input(|i| (!i.raw.hovered_files.is_empty(), i.raw.dropped_files.clone()));
+
+    if hovering {
+        let screen = ctx.content_rect();
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("file_drop_overlay"),
+        ));
+        painter.rect_filled(screen, 0.0, Color32::from_black_alpha(80));
+        painter.text(
+            screen.center(),
+            Align2::CENTER_CENTER,
+            "Drop a show file to open it, or an audio file on a cue list",
+            FontId::proportional(20.0),
+            Color32::WHITE,
+        );
+    }
+
+    for file in dropped {
+        let Some(path) = file.path else {
+            continue;
+        };
+
+        if path.extension().is_some_and(|ext| ext == "json") {
+            unsaved_changes.request(ShowAction::Load { path }, ctx, state, console_tx);
+        } else if is_audio(&path) {
+            let list_index = target.unwrap_or(state.current_cue_list_index);
+            if list_index < state.cue_lists.len() {
+                let _ = console_tx.send(ConsoleCommand::SetCueListAudioFile {
+                    list_index,
+                    audio_file: Some(path.to_string_lossy().to_string()),
+                });
+            }
+        } else {
+            log::warn!("Ignoring dropped file {}", path.display());
+        }
+    }
+}
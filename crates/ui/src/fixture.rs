@@ -1,8 +1,9 @@
 use eframe::egui::{self, Color32, CornerRadius, Rect, Stroke, Vec2};
-use halo_core::ConsoleCommand;
-use halo_fixtures::FixtureType;
+use halo_core::{ConsoleCommand, FixtureGridLayout, FixtureGridSection, TileSize};
+use halo_fixtures::{Fixture, FixtureType};
 use tokio::sync::mpsc;
 
+use crate::pixel_strip;
 use crate::state::ConsoleState;
 
 const FIXTURE_TYPE_COLORS: [(FixtureType, Color32); 7] = [
@@ -48,152 +49,293 @@ pub fn render(
     });
 }
 
+/// Dashboard fixture grid editing state
+#[derive(Default)]
+pub struct FixtureGridState {
+    editing: bool,
+    new_section_name: String,
+}
+
 pub fn render_grid(
     ui: &mut eframe::egui::Ui,
     state: &ConsoleState,
+    grid_state: &mut FixtureGridState,
     console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
     height: f32,
 ) {
-    let text_color = Color32::from_rgb(255, 255, 255);
-    let fixture_bg = Color32::from_rgb(30, 30, 30);
-    let highlight_color = Color32::from_rgb(59, 130, 246);
+    // Edits are made on a copy and sent to the console, which saves them with the show
+    let mut layout = state.fixture_grid.clone();
 
     // Create a scrollable area for fixtures
     egui::ScrollArea::vertical()
         .max_height(height)
         .show(ui, |ui| {
             ui.add_space(8.0);
-            ui.heading("FIXTURES");
+            ui.horizontal(|ui| {
+                ui.heading("FIXTURES");
+                ui.toggle_value(&mut grid_state.editing, "Edit Layout");
+                if grid_state.editing {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut grid_state.new_section_name)
+                            .hint_text("Section name")
+                            .desired_width(140.0),
+                    );
+                    let name = grid_state.new_section_name.trim().to_string();
+                    if ui
+                        .add_enabled(!name.is_empty(), egui::Button::new("Add Section"))
+                        .on_hover_text("New section holding the selected fixtures")
+                        .clicked()
+                    {
+                        layout.move_to_section(&state.selected_fixtures, None);
+                        layout.sections.push(FixtureGridSection {
+                            name,
+                            fixture_ids: state.selected_fixtures.clone(),
+                        });
+                        grid_state.new_section_name.clear();
+                    }
+                }
+            });
             ui.add_space(4.0);
 
-            // Determine grid layout based on available width
-            let available_width = ui.available_width();
-            let fixture_width = 100.0;
-            let spacing = 10.0;
-            let columns =
-                ((available_width + spacing) / (fixture_width + spacing)).floor() as usize;
-            let columns = columns.max(1); // At least 1 column
+            // Patch order for anything not placed in a section
+            let mut patched_ids: Vec<usize> = state.fixtures.values().map(|f| f.id).collect();
+            patched_ids.sort();
 
-            // Convert to vector and sort by fixture ID for consistent ordering
-            let mut fixtures: Vec<_> = state.fixtures.iter().collect();
-            fixtures.sort_by_key(|(_, f)| f.id);
+            let arranged = state.fixture_grid.arrange(&patched_ids);
+            let has_sections = !state.fixture_grid.sections.is_empty();
+            for (section_index, (name, fixture_ids)) in arranged.into_iter().enumerate() {
+                let section = name.map(|_| section_index);
+                if has_sections {
+                    ui.add_space(6.0);
+                    ui.horizontal(|ui| {
+                        ui.strong(name.unwrap_or("Unsorted"));
+                        if grid_state.editing {
+                            render_section_controls(ui, &mut layout, section, state);
+                        }
+                    });
+                }
 
-            // Create a grid layout for fixtures
-            egui::Grid::new("fixtures_grid")
-                .num_columns(columns)
-                .spacing([spacing, spacing])
-                .show(ui, |ui| {
-                    for (i, (fixture_id, fixture)) in fixtures.iter().enumerate() {
-                        // Create a fixture button
-                        let fixture_height = if fixture.profile.fixture_type == FixtureType::LEDBar
-                            || fixture.profile.fixture_type == FixtureType::PixelBar
-                        {
-                            70.0
-                        } else {
-                            80.0
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing = Vec2::splat(10.0);
+                    for fixture_id in fixture_ids {
+                        let Some(fixture) = state.fixtures.values().find(|f| f.id == fixture_id)
+                        else {
+                            continue;
                         };
+                        let response = render_tile(
+                            ui,
+                            fixture,
+                            state.fixture_grid.tile_size(fixture_id),
+                            state,
+                            console_tx,
+                        );
+                        response.context_menu(|ui| {
+                            render_tile_menu(ui, &mut layout, fixture_id, section.is_some());
+                        });
+                    }
+                });
+            }
+        });
 
-                        // Draw fixture background
-                        let rect = ui
-                            .allocate_space(Vec2::new(fixture_width, fixture_height))
-                            .1;
-
-                        // Check if fixture is selected
-                        let is_selected = state.selected_fixtures.contains(&fixture.id);
-                        let border_color = if is_selected {
-                            highlight_color
-                        } else {
-                            Color32::from_gray(70)
-                        };
-                        let border_width = if is_selected { 2.0 } else { 1.0 };
+    if layout != state.fixture_grid {
+        let _ = console_tx.send(ConsoleCommand::SetFixtureGridLayout { layout });
+    }
+}
 
-                        // Draw fixture box
-                        ui.painter()
-                            .rect_filled(rect, CornerRadius::same(4), fixture_bg);
+fn render_section_controls(
+    ui: &mut egui::Ui,
+    layout: &mut FixtureGridLayout,
+    section: Option<usize>,
+    state: &ConsoleState,
+) {
+    if ui
+        .small_button("+ Selected")
+        .on_hover_text("Move the selected fixtures into this section")
+        .clicked()
+    {
+        layout.move_to_section(&state.selected_fixtures, section);
+    }
+    let Some(index) = section else {
+        return;
+    };
+    if ui.small_button("▲").clicked() && index > 0 {
+        layout.sections.swap(index, index - 1);
+    }
+    if ui.small_button("▼").clicked() && index + 1 < layout.sections.len() {
+        layout.sections.swap(index, index + 1);
+    }
+    if ui
+        .small_button("✕")
+        .on_hover_text("Remove the section, its fixtures go back to Unsorted")
+        .clicked()
+    {
+        layout.sections.remove(index);
+    }
+}
 
-                        ui.painter().rect_stroke(
-                            rect,
-                            CornerRadius::same(4),
-                            Stroke::new(border_width, border_color),
-                            egui::StrokeKind::Outside,
-                        );
+fn render_tile_menu(
+    ui: &mut egui::Ui,
+    layout: &mut FixtureGridLayout,
+    fixture_id: usize,
+    in_section: bool,
+) {
+    ui.label("Tile size");
+    let mut size = layout.tile_size(fixture_id);
+    for option in TileSize::all() {
+        if ui.radio_value(&mut size, option, option.as_str()).clicked() {
+            layout.set_tile_size(fixture_id, size);
+            ui.close();
+        }
+    }
 
-                        // Handle clicks for fixture selection
-                        let response = ui.interact(rect, ui.id().with(i), egui::Sense::click());
-                        if response.clicked() {
-                            let fixture_id = fixture.id;
-                            let is_selected = state.selected_fixtures.contains(&fixture_id);
-
-                            if is_selected {
-                                // Remove from selection
-                                let _ = console_tx
-                                    .send(ConsoleCommand::RemoveSelectedFixture { fixture_id });
-                            } else {
-                                // Add to selection
-                                let _ = console_tx
-                                    .send(ConsoleCommand::AddSelectedFixture { fixture_id });
-                            }
-                        }
+    ui.separator();
+    if in_section {
+        if ui.button("Move Earlier").clicked() {
+            layout.shift_fixture(fixture_id, true);
+        }
+        if ui.button("Move Later").clicked() {
+            layout.shift_fixture(fixture_id, false);
+        }
+    }
+    ui.menu_button("Move to Section", |ui| {
+        let names: Vec<String> = layout.sections.iter().map(|s| s.name.clone()).collect();
+        for (index, name) in names.iter().enumerate() {
+            if ui.button(name).clicked() {
+                layout.move_to_section(&[fixture_id], Some(index));
+                ui.close();
+            }
+        }
+        if ui.button("Unsorted").clicked() {
+            layout.move_to_section(&[fixture_id], None);
+            ui.close();
+        }
+    });
+}
 
-                        // Draw color strip at the top of the fixture box
-                        let color_strip_height = 6.0;
-                        let color_strip_rect = Rect::from_min_size(
-                            rect.min,
-                            Vec2::new(rect.width(), color_strip_height),
-                        );
-                        ui.painter().rect_filled(
-                            color_strip_rect,
-                            CornerRadius::same(4).at_least(4),
-                            get_fixture_type_color(&fixture.profile.fixture_type),
-                        );
+fn render_tile(
+    ui: &mut egui::Ui,
+    fixture: &Fixture,
+    size: TileSize,
+    state: &ConsoleState,
+    console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+) -> egui::Response {
+    let text_color = Color32::from_rgb(255, 255, 255);
+    let fixture_bg = Color32::from_rgb(30, 30, 30);
+    let highlight_color = Color32::from_rgb(59, 130, 246);
 
-                        // Draw fixture name (centered)
-                        ui.painter().text(
-                            rect.center(),
-                            egui::Align2::CENTER_CENTER,
-                            &fixture.name,
-                            egui::FontId::proportional(14.0),
-                            text_color,
-                        );
+    let is_bar = fixture.profile.fixture_type == FixtureType::LEDBar
+        || fixture.profile.fixture_type == FixtureType::PixelBar;
+    let (tile_size, name_size) = match size {
+        TileSize::Small => (Vec2::new(70.0, 50.0), 11.0),
+        TileSize::Normal => (Vec2::new(100.0, if is_bar { 70.0 } else { 80.0 }), 14.0),
+        TileSize::Large => (Vec2::new(150.0, 110.0), 18.0),
+    };
 
-                        // Add intensity percentage in bottom right corner
-                        let intensity_value = if let Some(channel) =
-                            fixture.channels.iter().find(|c| {
-                                c.name.to_lowercase().contains("dimmer")
-                                    || c.name.to_lowercase().contains("intensity")
-                            }) {
-                            channel.value
-                        } else {
-                            0 // Default if no dimmer/intensity channel found
-                        };
+    // Draw fixture background and handle clicks for fixture selection
+    let (rect, response) = ui.allocate_exact_size(tile_size, egui::Sense::click());
 
-                        // Format as percentage
-                        let intensity_text = format!(
-                            "{}%",
-                            (intensity_value as f32 / 255.0 * 100.0).round() as u8
-                        );
+    // Check if fixture is selected
+    let is_selected = state.selected_fixtures.contains(&fixture.id);
+    let border_color = if is_selected {
+        highlight_color
+    } else {
+        Color32::from_gray(70)
+    };
+    let border_width = if is_selected { 2.0 } else { 1.0 };
 
-                        // Position in bottom right with some padding
-                        let text_pos = rect.right_bottom() - Vec2::new(8.0, 8.0);
-                        ui.painter().text(
-                            text_pos,
-                            egui::Align2::RIGHT_BOTTOM,
-                            &intensity_text,
-                            egui::FontId::proportional(11.0),
-                            if intensity_value > 0 {
-                                highlight_color.linear_multiply(0.9)
-                            } else {
-                                Color32::from_gray(130) // Dimmed when intensity is 0
-                            },
-                        );
+    // Draw fixture box
+    ui.painter()
+        .rect_filled(rect, CornerRadius::same(4), fixture_bg);
 
-                        // New row after each column
-                        if (i + 1) % columns == 0 && i < state.fixtures.len() - 1 {
-                            ui.end_row();
-                        }
-                    }
-                });
-        });
+    ui.painter().rect_stroke(
+        rect,
+        CornerRadius::same(4),
+        Stroke::new(border_width, border_color),
+        egui::StrokeKind::Outside,
+    );
+
+    if response.clicked() {
+        let fixture_id = fixture.id;
+        if is_selected {
+            // Remove from selection
+            let _ = console_tx.send(ConsoleCommand::RemoveSelectedFixture { fixture_id });
+        } else {
+            // Add to selection
+            let _ = console_tx.send(ConsoleCommand::AddSelectedFixture { fixture_id });
+        }
+    }
+
+    // Draw color strip at the top of the fixture box, pixel bars show their live output there
+    let color_strip_height = 6.0;
+    let color_strip_rect =
+        Rect::from_min_size(rect.min, Vec2::new(rect.width(), color_strip_height));
+    match state.pixel_data.get(&fixture.id) {
+        Some(pixels) if fixture.profile.fixture_type == FixtureType::PixelBar => {
+            pixel_strip::paint(ui.painter(), color_strip_rect, pixels);
+        }
+        _ => {
+            ui.painter().rect_filled(
+                color_strip_rect,
+                CornerRadius::same(4).at_least(4),
+                get_fixture_type_color(&fixture.profile.fixture_type),
+            );
+        }
+    }
+
+    // Draw fixture name (centered)
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        &fixture.name,
+        egui::FontId::proportional(name_size),
+        text_color,
+    );
+
+    // Selection order, used for fans and effect distributions
+    if let Some(order) = state
+        .selected_fixtures
+        .iter()
+        .position(|id| *id == fixture.id)
+    {
+        ui.painter().text(
+            rect.left_bottom() + Vec2::new(8.0, -8.0),
+            egui::Align2::LEFT_BOTTOM,
+            format!("#{}", order + 1),
+            egui::FontId::proportional(11.0),
+            highlight_color,
+        );
+    }
+
+    // Add intensity percentage in bottom right corner
+    let intensity_value = if let Some(channel) = fixture.channels.iter().find(|c| {
+        c.name.to_lowercase().contains("dimmer") || c.name.to_lowercase().contains("intensity")
+    }) {
+        channel.value
+    } else {
+        0 // Default if no dimmer/intensity channel found
+    };
+
+    // Format as percentage
+    let intensity_text = format!(
+        "{}%",
+        (intensity_value as f32 / 255.0 * 100.0).round() as u8
+    );
+
+    // Position in bottom right with some padding
+    let text_pos = rect.right_bottom() - Vec2::new(8.0, 8.0);
+    ui.painter().text(
+        text_pos,
+        egui::Align2::RIGHT_BOTTOM,
+        &intensity_text,
+        egui::FontId::proportional(11.0),
+        if intensity_value > 0 {
+            highlight_color.linear_multiply(0.9)
+        } else {
+            Color32::from_gray(130) // Dimmed when intensity is 0
+        },
+    );
+
+    response
 }
 
 fn get_fixture_type_color(fixture_type: &FixtureType) -> Color32 {
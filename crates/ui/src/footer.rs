@@ -1,7 +1,9 @@
-use eframe::egui::{Align, CornerRadius, Direction, Layout, RichText};
+use eframe::egui::{self, Align, Color32, CornerRadius, Direction, Layout, RichText};
 use halo_core::ConsoleCommand;
 use tokio::sync::mpsc;
 
+use crate::log_viewer::LogViewer;
+use crate::network_status::NetworkStatusPanel;
 use crate::utils::theme::Theme;
 
 pub fn render(
@@ -9,8 +11,10 @@ pub fn render(
     _console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
     state: &crate::state::ConsoleState,
     fps: u32,
+    network_status_panel: &mut NetworkStatusPanel,
+    log_viewer: &mut LogViewer,
 ) {
-    let theme = Theme::default();
+    let theme = Theme::from_settings(&state.settings);
     let fixture_count = state.fixtures.len();
     let bpm = state.bpm;
     let rhythm_state = &state.rhythm_state;
@@ -51,6 +55,43 @@ pub fn render(
         ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
             ui.add_space(12.0);
             ui.label(RichText::new("Halo v0.4").size(12.0).color(theme.text_dim));
+            ui.add_space(12.0);
+
+            // Art-Net health at a glance, click for the full network status
+            let network = &state.network_status;
+            let (text, color) = if network.socket_errors > 0 {
+                (
+                    format!("Art-Net: {} errors", network.socket_errors),
+                    Color32::RED,
+                )
+            } else if network.discovery_error.is_some() {
+                ("Art-Net: discovery degraded".to_string(), Color32::YELLOW)
+            } else {
+                (
+                    format!("Art-Net: {} universes", network.universes.len()),
+                    theme.text_dim,
+                )
+            };
+            let indicator =
+                egui::Button::new(RichText::new(text).size(12.0).color(color)).frame(false);
+            if ui
+                .add(indicator)
+                .on_hover_text("Show network status")
+                .clicked()
+            {
+                network_status_panel.open = !network_status_panel.open;
+            }
+            ui.add_space(12.0);
+
+            let (text, color) = match log_viewer.unseen_errors() {
+                0 => ("Log".to_string(), theme.text_dim),
+                errors => (format!("Log: {errors} errors"), Color32::RED),
+            };
+            let log_button =
+                egui::Button::new(RichText::new(text).size(12.0).color(color)).frame(false);
+            if ui.add(log_button).on_hover_text("Show the log").clicked() {
+                log_viewer.open = !log_viewer.open;
+            }
         });
     });
 }
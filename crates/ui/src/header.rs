@@ -3,7 +3,8 @@ use halo_core::ConsoleCommand;
 use tokio::sync::mpsc;
 
 use crate::settings::SettingsPanel;
-use crate::ActiveTab;
+use crate::unsaved::{ShowAction, UnsavedChanges};
+use crate::{ActiveTab, DetachedPanel};
 
 pub fn render(
     ui: &mut eframe::egui::Ui,
@@ -11,6 +12,8 @@ pub fn render(
     console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
     state: &crate::state::ConsoleState,
     settings_panel: &mut SettingsPanel,
+    detached_panels: &mut Vec<DetachedPanel>,
+    unsaved_changes: &mut UnsavedChanges,
 ) {
     ui.menu_button("File", |ui| {
         if ui.button("New Show").clicked() {
@@ -20,7 +23,7 @@ pub fn render(
                     .unwrap_or_default()
                     .to_string_lossy()
                     .to_string();
-                let _ = console_tx.send(ConsoleCommand::NewShow { name });
+                unsaved_changes.request(ShowAction::New { name }, ui.ctx(), state, console_tx);
             }
             ui.close();
         }
@@ -31,13 +34,14 @@ pub fn render(
                 .set_title("Open Show")
                 .pick_file()
             {
-                let _ = console_tx.send(ConsoleCommand::LoadShow { path });
+                unsaved_changes.request(ShowAction::Load { path }, ui.ctx(), state, console_tx);
             }
             ui.close();
         }
 
         if ui.button("Reload Show").clicked() {
-            let _ = console_tx.send(ConsoleCommand::ReloadShow);
+            unsaved_changes.request(ShowAction::Reload, ui.ctx(), state, console_tx);
+            ui.close();
         }
 
         if ui.button("Save Show").clicked() {
@@ -78,14 +82,36 @@ pub fn render(
         ui.separator();
 
         if ui.button("Quit").clicked() {
-            // TODO - add are you sure? modal.
-            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+            unsaved_changes.request(ShowAction::Quit, ui.ctx(), state, console_tx);
         }
     });
     ui.menu_button("View", |ui| {
         if ui.button("Patch").clicked() {
             *active_tab = ActiveTab::PatchPanel;
         }
+
+        let mut overlay = state.settings.performance_overlay;
+        if ui.checkbox(&mut overlay, "Performance Overlay").changed() {
+            let mut settings = state.settings.clone();
+            settings.performance_overlay = overlay;
+            let _ = console_tx.send(ConsoleCommand::UpdateSettings { settings });
+        }
+
+        ui.separator();
+
+        // Pop panels out into their own windows, e.g. playback on a second monitor
+        ui.menu_button("Pop Out", |ui| {
+            for panel in DetachedPanel::all() {
+                let mut detached = detached_panels.contains(&panel);
+                if ui.checkbox(&mut detached, panel.label()).changed() {
+                    if detached {
+                        detached_panels.push(panel);
+                    } else {
+                        detached_panels.retain(|p| *p != panel);
+                    }
+                }
+            }
+        });
     });
     ui.menu_button("Tools", |ui| {
         if ui
@@ -109,6 +135,33 @@ pub fn render(
             // TODO: Open DMX settings
         }
     });
+
+    ui.separator();
+
+    // Programmer undo/redo, the tooltips name the edit that would be reverted or reapplied
+    let undo = ui.add_enabled(state.programmer_next_undo.is_some(), egui::Button::new("↶"));
+    let undo = match &state.programmer_next_undo {
+        Some(action) => undo.on_hover_text(format!("Undo {action}")),
+        None => undo.on_disabled_hover_text("Nothing to undo"),
+    };
+    if undo.clicked() {
+        let _ = console_tx.send(ConsoleCommand::UndoProgrammer);
+    }
+
+    let redo = ui.add_enabled(state.programmer_next_redo.is_some(), egui::Button::new("↷"));
+    let redo = match &state.programmer_next_redo {
+        Some(action) => redo.on_hover_text(format!("Redo {action}")),
+        None => redo.on_disabled_hover_text("Nothing to redo"),
+    };
+    if redo.clicked() {
+        let _ = console_tx.send(ConsoleCommand::RedoProgrammer);
+    }
+
+    if state.show_dirty {
+        ui.separator();
+        ui.label(egui::RichText::new("● Unsaved").color(egui::Color32::from_rgb(255, 165, 0)))
+            .on_hover_text("The show has changes that haven't been saved");
+    }
     // Tab selector
     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
         if ui
@@ -123,6 +176,24 @@ pub fn render(
         {
             *active_tab = ActiveTab::PatchPanel;
         }
+        if ui
+            .selectable_label(matches!(active_tab, ActiveTab::MagicSheet), "Magic Sheet")
+            .clicked()
+        {
+            *active_tab = ActiveTab::MagicSheet;
+        }
+        if ui
+            .selectable_label(matches!(active_tab, ActiveTab::PresetPool), "Presets")
+            .clicked()
+        {
+            *active_tab = ActiveTab::PresetPool;
+        }
+        if ui
+            .selectable_label(matches!(active_tab, ActiveTab::Previs), "Visualizer")
+            .clicked()
+        {
+            *active_tab = ActiveTab::Previs;
+        }
         if ui
             .selectable_label(matches!(active_tab, ActiveTab::CueEditor), "Cue Editor")
             .clicked()
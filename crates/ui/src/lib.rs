@@ -1,37 +1,109 @@
 use std::time::{Duration, Instant, SystemTime};
 
 use eframe::egui;
-use halo_core::{ConfigManager, ConsoleCommand, ConsoleEvent};
+use halo_core::{ConfigManager, ConsoleCommand, ConsoleEvent, DashboardWidget, UiTheme};
+pub use log_viewer::{init_logging, LogReceiver, LogRecord};
 use tokio::sync::mpsc;
 
 use crate::state::ConsoleState;
+use crate::utils::theme::Theme;
 mod footer;
 mod header;
 mod settings;
+mod shortcuts;
 mod state;
 mod utils;
 
 // Enable all UI modules
+mod color_picker;
+mod command_line;
 mod cue;
 mod cue_editor;
 mod fader;
+mod file_drop;
 mod fixture;
+mod log_viewer;
+mod magic_sheet;
 mod master;
+mod network_status;
+mod next_cue;
 mod patch_panel;
+mod performance;
+mod pixel_strip;
+mod preset_pool;
+mod previs;
 mod programmer;
+mod search;
 mod session;
 mod show_panel;
 mod timeline;
+mod touch;
+mod unsaved;
 mod visualizer;
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ActiveTab {
     Dashboard,
     Programmer,
     CueEditor,
     PatchPanel,
+    PresetPool,
+    Previs,
+    MagicSheet,
     ShowManager,
 }
 
+/// A panel that can be popped out into its own native window
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DetachedPanel {
+    CueList,
+    Programmer,
+    CueEditor,
+    PresetPool,
+    Previs,
+    PatchPanel,
+    MagicSheet,
+}
+
+impl DetachedPanel {
+    pub fn all() -> [DetachedPanel; 7] {
+        [
+            DetachedPanel::CueList,
+            DetachedPanel::Programmer,
+            DetachedPanel::CueEditor,
+            DetachedPanel::PresetPool,
+            DetachedPanel::Previs,
+            DetachedPanel::PatchPanel,
+            DetachedPanel::MagicSheet,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DetachedPanel::CueList => "Cue List",
+            DetachedPanel::Programmer => "Programmer",
+            DetachedPanel::CueEditor => "Cue Editor",
+            DetachedPanel::PresetPool => "Presets",
+            DetachedPanel::Previs => "Visualizer",
+            DetachedPanel::PatchPanel => "Patch",
+            DetachedPanel::MagicSheet => "Magic Sheet",
+        }
+    }
+
+    /// The tab this panel replaces in the main window, the cue list lives on the dashboard
+    fn tab(&self) -> Option<ActiveTab> {
+        match self {
+            DetachedPanel::CueList => None,
+            DetachedPanel::Programmer => Some(ActiveTab::Programmer),
+            DetachedPanel::CueEditor => Some(ActiveTab::CueEditor),
+            DetachedPanel::PresetPool => Some(ActiveTab::PresetPool),
+            DetachedPanel::Previs => Some(ActiveTab::Previs),
+            DetachedPanel::PatchPanel => Some(ActiveTab::PatchPanel),
+            DetachedPanel::MagicSheet => Some(ActiveTab::MagicSheet),
+        }
+    }
+}
+
 pub struct HaloApp {
     state: ConsoleState,
 
@@ -51,16 +123,31 @@ pub struct HaloApp {
 
     // Configuration manager
     config_manager: ConfigManager,
+    key_bindings: shortcuts::KeyBindings,
+    // Theme, accent and touch layout the egui style was last built from
+    applied_style: Option<(UiTheme, [u8; 3], bool)>,
 
     // Component state - maintain state between renders
     programmer_state: programmer::ProgrammerState,
     cue_editor_state: cue_editor::CueEditor,
     patch_panel_state: patch_panel::PatchPanelState,
+    preset_pool_state: preset_pool::PresetPoolPanel,
+    previs_state: previs::PrevisPanel,
+    magic_sheet_state: magic_sheet::MagicSheetPanel,
     show_panel_state: show_panel::ShowPanelState,
     session_panel_state: session::SessionPanel,
     cue_panel_state: cue::CuePanel,
     settings_panel: settings::SettingsPanel,
+    network_status_panel: network_status::NetworkStatusPanel,
+    log_viewer: log_viewer::LogViewer,
+    search_palette: search::SearchPalette,
+    performance_overlay: performance::PerformanceOverlay,
     timeline_state: timeline::TimelineState,
+    command_line: command_line::CommandLine,
+    // Panels popped out into their own windows
+    detached_panels: Vec<DetachedPanel>,
+    unsaved_changes: unsaved::UnsavedChanges,
+    fixture_grid_state: fixture::FixtureGridState,
 }
 
 impl HaloApp {
@@ -70,6 +157,7 @@ impl HaloApp {
         console_rx: std::sync::mpsc::Receiver<ConsoleEvent>,
         show_file_path: Option<std::path::PathBuf>,
         config_manager: ConfigManager,
+        log_rx: LogReceiver,
     ) -> Self {
         // Request initial data from console
         let _ = console_tx.send(ConsoleCommand::QueryFixtures);
@@ -81,8 +169,14 @@ impl HaloApp {
         let _ = console_tx.send(ConsoleCommand::QueryShow);
         let _ = console_tx.send(ConsoleCommand::QueryLinkState);
 
+        // Start from the saved settings so the theme is right on the first frame
+        let state = ConsoleState {
+            settings: config_manager.settings().clone(),
+            ..ConsoleState::default()
+        };
+
         Self {
-            state: ConsoleState::default(),
+            state,
             console_tx,
             console_rx,
             last_update: Instant::now(),
@@ -92,22 +186,47 @@ impl HaloApp {
             fps: 60,
             initial_show_loaded: false,
             show_file_path,
+            key_bindings: shortcuts::KeyBindings::from_settings(
+                &config_manager.settings().key_bindings,
+            ),
+            applied_style: None,
             config_manager,
             programmer_state: programmer::ProgrammerState::default(),
             cue_editor_state: cue_editor::CueEditor::new(),
             patch_panel_state: patch_panel::PatchPanelState::default(),
+            preset_pool_state: preset_pool::PresetPoolPanel::default(),
+            previs_state: previs::PrevisPanel::default(),
+            magic_sheet_state: magic_sheet::MagicSheetPanel::default(),
             show_panel_state: show_panel::ShowPanelState::default(),
             session_panel_state: session::SessionPanel::default(),
             cue_panel_state: cue::CuePanel::default(),
             settings_panel: settings::SettingsPanel::new(),
+            network_status_panel: network_status::NetworkStatusPanel::default(),
+            log_viewer: log_viewer::LogViewer::new(log_rx),
+            search_palette: search::SearchPalette::default(),
+            performance_overlay: performance::PerformanceOverlay::default(),
             timeline_state: timeline::TimelineState::default(),
+            command_line: command_line::CommandLine::default(),
+            detached_panels: Vec::new(),
+            unsaved_changes: unsaved::UnsavedChanges::default(),
+            fixture_grid_state: fixture::FixtureGridState::default(),
         }
     }
 
-    fn process_engine_updates(&mut self) {
+    /// Apply queued console events, returning how many there were
+    fn process_engine_updates(&mut self) -> usize {
+        let mut count = 0;
         while let Ok(event) = self.console_rx.try_recv() {
+            count += 1;
+            if let ConsoleEvent::SettingsUpdated { settings } = &event {
+                self.key_bindings = shortcuts::KeyBindings::from_settings(&settings.key_bindings);
+                if let Err(e) = self.config_manager.update_settings(settings.clone()) {
+                    log::warn!("Failed to save settings: {e}");
+                }
+            }
             self.state.update(event);
         }
+        count
     }
 
     fn render_error_dialog(&mut self, ctx: &egui::Context) {
@@ -149,6 +268,8 @@ impl HaloApp {
                     &self.console_tx,
                     &self.state,
                     &mut self.settings_panel,
+                    &mut self.detached_panels,
+                    &mut self.unsaved_changes,
                 );
             });
         });
@@ -173,44 +294,188 @@ impl HaloApp {
             timeline::render(ui, &self.state, &mut self.timeline_state, &self.console_tx);
             ui.separator();
 
+            // Command line
+            self.command_line.render(ui, &self.state, &self.console_tx);
+            ui.separator();
+
             // Show footer status
-            footer::render(ui, &self.console_tx, &self.state, self.fps);
+            footer::render(
+                ui,
+                &self.console_tx,
+                &self.state,
+                self.fps,
+                &mut self.network_status_panel,
+                &mut self.log_viewer,
+            );
         });
 
+        if self.state.settings.touch_layout {
+            egui::TopBottomPanel::bottom("touch_go_bar")
+                .exact_height(touch::GO_BAR_HEIGHT)
+                .show(ctx, |ui| {
+                    touch::render_go_bar(ui, &self.state, &self.console_tx);
+                });
+        }
+
         match self.active_tab {
             ActiveTab::Dashboard => {
-                egui::SidePanel::right("right_panel")
-                    .frame(egui::Frame::default().fill(egui::Color32::from_gray(20)))
-                    .show(ctx, |ui| {
-                        ui.set_min_width(400.0);
-
-                        self.session_panel_state
-                            .render(ui, &self.state, &self.console_tx);
-                        ui.separator();
-
-                        // Update cue panel state and render with auto-scroll
-                        self.cue_panel_state
-                            .set_playback_state(self.state.playback_state);
-                        self.cue_panel_state
-                            .render(ui, &self.state, &self.console_tx);
-                    });
+                let layout = self.state.settings.dashboard_layout.clone();
+                if !layout.side.is_empty() {
+                    egui::SidePanel::right("right_panel")
+                        .frame(
+                            egui::Frame::default()
+                                .fill(Theme::from_settings(&self.state.settings).panel_bg),
+                        )
+                        .show(ctx, |ui| {
+                            ui.set_min_width(400.0);
+
+                            for (i, widget) in layout.side.iter().enumerate() {
+                                if i > 0 {
+                                    ui.separator();
+                                }
+                                self.render_dashboard_widget(ui, *widget);
+                            }
+                        });
+                }
 
                 egui::CentralPanel::default().show(ctx, |ui| {
-                    ui.horizontal(|ui| {
-                        // Master Panel with the visualizer, overrides and master faders
-                        master::render(ui, &self.state, &self.console_tx);
-                    });
+                    // Compact widgets next to each other share a row, like the visualizer and
+                    // masters in the default layout
+                    let mut widgets = layout.main.iter().peekable();
+                    while let Some(widget) = widgets.next() {
+                        let compact = |w: &DashboardWidget| {
+                            matches!(w, DashboardWidget::Masters | DashboardWidget::PixelPreview)
+                        };
+                        match widgets.next_if(|next| compact(widget) && compact(next)) {
+                            Some(next) => {
+                                ui.horizontal(|ui| {
+                                    self.render_dashboard_widget(ui, *widget);
+                                    self.render_dashboard_widget(ui, *next);
+                                });
+                            }
+                            None => self.render_dashboard_widget(ui, *widget),
+                        }
+                    }
+                });
+            }
+            tab => {
+                let detached = DetachedPanel::all()
+                    .into_iter()
+                    .find(|panel| panel.tab() == Some(tab) && self.detached_panels.contains(panel));
+                match detached {
+                    Some(panel) => {
+                        egui::CentralPanel::default().show(ctx, |ui| {
+                            ui.vertical_centered(|ui| {
+                                ui.add_space(40.0);
+                                ui.label(format!("{} is open in a separate window", panel.label()));
+                                if ui.button("Bring Back").clicked() {
+                                    self.detached_panels.retain(|p| *p != panel);
+                                }
+                            });
+                        });
+                    }
+                    None => self.render_tab(ctx, tab),
+                }
+            }
+        }
+
+        self.render_detached_panels(ctx);
+
+        // Render settings panel (modal window)
+        self.settings_panel
+            .render(ctx, &self.state, &self.console_tx);
+
+        self.network_status_panel
+            .render(ctx, &self.state, &self.console_tx);
+        self.log_viewer.render(ctx);
+
+        if let Some(target) = self.search_palette.render(ctx, &self.state) {
+            self.jump_to(target);
+        }
+
+        file_drop::handle_dropped_files(
+            ctx,
+            &self.state,
+            &mut self.unsaved_changes,
+            &self.console_tx,
+        );
+
+        self.unsaved_changes
+            .render(ctx, &self.state, &self.console_tx);
+    }
 
-                    // Fixtures Grid
-                    let main_content_height = ui.available_height();
-                    fixture::render_grid(
-                        ui,
-                        &self.state,
-                        &self.console_tx,
-                        main_content_height - 60.0,
-                    );
+    fn render_dashboard_widget(&mut self, ui: &mut egui::Ui, widget: DashboardWidget) {
+        match widget {
+            DashboardWidget::Session => {
+                self.session_panel_state
+                    .render(ui, &self.state, &self.console_tx);
+            }
+            DashboardWidget::NextCue => next_cue::render(ui, &self.state),
+            DashboardWidget::CueList => {
+                if self.detached_panels.contains(&DetachedPanel::CueList) {
+                    ui.label("Cue list is open in a separate window");
+                } else {
+                    self.render_cue_list(ui);
+                }
+            }
+            DashboardWidget::Masters => {
+                // Overrides and master faders
+                master::render(ui, &self.state, &self.console_tx);
+            }
+            DashboardWidget::PixelPreview => {
+                ui.vertical(|ui| {
+                    ui.heading("VISUALIZER");
+                    ui.add_space(5.0);
+                    visualizer::render(ui, &self.state, &self.console_tx);
                 });
             }
+            DashboardWidget::FixtureGrid => {
+                let main_content_height = ui.available_height();
+                fixture::render_grid(
+                    ui,
+                    &self.state,
+                    &mut self.fixture_grid_state,
+                    &self.console_tx,
+                    main_content_height - 60.0,
+                );
+            }
+        }
+    }
+
+    /// Select what was picked in the search palette and show the tab it lives on
+    fn jump_to(&mut self, target: search::SearchTarget) {
+        match target {
+            search::SearchTarget::Fixture(fixture_id) => {
+                let _ = self.console_tx.send(ConsoleCommand::SetSelectedFixtures {
+                    fixture_ids: vec![fixture_id],
+                });
+                self.active_tab = ActiveTab::Programmer;
+            }
+            search::SearchTarget::Group(group_id) => {
+                let _ = self.console_tx.send(ConsoleCommand::SelectFixtureGroup {
+                    group_id,
+                    mode: halo_core::GroupSelectMode::Replace,
+                });
+                self.active_tab = ActiveTab::Programmer;
+            }
+            search::SearchTarget::CueList(list_index) => {
+                self.cue_editor_state.select(list_index, None);
+                self.active_tab = ActiveTab::CueEditor;
+            }
+            search::SearchTarget::Cue {
+                list_index,
+                cue_index,
+            } => {
+                self.cue_editor_state.select(list_index, Some(cue_index));
+                self.active_tab = ActiveTab::CueEditor;
+            }
+        }
+    }
+
+    fn render_tab(&mut self, ctx: &egui::Context, tab: ActiveTab) {
+        match tab {
+            // The dashboard is laid out around the main window and can't be popped out
+            ActiveTab::Dashboard => {}
             ActiveTab::CueEditor => {
                 self.cue_editor_state
                     .render(ctx, &self.state, &self.console_tx);
@@ -223,15 +488,71 @@ impl HaloApp {
                 self.patch_panel_state
                     .render(ctx, &self.state, &self.console_tx);
             }
+            ActiveTab::PresetPool => {
+                self.preset_pool_state
+                    .render(ctx, &self.state, &self.console_tx);
+            }
+            ActiveTab::Previs => {
+                self.previs_state.render(ctx, &self.state, &self.console_tx);
+            }
+            ActiveTab::MagicSheet => {
+                self.magic_sheet_state
+                    .render(ctx, &self.state, &self.console_tx);
+            }
             ActiveTab::ShowManager => {
                 self.show_panel_state
                     .render(ctx, &self.state, &self.console_tx);
             }
         }
+    }
 
-        // Render settings panel (modal window)
-        self.settings_panel
-            .render(ctx, &self.state, &self.console_tx);
+    fn render_cue_list(&mut self, ui: &mut egui::Ui) {
+        if self.state.settings.touch_layout {
+            touch::render_cue_list(ui, &self.state, &self.console_tx);
+            return;
+        }
+
+        // Update cue panel state and render with auto-scroll
+        self.cue_panel_state
+            .set_playback_state(self.state.playback_state);
+        self.cue_panel_state
+            .render(ui, &self.state, &self.console_tx);
+    }
+
+    /// Show each popped out panel in its own native window, e.g. for a second monitor
+    fn render_detached_panels(&mut self, ctx: &egui::Context) {
+        for panel in self.detached_panels.clone() {
+            let mut close = false;
+            ctx.show_viewport_immediate(
+                egui::ViewportId::from_hash_of(("detached_panel", panel.label())),
+                egui::ViewportBuilder::default()
+                    .with_title(format!("Halo - {}", panel.label()))
+                    .with_inner_size([1000.0, 700.0]),
+                |ctx, class| {
+                    // Without multi-window support there is nowhere to put the panel
+                    if class == egui::ViewportClass::Embedded {
+                        close = true;
+                        return;
+                    }
+
+                    match panel.tab() {
+                        Some(tab) => self.render_tab(ctx, tab),
+                        None => {
+                            egui::CentralPanel::default().show(ctx, |ui| {
+                                self.render_cue_list(ui);
+                            });
+                        }
+                    }
+
+                    if ctx.input(|i| i.viewport().close_requested()) {
+                        close = true;
+                    }
+                },
+            );
+            if close {
+                self.detached_panels.retain(|p| *p != panel);
+            }
+        }
     }
 }
 
@@ -253,7 +574,18 @@ impl eframe::App for HaloApp {
         }
 
         // Process all updates first
-        self.process_engine_updates();
+        let queued_events = self.process_engine_updates();
+        self.log_viewer.poll();
+        self.unsaved_changes.handle_close_request(ctx, &self.state);
+
+        // Rebuild the style when the appearance settings change
+        let settings = &self.state.settings;
+        let style_key = (settings.theme, settings.accent_color, settings.touch_layout);
+        if self.applied_style != Some(style_key) {
+            ctx.set_visuals(Theme::from_settings(settings).visuals());
+            touch::apply_style(ctx, settings.touch_layout);
+            self.applied_style = Some(style_key);
+        }
 
         // Periodically query Link state (every 2 seconds)
         if now.duration_since(self.last_link_query).as_secs() >= 2 {
@@ -261,12 +593,29 @@ impl eframe::App for HaloApp {
             self.last_link_query = now;
         }
 
+        // Keyboard shortcuts, paused while the settings window may be recording one
+        if !self.settings_panel.open {
+            self.search_palette.handle_shortcut(ctx, &self.key_bindings);
+            self.key_bindings.handle_input(
+                ctx,
+                &mut self.active_tab,
+                &self.state,
+                &self.console_tx,
+            );
+        }
+
         // Render UI
         self.render_ui(ctx);
 
         // Render error dialog on top of everything
         self.render_error_dialog(ctx);
 
+        if self.state.settings.performance_overlay {
+            self.performance_overlay.render(ctx, &self.state);
+        }
+        self.performance_overlay
+            .record_frame(now.elapsed(), queued_events);
+
         // Smart repaint based on playback state or active pixel effects
         let has_pixel_fixtures = self
             .state
@@ -289,12 +638,14 @@ pub fn run_ui(
     console_rx: std::sync::mpsc::Receiver<ConsoleEvent>,
     show_file_path: Option<std::path::PathBuf>,
     config_manager: ConfigManager,
+    log_rx: LogReceiver,
 ) -> eframe::Result {
     let native_options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder {
             title: Some(String::from("Halo")),
             app_id: Some(String::from("io.github.robmorgan.halo")),
             maximized: Some(true),
+            drag_and_drop: Some(true),
             ..eframe::egui::ViewportBuilder::default()
         },
         ..Default::default()
@@ -310,6 +661,7 @@ pub fn run_ui(
                 console_rx,
                 show_file_path,
                 config_manager,
+                log_rx,
             )))
         }),
     )
@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use eframe::egui::{self, Color32, RichText};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+// Enough for a whole show's worth of cue changes without growing forever
+const MAX_RECORDS: usize = 5000;
+// Records waiting for the UI to take them; a burst beyond this (or a stalled UI) drops records
+// rather than growing memory
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// A log record captured for the log viewer
+pub struct LogRecord {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+/// Sends every log record to the log viewer, and still prints it for when there is a terminal
+struct ChannelLogger {
+    level: LevelFilter,
+    tx: SyncSender<LogRecord>,
+    dropped: Arc<AtomicUsize>,
+}
+
+/// The log viewer's end of the logger: records plus a count of those dropped on overflow
+pub struct LogReceiver {
+    rx: Receiver<LogRecord>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl Log for ChannelLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let record = LogRecord {
+            time: Local::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        eprintln!(
+            "[{} {} {}] {}",
+            record.time.format("%H:%M:%S%.3f"),
+            record.level,
+            record.target,
+            record.message
+        );
+        // The viewer is gone once the UI has closed, there's nothing left to show the record in
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(record) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install the logger behind the log viewer, call this before anything logs
+///
+/// The level comes from `RUST_LOG` when it's a plain level like `debug`, otherwise `info`.
+pub fn init_logging() -> LogReceiver {
+    let level = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|level| level.parse().ok())
+        .unwrap_or(LevelFilter::Info);
+    let (tx, rx) = sync_channel(CHANNEL_CAPACITY);
+    let dropped = Arc::new(AtomicUsize::new(0));
+
+    let logger = ChannelLogger {
+        level,
+        tx,
+        dropped: dropped.clone(),
+    };
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(level);
+    }
+    LogReceiver { rx, dropped }
+}
+
+/// Searchable list of recent log records, for seeing why something happened without a terminal
+pub struct LogViewer {
+    pub open: bool,
+    rx: LogReceiver,
+    records: VecDeque<LogRecord>,
+    // Least severe level shown
+    min_level: Level,
+    search: String,
+    follow: bool,
+    // Errors logged since the viewer was last open
+    unseen_errors: usize,
+}
+
+impl LogViewer {
+    pub fn new(rx: LogReceiver) -> Self {
+        Self {
+            open: false,
+            rx,
+            records: VecDeque::new(),
+            min_level: Level::Info,
+            search: String::new(),
+            follow: true,
+            unseen_errors: 0,
+        }
+    }
+
+    pub fn unseen_errors(&self) -> usize {
+        self.unseen_errors
+    }
+
+    /// Take records from the logger, call this every frame so the channel doesn't back up
+    pub fn poll(&mut self) {
+        while let Ok(record) = self.rx.rx.try_recv() {
+            if record.level == Level::Error && !self.open {
+                self.unseen_errors += 1;
+            }
+            if self.records.len() == MAX_RECORDS {
+                self.records.pop_front();
+            }
+            self.records.push_back(record);
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+        self.unseen_errors = 0;
+
+        let mut open = self.open;
+        egui::Window::new("Log")
+            .open(&mut open)
+            .default_size([720.0, 400.0])
+            .resizable(true)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("log_level_filter")
+                        .selected_text(self.min_level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [
+                                Level::Error,
+                                Level::Warn,
+                                Level::Info,
+                                Level::Debug,
+                                Level::Trace,
+                            ] {
+                                ui.selectable_value(&mut self.min_level, level, level.as_str());
+                            }
+                        });
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.search)
+                            .hint_text("Search")
+                            .desired_width(240.0),
+                    );
+                    ui.checkbox(&mut self.follow, "Follow");
+                    if ui.button("Clear").clicked() {
+                        self.records.clear();
+                    }
+                    let dropped = self.rx.dropped.load(Ordering::Relaxed);
+                    if dropped > 0 {
+                        ui.colored_label(Color32::YELLOW, format!("{dropped} dropped"))
+                            .on_hover_text("Records logged faster than the viewer could take them");
+                    }
+                });
+                ui.separator();
+
+                let search = self.search.to_lowercase();
+                let shown: Vec<&LogRecord> = self
+                    .records
+                    .iter()
+                    .filter(|record| record.level <= self.min_level)
+                    .filter(|record| {
+                        search.is_empty()
+                            || record.message.to_lowercase().contains(&search)
+                            || record.target.to_lowercase().contains(&search)
+                    })
+                    .collect();
+
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                egui::ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(self.follow)
+                    .show_rows(ui, row_height, shown.len(), |ui, rows| {
+                        for record in &shown[rows] {
+                            ui.horizontal(|ui| {
+                                ui.monospace(record.time.format("%H:%M:%S%.3f").to_string());
+                                ui.label(
+                                    RichText::new(format!("{:<5}", record.level))
+                                        .monospace()
+                                        .color(level_color(record.level)),
+                                );
+                                ui.label(RichText::new(&record.target).monospace().weak());
+                                ui.monospace(&record.message);
+                            });
+                        }
+                    });
+            });
+        self.open = open;
+    }
+}
+
+fn level_color(level: Level) -> Color32 {
+    match level {
+        Level::Error => Color32::RED,
+        Level::Warn => Color32::YELLOW,
+        Level::Info => Color32::LIGHT_GRAY,
+        Level::Debug | Level::Trace => Color32::GRAY,
+    }
+}
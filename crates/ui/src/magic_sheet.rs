@@ -0,0 +1,499 @@
+use eframe::egui::{self, Align2, Color32, FontId, Rect, Sense, Stroke, Vec2};
+use halo_core::{
+    ConsoleCommand, GroupSelectMode, MagicSheet, MagicSheetAction, MagicSheetItem,
+    MagicSheetItemKind,
+};
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+use crate::utils::theme::Theme;
+
+const BUTTON_SIZE: Vec2 = Vec2::new(120.0, 60.0);
+const FIXTURE_SIZE: Vec2 = Vec2::new(60.0, 60.0);
+// Items snap to this grid when dropped so rows line up
+const SNAP: f32 = 10.0;
+
+fn item_size(item: &MagicSheetItem) -> Vec2 {
+    match item.kind {
+        MagicSheetItemKind::Button { .. } => BUTTON_SIZE,
+        MagicSheetItemKind::Fixture { .. } => FIXTURE_SIZE,
+    }
+}
+
+/// A canvas of buttons and fixture icons, laid out in edit mode and played in run mode
+#[derive(Default)]
+pub struct MagicSheetPanel {
+    editing: bool,
+    // The sheet being edited, so typing isn't held up by the round trip through the console
+    draft: Option<MagicSheet>,
+    selected: Option<usize>,
+    // Item being dragged, and how far it has moved
+    dragging: Option<(usize, Vec2)>,
+    // Flash button held down, and the fixtures it flashed
+    flashing: Option<(usize, Vec<usize>)>,
+}
+
+impl MagicSheetPanel {
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let mut sheet = match &self.draft {
+            Some(draft) if self.editing => draft.clone(),
+            _ => state.magic_sheet.clone(),
+        };
+        let mut changed = false;
+
+        if self.editing {
+            egui::SidePanel::right("magic_sheet_inspector")
+                .default_width(260.0)
+                .show(ctx, |ui| {
+                    changed |= self.render_inspector(ui, state, &mut sheet);
+                });
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Magic Sheet");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.selectable_label(self.editing, "✏ Edit").clicked() {
+                        self.editing = !self.editing;
+                        self.draft = self.editing.then(|| state.magic_sheet.clone());
+                        self.selected = None;
+                    }
+                });
+            });
+            ui.separator();
+
+            changed |= self.render_canvas(ui, state, console_tx, &mut sheet);
+        });
+
+        if changed {
+            self.draft = Some(sheet.clone());
+            let _ = console_tx.send(ConsoleCommand::SetMagicSheet { sheet });
+        }
+    }
+
+    fn render_canvas(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+        sheet: &mut MagicSheet,
+    ) -> bool {
+        let theme = Theme::from_settings(&state.settings);
+        let (canvas, background) = ui.allocate_exact_size(ui.available_size(), Sense::click());
+        let painter = ui.painter_at(canvas);
+        painter.rect_filled(canvas, 4.0, theme.panel_bg);
+
+        if sheet.items.is_empty() {
+            painter.text(
+                canvas.center(),
+                Align2::CENTER_CENTER,
+                if self.editing {
+                    "Add buttons and fixtures from the panel on the right"
+                } else {
+                    "Empty magic sheet, click Edit to add buttons"
+                },
+                FontId::proportional(16.0),
+                theme.text_dim,
+            );
+        }
+        if self.editing && background.clicked() {
+            self.selected = None;
+        }
+
+        let mut changed = false;
+        for (index, item) in sheet.items.iter_mut().enumerate() {
+            let offset = match self.dragging {
+                Some((dragged, offset)) if dragged == index => offset,
+                _ => Vec2::ZERO,
+            };
+            let rect = Rect::from_min_size(
+                canvas.min + Vec2::from(item.position) + offset,
+                item_size(item),
+            );
+            let response = ui.interact(
+                rect,
+                ui.id().with(("magic_sheet_item", index)),
+                Sense::click_and_drag(),
+            );
+
+            if self.editing {
+                if response.clicked() || response.drag_started() {
+                    self.selected = Some(index);
+                }
+                if response.dragged() {
+                    self.dragging = Some((index, offset + response.drag_delta()));
+                }
+                if response.drag_stopped() {
+                    let moved = Vec2::from(item.position) + offset;
+                    item.position = [
+                        ((moved.x / SNAP).round() * SNAP).max(0.0),
+                        ((moved.y / SNAP).round() * SNAP).max(0.0),
+                    ];
+                    self.dragging = None;
+                    changed = true;
+                }
+            } else {
+                self.run_item(index, item, &response, state, console_tx);
+            }
+
+            let selected = self.editing && self.selected == Some(index);
+            draw_item(&painter, rect, item, state, &theme, selected);
+        }
+        changed
+    }
+
+    /// Send the item's action when it's pressed in run mode
+    fn run_item(
+        &mut self,
+        index: usize,
+        item: &MagicSheetItem,
+        response: &egui::Response,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let action = match &item.kind {
+            MagicSheetItemKind::Button { action, .. } => *action,
+            MagicSheetItemKind::Fixture { fixture_id } => {
+                if response.clicked() {
+                    let fixture_id = *fixture_id;
+                    let _ = console_tx.send(if state.selected_fixtures.contains(&fixture_id) {
+                        ConsoleCommand::RemoveSelectedFixture { fixture_id }
+                    } else {
+                        ConsoleCommand::AddSelectedFixture { fixture_id }
+                    });
+                }
+                return;
+            }
+        };
+
+        match action {
+            MagicSheetAction::FireCue {
+                list_index,
+                cue_index,
+            } => {
+                if response.clicked() {
+                    let _ = console_tx.send(ConsoleCommand::GoToCue {
+                        list_index,
+                        cue_index,
+                    });
+                }
+            }
+            MagicSheetAction::SelectGroup { group_id } => {
+                if response.clicked() {
+                    let _ = console_tx.send(ConsoleCommand::SelectFixtureGroup {
+                        group_id,
+                        mode: GroupSelectMode::Replace,
+                    });
+                }
+            }
+            MagicSheetAction::FlashGroup { group_id } => {
+                let held = response.is_pointer_button_down_on();
+                let flashing = matches!(&self.flashing, Some((i, _)) if *i == index);
+                if held && !flashing {
+                    let fixture_ids = state
+                        .fixture_groups
+                        .iter()
+                        .find(|g| g.id == group_id)
+                        .map(|g| g.fixture_ids.clone())
+                        .unwrap_or_default();
+                    let _ = console_tx.send(ConsoleCommand::SetFlash {
+                        fixture_ids: fixture_ids.clone(),
+                        active: true,
+                    });
+                    self.flashing = Some((index, fixture_ids));
+                } else if !held && flashing {
+                    if let Some((_, fixture_ids)) = self.flashing.take() {
+                        let _ = console_tx.send(ConsoleCommand::SetFlash {
+                            fixture_ids,
+                            active: false,
+                        });
+                    }
+                }
+            }
+            MagicSheetAction::ToggleSmoke => {
+                if response.clicked() {
+                    let _ = console_tx.send(ConsoleCommand::SetSmoke {
+                        active: !state.smoke_active,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Add items and edit the selected one, returning whether the sheet changed
+    fn render_inspector(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        sheet: &mut MagicSheet,
+    ) -> bool {
+        let mut changed = false;
+        // New items are staggered so they don't land on top of each other
+        let next_position = [
+            20.0 + (sheet.items.len() % 8) as f32 * SNAP * 2.0,
+            20.0 + (sheet.items.len() % 8) as f32 * SNAP * 2.0,
+        ];
+
+        ui.heading("Add");
+        ui.horizontal(|ui| {
+            if ui.button("Button").clicked() {
+                sheet.items.push(MagicSheetItem {
+                    position: next_position,
+                    kind: MagicSheetItemKind::Button {
+                        label: "Button".to_string(),
+                        action: MagicSheetAction::ToggleSmoke,
+                        color: [59, 130, 246],
+                    },
+                });
+                self.selected = Some(sheet.items.len() - 1);
+                changed = true;
+            }
+            ui.menu_button("Fixture", |ui| {
+                let mut fixtures: Vec<_> = state.fixtures.values().collect();
+                fixtures.sort_by_key(|f| f.id);
+                for fixture in fixtures {
+                    if ui.button(&fixture.name).clicked() {
+                        sheet.items.push(MagicSheetItem {
+                            position: next_position,
+                            kind: MagicSheetItemKind::Fixture {
+                                fixture_id: fixture.id,
+                            },
+                        });
+                        self.selected = Some(sheet.items.len() - 1);
+                        changed = true;
+                        ui.close();
+                    }
+                }
+            });
+        });
+        ui.separator();
+
+        let Some(index) = self.selected.filter(|i| *i < sheet.items.len()) else {
+            ui.weak("Select an item to edit it, drag items to move them");
+            return changed;
+        };
+
+        match &mut sheet.items[index].kind {
+            MagicSheetItemKind::Button {
+                label,
+                action,
+                color,
+            } => {
+                ui.heading("Button");
+                egui::Grid::new("magic_sheet_button_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Label:");
+                        changed |= ui.text_edit_singleline(label).changed();
+                        ui.end_row();
+
+                        ui.label("Color:");
+                        changed |= ui.color_edit_button_srgb(color).changed();
+                        ui.end_row();
+
+                        changed |= render_action_editor(ui, state, action);
+                    });
+            }
+            MagicSheetItemKind::Fixture { fixture_id } => {
+                ui.heading("Fixture");
+                let name = fixture_name(state, *fixture_id);
+                egui::ComboBox::from_id_salt("magic_sheet_fixture")
+                    .selected_text(name)
+                    .show_ui(ui, |ui| {
+                        let mut fixtures: Vec<_> = state.fixtures.values().collect();
+                        fixtures.sort_by_key(|f| f.id);
+                        for fixture in fixtures {
+                            changed |= ui
+                                .selectable_value(fixture_id, fixture.id, &fixture.name)
+                                .changed();
+                        }
+                    });
+            }
+        }
+
+        ui.add_space(10.0);
+        if ui.button("🗑 Delete").clicked() {
+            sheet.items.remove(index);
+            self.selected = None;
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// Action type and target rows for the button grid
+fn render_action_editor(
+    ui: &mut egui::Ui,
+    state: &ConsoleState,
+    action: &mut MagicSheetAction,
+) -> bool {
+    let mut changed = false;
+    let first_group = state.fixture_groups.first().map_or(0, |g| g.id);
+
+    ui.label("Action:");
+    egui::ComboBox::from_id_salt("magic_sheet_action")
+        .selected_text(action.as_str())
+        .show_ui(ui, |ui| {
+            for option in [
+                MagicSheetAction::FireCue {
+                    list_index: 0,
+                    cue_index: 0,
+                },
+                MagicSheetAction::SelectGroup {
+                    group_id: first_group,
+                },
+                MagicSheetAction::FlashGroup {
+                    group_id: first_group,
+                },
+                MagicSheetAction::ToggleSmoke,
+            ] {
+                let current = std::mem::discriminant(action) == std::mem::discriminant(&option);
+                if ui.selectable_label(current, option.as_str()).clicked() && !current {
+                    *action = option;
+                    changed = true;
+                }
+            }
+        });
+    ui.end_row();
+
+    match action {
+        MagicSheetAction::FireCue {
+            list_index,
+            cue_index,
+        } => {
+            ui.label("Cue list:");
+            let list_name = state
+                .cue_lists
+                .get(*list_index)
+                .map_or("None", |list| list.name.as_str());
+            egui::ComboBox::from_id_salt("magic_sheet_cue_list")
+                .selected_text(list_name)
+                .show_ui(ui, |ui| {
+                    for (i, list) in state.cue_lists.iter().enumerate() {
+                        if ui.selectable_value(list_index, i, &list.name).changed() {
+                            *cue_index = 0;
+                            changed = true;
+                        }
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Cue:");
+            let cues = state
+                .cue_lists
+                .get(*list_index)
+                .map(|list| list.cues.as_slice())
+                .unwrap_or_default();
+            let cue_name = cues.get(*cue_index).map_or("None", |cue| cue.name.as_str());
+            egui::ComboBox::from_id_salt("magic_sheet_cue")
+                .selected_text(cue_name)
+                .show_ui(ui, |ui| {
+                    for (i, cue) in cues.iter().enumerate() {
+                        changed |= ui.selectable_value(cue_index, i, &cue.name).changed();
+                    }
+                });
+            ui.end_row();
+        }
+        MagicSheetAction::SelectGroup { group_id } | MagicSheetAction::FlashGroup { group_id } => {
+            ui.label("Group:");
+            let group_name = state
+                .fixture_groups
+                .iter()
+                .find(|g| g.id == *group_id)
+                .map_or("None", |g| g.name.as_str());
+            egui::ComboBox::from_id_salt("magic_sheet_group")
+                .selected_text(group_name)
+                .show_ui(ui, |ui| {
+                    for group in &state.fixture_groups {
+                        changed |= ui
+                            .selectable_value(group_id, group.id, &group.name)
+                            .changed();
+                    }
+                });
+            ui.end_row();
+        }
+        MagicSheetAction::ToggleSmoke => {}
+    }
+    changed
+}
+
+fn fixture_name(state: &ConsoleState, fixture_id: usize) -> String {
+    state
+        .fixtures
+        .values()
+        .find(|f| f.id == fixture_id)
+        .map_or_else(|| format!("Fixture {fixture_id}"), |f| f.name.clone())
+}
+
+fn draw_item(
+    painter: &egui::Painter,
+    rect: Rect,
+    item: &MagicSheetItem,
+    state: &ConsoleState,
+    theme: &Theme,
+    selected: bool,
+) {
+    let outline = if selected {
+        Stroke::new(2.0, theme.accent_color)
+    } else {
+        Stroke::new(1.0, Color32::from_gray(70))
+    };
+
+    match &item.kind {
+        MagicSheetItemKind::Button {
+            label,
+            action,
+            color,
+        } => {
+            let [r, g, b] = *color;
+            let fill = Color32::from_rgb(r, g, b);
+            // Smoke buttons are dimmed until the smoke is running
+            let dimmed = matches!(action, MagicSheetAction::ToggleSmoke) && !state.smoke_active;
+            let fill = if dimmed {
+                fill.gamma_multiply(0.5)
+            } else {
+                fill
+            };
+            painter.rect_filled(rect, 6.0, fill);
+            painter.rect_stroke(rect, 6.0, outline, egui::StrokeKind::Outside);
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                label,
+                FontId::proportional(15.0),
+                Color32::WHITE,
+            );
+        }
+        MagicSheetItemKind::Fixture { fixture_id } => {
+            let in_selection = state.selected_fixtures.contains(fixture_id);
+            let radius = rect.width().min(rect.height()) / 2.0 - 8.0;
+            let center = rect.center() - Vec2::new(0.0, 6.0);
+            painter.circle_filled(center, radius, Color32::from_gray(40));
+            painter.circle_stroke(
+                center,
+                radius,
+                if in_selection {
+                    Stroke::new(3.0, theme.accent_color)
+                } else {
+                    Stroke::new(1.0, Color32::from_gray(110))
+                },
+            );
+            if selected {
+                painter.rect_stroke(rect, 4.0, outline, egui::StrokeKind::Outside);
+            }
+            painter.text(
+                rect.center_bottom(),
+                Align2::CENTER_BOTTOM,
+                fixture_name(state, *fixture_id),
+                FontId::proportional(11.0),
+                theme.text_dim,
+            );
+        }
+    }
+}
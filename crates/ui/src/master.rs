@@ -3,7 +3,6 @@ use halo_core::ConsoleCommand;
 use tokio::sync::mpsc;
 
 use crate::state::ConsoleState;
-use crate::visualizer;
 
 // Override button state
 #[derive(Clone, Debug)]
@@ -52,13 +51,6 @@ pub fn render(
     console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
 ) {
     ui.horizontal(|ui| {
-        // Visualizer section
-        ui.vertical(|ui| {
-            ui.heading("VISUALIZER");
-            ui.add_space(5.0);
-            visualizer::render(ui, state, console_tx);
-        });
-
         // Left side - Overrides section
         ui.vertical(|ui| {
             ui.heading("OVERRIDES");
@@ -104,11 +96,34 @@ pub fn render(
             // Stack faders vertically
             ui.vertical(|ui| {
                 // Master fader
-                draw_master_fader(ui, "Master", 1.0, Color32::from_rgb(150, 150, 150));
+                draw_master_fader(ui, "Master", 1.0, 1.0, Color32::from_rgb(150, 150, 150));
                 ui.add_space(10.0);
 
                 // Smoke fader
-                draw_master_fader(ui, "Smoke", 0.75, Color32::from_rgb(100, 100, 100));
+                draw_master_fader(ui, "Smoke", 0.75, 1.0, Color32::from_rgb(100, 100, 100));
+                ui.add_space(10.0);
+
+                // FX rate and size masters scale every running effect (0-200%)
+                if let Some(rate) = draw_master_fader(
+                    ui,
+                    "FX Rate",
+                    state.fx_rate_master as f32,
+                    2.0,
+                    Color32::from_rgb(0, 150, 200),
+                ) {
+                    let _ = console_tx.send(ConsoleCommand::SetFxRateMaster { rate: rate as f64 });
+                }
+                ui.add_space(10.0);
+
+                if let Some(size) = draw_master_fader(
+                    ui,
+                    "FX Size",
+                    state.fx_size_master as f32,
+                    2.0,
+                    Color32::from_rgb(200, 120, 0),
+                ) {
+                    let _ = console_tx.send(ConsoleCommand::SetFxSizeMaster { size: size as f64 });
+                }
             });
         });
     });
@@ -165,15 +180,21 @@ fn draw_override_button(
     response
 }
 
-// Draw a single master fader
-fn draw_master_fader(ui: &mut egui::Ui, name: &str, mut value: f32, color: Color32) {
+// Draw a single master fader, returning the new value when it is moved
+fn draw_master_fader(
+    ui: &mut egui::Ui,
+    name: &str,
+    mut value: f32,
+    max: f32,
+    color: Color32,
+) -> Option<f32> {
     ui.vertical(|ui| {
         // Fader label with percentage immediately following
         ui.label(format!("{} {:.0}%", name, value * 100.0));
 
         // Fader slider
         let response = ui.add(
-            egui::Slider::new(&mut value, 0.0..=1.0)
+            egui::Slider::new(&mut value, 0.0..=max)
                 .show_value(false)
                 .fixed_decimals(2)
                 .orientation(egui::SliderOrientation::Horizontal),
@@ -191,17 +212,15 @@ fn draw_master_fader(ui: &mut egui::Ui, name: &str, mut value: f32, color: Color
         );
 
         // Draw filled portion
-        let fill_width = slider_rect.width() * value;
+        let fill_width = slider_rect.width() * value / max;
         let fill_rect = Rect::from_min_size(track_rect.min, Vec2::new(fill_width, track_height));
 
         ui.painter()
-            .rect_filled(track_rect, 2.0, Color32::from_rgb(40, 40, 40));
+            .rect_filled(track_rect, 2.0, ui.visuals().extreme_bg_color);
 
         ui.painter().rect_filled(fill_rect, 2.0, color);
 
-        // Apply fader value changes (TODO: implement via message passing)
-        if response.changed() {
-            // TODO: Send master fader command
-        }
-    });
+        response.changed().then_some(value)
+    })
+    .inner
 }
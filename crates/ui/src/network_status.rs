@@ -0,0 +1,126 @@
+use eframe::egui::{self, Color32, RichText};
+use halo_core::ConsoleCommand;
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+
+// Art-Net output is sent at about 44 packets a second, well under that means dropped frames
+const LOW_PACKET_RATE: f32 = 30.0;
+
+/// Art-Net destinations, discovered nodes, per-universe packet rates and socket errors,
+/// for diagnosing network problems from FOH
+#[derive(Default)]
+pub struct NetworkStatusPanel {
+    pub open: bool,
+    // Node discovery holds the Art-Net port, so it only runs while the panel is open
+    discovering: bool,
+}
+
+impl NetworkStatusPanel {
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        if self.discovering != self.open {
+            self.discovering = self.open;
+            let _ = console_tx.send(ConsoleCommand::SetNodeDiscovery { enabled: self.open });
+        }
+        if !self.open {
+            return;
+        }
+
+        let status = &state.network_status;
+        egui::Window::new("Network Status")
+            .open(&mut self.open)
+            .default_width(480.0)
+            .resizable(true)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.heading("Destinations");
+                    if status.destinations.is_empty() {
+                        ui.weak("No Art-Net destinations configured");
+                    }
+                    egui::Grid::new("network_destinations_grid")
+                        .num_columns(3)
+                        .spacing([20.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for destination in &status.destinations {
+                                ui.strong(&destination.name);
+                                ui.monospace(&destination.address);
+                                let universes: Vec<String> = destination
+                                    .universes
+                                    .iter()
+                                    .map(|u| u.to_string())
+                                    .collect();
+                                ui.label(format!("Universes: {}", universes.join(", ")));
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.add_space(8.0);
+                    ui.heading("Universes");
+                    if status.universes.is_empty() {
+                        ui.weak("No universes are being output");
+                    }
+                    egui::Grid::new("network_universes_grid")
+                        .num_columns(3)
+                        .spacing([20.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for universe in &status.universes {
+                                ui.label(format!("Universe {}", universe.universe));
+                                match &universe.destination {
+                                    Some(name) => ui.label(name),
+                                    None => ui.colored_label(Color32::YELLOW, "Not routed"),
+                                };
+                                let rate = format!("{:.1} pkt/s", universe.packets_per_sec);
+                                if universe.packets_per_sec < LOW_PACKET_RATE {
+                                    ui.colored_label(Color32::YELLOW, rate);
+                                } else {
+                                    ui.label(rate);
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.add_space(8.0);
+                    ui.heading("Discovered Nodes");
+                    if let Some(error) = &status.discovery_error {
+                        ui.weak(error);
+                    } else if status.nodes.is_empty() {
+                        ui.weak("No nodes have answered ArtPoll");
+                    }
+                    egui::Grid::new("network_nodes_grid")
+                        .num_columns(3)
+                        .spacing([20.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for node in &status.nodes {
+                                ui.monospace(node.address.to_string());
+                                ui.label(&node.short_name).on_hover_text(&node.long_name);
+                                ui.weak(&node.report);
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.add_space(8.0);
+                    ui.heading("Socket Errors");
+                    if status.socket_errors == 0 {
+                        ui.label("None");
+                    } else {
+                        ui.label(
+                            RichText::new(format!("{} send errors", status.socket_errors))
+                                .color(Color32::RED),
+                        );
+                        for error in status.recent_errors.iter().rev() {
+                            ui.monospace(error);
+                        }
+                    }
+                });
+            });
+    }
+}
@@ -0,0 +1,58 @@
+use eframe::egui::{self, Align, Color32, Layout, RichText};
+
+use crate::state::ConsoleState;
+use crate::utils::theme::Theme;
+
+// Countdowns under this turn yellow so the operator knows a cue is about to fire
+const WARNING_SECONDS: f64 = 10.0;
+
+/// The cue the next Go or timecode trigger will play, with a countdown for timecoded cues
+pub fn render(ui: &mut egui::Ui, state: &ConsoleState) {
+    let theme = Theme::from_settings(&state.settings);
+
+    egui::Frame::default().inner_margin(10.0).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.heading("Next Cue");
+            if let Some(seconds) = state.next_cue.as_ref().and_then(|cue| cue.seconds_until) {
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    let color = if seconds < WARNING_SECONDS {
+                        Color32::YELLOW
+                    } else {
+                        ui.visuals().strong_text_color()
+                    };
+                    ui.label(
+                        RichText::new(format_countdown(seconds))
+                            .monospace()
+                            .size(20.0)
+                            .color(color),
+                    );
+                });
+            }
+        });
+
+        let Some(cue) = &state.next_cue else {
+            ui.label(RichText::new("End of cue list").color(theme.text_dim));
+            return;
+        };
+
+        ui.label(RichText::new(&cue.name).size(16.0).strong());
+        ui.label(
+            RichText::new(format!(
+                "{} fixtures | {} effects | {:.1}s fade{}",
+                cue.fixture_count,
+                cue.effect_count,
+                cue.fade_time.as_secs_f64(),
+                cue.timecode
+                    .as_ref()
+                    .map(|tc| format!(" | at {tc}"))
+                    .unwrap_or_default()
+            ))
+            .color(theme.text_dim),
+        );
+    });
+}
+
+fn format_countdown(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).floor();
+    format!("-{minutes:02}:{:04.1}", seconds - minutes * 60.0)
+}
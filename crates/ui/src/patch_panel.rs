@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use eframe::egui;
 use halo_core::ConsoleCommand;
+use halo_fixtures::FixturePosition;
 use tokio::sync::mpsc;
 
 use crate::state::ConsoleState;
@@ -17,6 +18,8 @@ pub struct PatchPanelState {
     limit_pan_max: u8,
     limit_tilt_min: u8,
     limit_tilt_max: u8,
+    editing_position_fixture_id: Option<usize>,
+    position: FixturePosition,
     fixture_to_remove: Option<usize>,
     fixture_to_remove_name: String,
 }
@@ -41,6 +44,8 @@ impl Default for PatchPanelState {
             limit_pan_max: 255,
             limit_tilt_min: 0,
             limit_tilt_max: 255,
+            editing_position_fixture_id: None,
+            position: FixturePosition::default(),
             fixture_to_remove: None,
             fixture_to_remove_name: String::new(),
         }
@@ -189,6 +194,24 @@ impl PatchPanelState {
                                         }
                                     }
 
+                                    // Show position badge if set
+                                    if let Some(position) = &fixture.position {
+                                        ui.label(format!(
+                                            "📍 {:.1}, {:.1}, {:.1}",
+                                            position.x, position.y, position.z
+                                        ));
+                                    }
+
+                                    if ui.button("Position").clicked() {
+                                        // Toggle position editor for this fixture
+                                        if self.editing_position_fixture_id == Some(fixture.id) {
+                                            self.editing_position_fixture_id = None;
+                                        } else {
+                                            self.editing_position_fixture_id = Some(fixture.id);
+                                            self.position = fixture.position.unwrap_or_default();
+                                        }
+                                    }
+
                                     if ui.button("Remove").clicked() {
                                         self.fixture_to_remove = Some(fixture.id);
                                         self.fixture_to_remove_name = fixture.name.clone();
@@ -249,6 +272,55 @@ impl PatchPanelState {
                                         });
                                     });
                                 }
+
+                                // Show position editor if this fixture is being edited
+                                if self.editing_position_fixture_id == Some(fixture.id) {
+                                    ui.indent(format!("position_editor_{}", fixture.id), |ui| {
+                                        ui.horizontal(|ui| {
+                                            ui.label("X:");
+                                            ui.add(
+                                                egui::DragValue::new(&mut self.position.x)
+                                                    .speed(0.05)
+                                                    .suffix(" m"),
+                                            );
+                                            ui.label("Y:");
+                                            ui.add(
+                                                egui::DragValue::new(&mut self.position.y)
+                                                    .speed(0.05)
+                                                    .suffix(" m"),
+                                            );
+                                            ui.label("Z:");
+                                            ui.add(
+                                                egui::DragValue::new(&mut self.position.z)
+                                                    .speed(0.05)
+                                                    .suffix(" m"),
+                                            );
+                                        });
+                                        ui.horizontal(|ui| {
+                                            if ui.button("Apply Position").clicked() {
+                                                let _ = console_tx.send(
+                                                    ConsoleCommand::SetFixturePosition {
+                                                        fixture_id: fixture.id,
+                                                        position: Some(self.position),
+                                                    },
+                                                );
+                                                self.editing_position_fixture_id = None;
+                                            }
+                                            if ui.button("Clear Position").clicked() {
+                                                let _ = console_tx.send(
+                                                    ConsoleCommand::SetFixturePosition {
+                                                        fixture_id: fixture.id,
+                                                        position: None,
+                                                    },
+                                                );
+                                                self.editing_position_fixture_id = None;
+                                            }
+                                            if ui.button("Cancel").clicked() {
+                                                self.editing_position_fixture_id = None;
+                                            }
+                                        });
+                                    });
+                                }
                             });
                         }
                     });
@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use eframe::egui::{self, Color32, RichText};
+
+use crate::state::ConsoleState;
+
+// About two seconds of frames at 60fps
+const FRAME_HISTORY: usize = 120;
+// Frame times past this will be visible as stutter on a 60Hz display
+const SLOW_FRAME_MS: f32 = 16.7;
+
+/// Small always-on-top readout of UI and console timings, for chasing stutters during a show
+#[derive(Default)]
+pub struct PerformanceOverlay {
+    // (frame time in ms, console events applied that frame)
+    frames: VecDeque<(f32, usize)>,
+}
+
+impl PerformanceOverlay {
+    pub fn record_frame(&mut self, frame_time: Duration, queued_events: usize) {
+        if self.frames.len() == FRAME_HISTORY {
+            self.frames.pop_front();
+        }
+        self.frames
+            .push_back((frame_time.as_secs_f32() * 1000.0, queued_events));
+    }
+
+    pub fn render(&self, ctx: &egui::Context, state: &ConsoleState) {
+        let count = self.frames.len().max(1) as f32;
+        let average_frame = self.frames.iter().map(|(ms, _)| ms).sum::<f32>() / count;
+        let worst_frame = self.frames.iter().map(|(ms, _)| *ms).fold(0.0, f32::max);
+        let worst_events = self.frames.iter().map(|(_, n)| *n).max().unwrap_or(0);
+        let metrics = &state.performance;
+
+        egui::Area::new(egui::Id::new("performance_overlay"))
+            .anchor(egui::Align2::RIGHT_TOP, [-10.0, 40.0])
+            .interactable(false)
+            .order(egui::Order::Tooltip)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style())
+                    .fill(Color32::from_black_alpha(200))
+                    .show(ui, |ui| {
+                        let row = |ui: &mut egui::Ui, label: &str, value: String, warn: bool| {
+                            ui.label(RichText::new(label).monospace().color(Color32::GRAY));
+                            let value = RichText::new(value).monospace();
+                            ui.label(if warn {
+                                value.color(Color32::YELLOW)
+                            } else {
+                                value.color(Color32::WHITE)
+                            });
+                            ui.end_row();
+                        };
+
+                        egui::Grid::new("performance_overlay_grid")
+                            .num_columns(2)
+                            .spacing([12.0, 2.0])
+                            .show(ui, |ui| {
+                                row(
+                                    ui,
+                                    "UI frame",
+                                    format!("{average_frame:.1} ms (worst {worst_frame:.1})"),
+                                    worst_frame > SLOW_FRAME_MS,
+                                );
+                                row(ui, "UI events", format!("{worst_events} per frame"), false);
+                                row(
+                                    ui,
+                                    "Console update",
+                                    format!("{:.2} ms", metrics.update_time_ms),
+                                    false,
+                                );
+                                row(
+                                    ui,
+                                    "Loop jitter",
+                                    format!("{:.1} ms", metrics.update_jitter_ms),
+                                    metrics.update_jitter_ms > 10.0,
+                                );
+                                row(
+                                    ui,
+                                    "Command queue",
+                                    metrics.command_queue_depth.to_string(),
+                                    metrics.command_queue_depth > 0,
+                                );
+                                row(
+                                    ui,
+                                    "Module queue",
+                                    metrics.module_queue_depth.to_string(),
+                                    metrics.module_queue_depth > 0,
+                                );
+                                row(
+                                    ui,
+                                    "DMX output",
+                                    format!("{:.1} fps", metrics.dmx_fps),
+                                    metrics.dmx_fps < 40.0,
+                                );
+                            });
+                    });
+            });
+    }
+}
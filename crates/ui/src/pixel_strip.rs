@@ -0,0 +1,47 @@
+use eframe::egui::{self, Color32, Painter, Pos2, Rect, Stroke, Vec2};
+
+/// Paint a pixel bar's live colors across `rect`, one cell per pixel
+pub fn paint(painter: &Painter, rect: Rect, pixels: &[(u8, u8, u8)]) {
+    if pixels.is_empty() {
+        return;
+    }
+
+    let pixel_width = rect.width() / pixels.len() as f32;
+    for (i, (r, g, b)) in pixels.iter().enumerate() {
+        let pixel_rect = Rect::from_min_size(
+            Pos2::new(rect.min.x + i as f32 * pixel_width, rect.min.y),
+            Vec2::new(pixel_width, rect.height()),
+        );
+        painter.rect_filled(pixel_rect, 0.0, Color32::from_rgb(*r, *g, *b));
+
+        // A subtle border between pixels, when there's room for one
+        if pixel_width > 2.0 {
+            painter.rect_stroke(
+                pixel_rect,
+                0.0,
+                Stroke::new(0.5, Color32::from_gray(40)),
+                egui::StrokeKind::Middle,
+            );
+        }
+    }
+}
+
+/// A strip the full available width, hovering shows the pixel under the pointer
+pub fn show(ui: &mut egui::Ui, pixels: &[(u8, u8, u8)], height: f32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(
+        Vec2::new(ui.available_width(), height),
+        egui::Sense::hover(),
+    );
+    paint(ui.painter(), rect, pixels);
+
+    let hovered_pixel = response.hover_pos().and_then(|pos| {
+        let index = ((pos.x - rect.min.x) / rect.width() * pixels.len() as f32) as usize;
+        pixels.get(index).map(|pixel| (index, pixel))
+    });
+    match hovered_pixel {
+        Some((index, (r, g, b))) => {
+            response.on_hover_text(format!("Pixel {}: R {r} G {g} B {b}", index + 1))
+        }
+        None => response,
+    }
+}
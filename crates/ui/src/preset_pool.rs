@@ -0,0 +1,233 @@
+use eframe::egui;
+use halo_core::{ConsoleCommand, PresetReference, PresetType};
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+
+const TILES_PER_POOL: usize = 16;
+const TILE_SIZE: egui::Vec2 = egui::vec2(72.0, 52.0);
+
+/// What clicking a preset tile does
+#[derive(Clone, Copy, PartialEq)]
+enum PoolMode {
+    Recall,
+    Store,
+    Reference,
+    Delete,
+}
+
+impl PoolMode {
+    fn all() -> [PoolMode; 4] {
+        [
+            PoolMode::Recall,
+            PoolMode::Store,
+            PoolMode::Reference,
+            PoolMode::Delete,
+        ]
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            PoolMode::Recall => "Recall",
+            PoolMode::Store => "Store",
+            PoolMode::Reference => "Reference in Cue",
+            PoolMode::Delete => "Delete",
+        }
+    }
+}
+
+pub struct PresetPoolPanel {
+    mode: PoolMode,
+    store_name: String,
+    store_changed_only: bool,
+    cue_index: usize,
+}
+
+impl Default for PresetPoolPanel {
+    fn default() -> Self {
+        Self {
+            mode: PoolMode::Recall,
+            store_name: String::new(),
+            store_changed_only: false,
+            cue_index: 0,
+        }
+    }
+}
+
+impl PresetPoolPanel {
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        egui::SidePanel::right("preset_pool_cue_panel")
+            .min_width(280.0)
+            .show(ctx, |ui| {
+                self.render_cue_references(ui, state, console_tx);
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Preset Pools");
+            ui.horizontal(|ui| {
+                for mode in PoolMode::all() {
+                    ui.selectable_value(&mut self.mode, mode, mode.as_str());
+                }
+                if self.mode == PoolMode::Store {
+                    ui.separator();
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut self.store_name);
+                    ui.checkbox(&mut self.store_changed_only, "Changed only")
+                        .on_hover_text("Skip values that match the running cues");
+                }
+            });
+            ui.label(format!(
+                "{} fixture(s) selected",
+                state.selected_fixtures.len()
+            ));
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for preset_type in PresetType::value_types() {
+                    self.render_pool(ui, state, console_tx, preset_type);
+                }
+                self.render_pool(ui, state, console_tx, PresetType::Effect);
+            });
+        });
+    }
+
+    fn render_pool(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+        preset_type: PresetType,
+    ) {
+        ui.label(egui::RichText::new(preset_type.as_str()).strong());
+        ui.horizontal_wrapped(|ui| {
+            for preset_id in 1..=TILES_PER_POOL {
+                let preset = state.presets.get_preset(&preset_type, preset_id);
+                let (label, fill) = match &preset {
+                    Some(preset) => (
+                        format!("{preset_id}\n{}", preset.name()),
+                        egui::Color32::from_rgb(40, 70, 110),
+                    ),
+                    None => (format!("{preset_id}"), egui::Color32::from_gray(35)),
+                };
+                let tile = ui.add_sized(
+                    TILE_SIZE,
+                    egui::Button::new(egui::RichText::new(label).small()).fill(fill),
+                );
+                if tile.clicked() {
+                    self.tile_clicked(state, console_tx, &preset_type, preset_id, preset.is_some());
+                }
+            }
+        });
+        ui.add_space(8.0);
+    }
+
+    fn tile_clicked(
+        &mut self,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+        preset_type: &PresetType,
+        preset_id: usize,
+        is_stored: bool,
+    ) {
+        match self.mode {
+            // Effect presets are stored from the cue editor
+            PoolMode::Store if *preset_type != PresetType::Effect => {
+                let _ = console_tx.send(ConsoleCommand::StoreProgrammerPreset {
+                    preset_type: preset_type.clone(),
+                    preset_id,
+                    name: std::mem::take(&mut self.store_name),
+                    changed_only: self.store_changed_only,
+                });
+            }
+            PoolMode::Recall if is_stored && *preset_type != PresetType::Effect => {
+                let _ = console_tx.send(ConsoleCommand::RecallPreset {
+                    preset_type: preset_type.clone(),
+                    preset_id,
+                    fixture_ids: state.selected_fixtures.clone(),
+                });
+            }
+            PoolMode::Reference if is_stored => {
+                let _ = console_tx.send(ConsoleCommand::AddCuePresetReference {
+                    list_index: state.current_cue_list_index,
+                    cue_index: self.cue_index,
+                    reference: PresetReference {
+                        preset_type: preset_type.clone(),
+                        preset_id,
+                        fixture_group_id: None,
+                        fixture_ids: state.selected_fixtures.clone(),
+                        overrides: Vec::new(),
+                    },
+                });
+            }
+            PoolMode::Delete if is_stored => {
+                let _ = console_tx.send(ConsoleCommand::DeletePreset {
+                    preset_type: preset_type.clone(),
+                    preset_id,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn render_cue_references(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        ui.heading("Cue References");
+
+        let Some(cue_list) = state.cue_lists.get(state.current_cue_list_index) else {
+            ui.label("No cue list selected");
+            return;
+        };
+        if cue_list.cues.is_empty() {
+            ui.label("Cue list has no cues");
+            return;
+        }
+        self.cue_index = self.cue_index.min(cue_list.cues.len() - 1);
+
+        egui::ComboBox::from_label("Cue")
+            .selected_text(cue_list.cues[self.cue_index].name.clone())
+            .show_ui(ui, |ui| {
+                for (index, cue) in cue_list.cues.iter().enumerate() {
+                    ui.selectable_value(&mut self.cue_index, index, &cue.name);
+                }
+            });
+        ui.separator();
+
+        let cue = &cue_list.cues[self.cue_index];
+        if cue.preset_references.is_empty() {
+            ui.label("No presets referenced");
+            return;
+        }
+
+        for (reference_index, reference) in cue.preset_references.iter().enumerate() {
+            let name = state
+                .presets
+                .get_preset(&reference.preset_type, reference.preset_id)
+                .map(|p| p.name().to_string())
+                .unwrap_or_else(|| "(missing)".to_string());
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{} {}: {name} ({} fixtures)",
+                    reference.preset_type.as_str(),
+                    reference.preset_id,
+                    reference.fixture_ids.len()
+                ));
+                if ui.small_button("✖").clicked() {
+                    let _ = console_tx.send(ConsoleCommand::RemoveCuePresetReference {
+                        list_index: state.current_cue_list_index,
+                        cue_index: self.cue_index,
+                        reference_index,
+                    });
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, Color32, Pos2, Rect, Sense, Stroke, Vec2};
+use halo_core::ConsoleCommand;
+use halo_fixtures::{ChannelType, Fixture, FixtureType};
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+
+/// How often live output is requested while the panel is open
+const OUTPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Height unplaced fixtures hang at, in metres
+const DEFAULT_TRIM_HEIGHT: f32 = 4.0;
+const MAX_BEAM_LENGTH: f32 = 12.0;
+const FLOOR_HALF_SIZE: i32 = 8;
+
+type Vec3 = [f32; 3];
+
+/// 3D pre-visualisation of the rig from fixture positions and live output
+///
+/// Positions are in metres with X across the stage, Y towards the audience and Z up. Moving
+/// heads point straight down at pan/tilt centre.
+pub struct PrevisPanel {
+    yaw: f32,
+    pitch: f32,
+    distance: f32,
+    last_output_query: Instant,
+}
+
+impl Default for PrevisPanel {
+    fn default() -> Self {
+        Self {
+            yaw: -std::f32::consts::FRAC_PI_2,
+            pitch: 0.35,
+            distance: 16.0,
+            last_output_query: Instant::now(),
+        }
+    }
+}
+
+/// A fixture's output reduced to what the beam renderer needs
+struct Beam {
+    origin: Vec3,
+    end: Vec3,
+    radius: f32,
+    color: Color32,
+    intensity: f32,
+}
+
+impl PrevisPanel {
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        // Fixture values only reach the UI on request, so poll while the panel is visible
+        if self.last_output_query.elapsed() >= OUTPUT_POLL_INTERVAL {
+            let _ = console_tx.send(ConsoleCommand::QueryFixtures);
+            self.last_output_query = Instant::now();
+        }
+        ctx.request_repaint_after(OUTPUT_POLL_INTERVAL);
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame::default().fill(Color32::from_gray(8)))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Visualizer");
+                    ui.label("Drag to orbit, scroll to zoom");
+                    if ui.button("Reset View").clicked() {
+                        *self = Self {
+                            last_output_query: self.last_output_query,
+                            ..Self::default()
+                        };
+                    }
+                });
+
+                let (rect, response) =
+                    ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+                if response.dragged() {
+                    let delta = response.drag_delta();
+                    self.yaw -= delta.x * 0.01;
+                    self.pitch = (self.pitch + delta.y * 0.01).clamp(0.05, 1.5);
+                }
+                if response.hovered() {
+                    let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+                    self.distance = (self.distance * (1.0 - scroll * 0.002)).clamp(3.0, 60.0);
+                }
+
+                self.render_scene(ui, rect, state);
+            });
+    }
+
+    fn render_scene(&self, ui: &egui::Ui, rect: Rect, state: &ConsoleState) {
+        let painter = ui.painter_at(rect);
+        let mut fixtures: Vec<&Fixture> = state.fixtures.values().collect();
+        fixtures.sort_by_key(|f| f.id);
+
+        let origins: Vec<Vec3> = fixtures
+            .iter()
+            .enumerate()
+            .map(|(index, fixture)| fixture_origin(fixture, index))
+            .collect();
+        let target = if origins.is_empty() {
+            [0.0, 0.0, 0.0]
+        } else {
+            let count = origins.len() as f32;
+            [
+                origins.iter().map(|o| o[0]).sum::<f32>() / count,
+                origins.iter().map(|o| o[1]).sum::<f32>() / count,
+                1.0,
+            ]
+        };
+        let camera = Camera::orbit(target, self.yaw, self.pitch, self.distance, rect);
+
+        // Floor grid
+        for i in -FLOOR_HALF_SIZE..=FLOOR_HALF_SIZE {
+            let offset = i as f32;
+            let extent = FLOOR_HALF_SIZE as f32;
+            for (a, b) in [
+                (
+                    [target[0] + offset, target[1] - extent, 0.0],
+                    [target[0] + offset, target[1] + extent, 0.0],
+                ),
+                (
+                    [target[0] - extent, target[1] + offset, 0.0],
+                    [target[0] + extent, target[1] + offset, 0.0],
+                ),
+            ] {
+                if let (Some(a), Some(b)) = (camera.project(a), camera.project(b)) {
+                    painter.line_segment([a, b], Stroke::new(1.0, Color32::from_gray(30)));
+                }
+            }
+        }
+
+        // Beams, furthest first so nearer ones draw over them
+        let mut beams: Vec<Beam> = fixtures
+            .iter()
+            .zip(&origins)
+            .filter_map(|(fixture, origin)| fixture_beam(fixture, *origin))
+            .collect();
+        beams.sort_by(|a, b| camera.depth(b.origin).total_cmp(&camera.depth(a.origin)));
+        for beam in &beams {
+            draw_beam(&painter, &camera, beam);
+        }
+
+        // Fixture bodies and labels
+        for (fixture, origin) in fixtures.iter().zip(&origins) {
+            let Some(pos) = camera.project(*origin) else {
+                continue;
+            };
+            let selected = state.selected_fixtures.contains(&fixture.id);
+            painter.circle_filled(pos, 5.0, Color32::from_gray(60));
+            painter.circle_stroke(
+                pos,
+                5.0,
+                Stroke::new(
+                    if selected { 2.0 } else { 1.0 },
+                    if selected {
+                        Color32::YELLOW
+                    } else {
+                        Color32::from_gray(140)
+                    },
+                ),
+            );
+            painter.text(
+                pos + Vec2::new(0.0, -10.0),
+                egui::Align2::CENTER_BOTTOM,
+                &fixture.name,
+                egui::FontId::proportional(10.0),
+                Color32::from_gray(160),
+            );
+        }
+
+        if fixtures.is_empty() {
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "No fixtures patched",
+                egui::FontId::proportional(14.0),
+                Color32::from_gray(100),
+            );
+        }
+    }
+}
+
+fn draw_beam(painter: &egui::Painter, camera: &Camera, beam: &Beam) {
+    let (Some(apex), Some(end)) = (camera.project(beam.origin), camera.project(beam.end)) else {
+        return;
+    };
+    let Some(edge) = camera.project(add(beam.end, scale(camera.right, beam.radius))) else {
+        return;
+    };
+    let screen_radius = end.distance(edge).max(1.0);
+    let along = end - apex;
+    let perp = if along.length() > f32::EPSILON {
+        Vec2::new(-along.y, along.x).normalized() * screen_radius
+    } else {
+        Vec2::ZERO
+    };
+
+    let alpha = (beam.intensity * 90.0) as u8;
+    let fill =
+        Color32::from_rgba_unmultiplied(beam.color.r(), beam.color.g(), beam.color.b(), alpha);
+    painter.add(egui::Shape::convex_polygon(
+        vec![apex, end + perp, end - perp],
+        fill,
+        Stroke::NONE,
+    ));
+
+    // Pool of light where the beam lands
+    let pool_alpha = (beam.intensity * 160.0) as u8;
+    painter.circle_filled(
+        end,
+        screen_radius,
+        Color32::from_rgba_unmultiplied(beam.color.r(), beam.color.g(), beam.color.b(), pool_alpha),
+    );
+}
+
+fn fixture_origin(fixture: &Fixture, index: usize) -> Vec3 {
+    match fixture.position {
+        Some(position) => [position.x as f32, position.y as f32, position.z as f32],
+        // Unplaced fixtures line up along X in patch order, like spatial effect distribution
+        None => [index as f32, 0.0, DEFAULT_TRIM_HEIGHT],
+    }
+}
+
+fn channel_value(fixture: &Fixture, channel_type: &ChannelType) -> Option<u8> {
+    fixture
+        .channels
+        .iter()
+        .find(|c| c.channel_type == *channel_type)
+        .map(|c| c.value)
+}
+
+fn fixture_beam(fixture: &Fixture, origin: Vec3) -> Option<Beam> {
+    if matches!(
+        fixture.profile.fixture_type,
+        FixtureType::PixelBar | FixtureType::Smoke
+    ) {
+        return None;
+    }
+
+    let color = fixture_color(fixture);
+    let intensity = match channel_value(fixture, &ChannelType::Dimmer) {
+        Some(dimmer) => dimmer as f32 / 255.0,
+        // Fixtures without a dimmer are as bright as their brightest emitter
+        None => color.r().max(color.g()).max(color.b()) as f32 / 255.0,
+    };
+    if intensity <= 0.0 {
+        return None;
+    }
+
+    let direction = match (
+        channel_value(fixture, &ChannelType::Pan),
+        channel_value(fixture, &ChannelType::Tilt),
+    ) {
+        (Some(pan), Some(tilt)) => {
+            // Typical 540° pan and 270° tilt ranges, centred on straight down
+            let pan = (pan as f32 / 255.0 - 0.5) * 540f32.to_radians();
+            let tilt = (tilt as f32 / 255.0 - 0.5) * 270f32.to_radians();
+            [tilt.sin() * pan.cos(), tilt.sin() * pan.sin(), -tilt.cos()]
+        }
+        _ => [0.0, 0.0, -1.0],
+    };
+
+    // Stop at the floor when the beam points down
+    let length = if direction[2] < -0.01 {
+        (origin[2] / -direction[2]).min(MAX_BEAM_LENGTH)
+    } else {
+        MAX_BEAM_LENGTH
+    };
+    let half_angle = match channel_value(fixture, &ChannelType::Zoom) {
+        Some(zoom) => (4.0 + zoom as f32 / 255.0 * 26.0).to_radians(),
+        None => 12f32.to_radians(),
+    };
+
+    Some(Beam {
+        origin,
+        end: add(origin, scale(direction, length.max(0.0))),
+        radius: length * half_angle.tan(),
+        color,
+        intensity,
+    })
+}
+
+fn fixture_color(fixture: &Fixture) -> Color32 {
+    let value = |channel_type| channel_value(fixture, &channel_type);
+
+    if let (Some(red), Some(green), Some(blue)) = (
+        value(ChannelType::Red),
+        value(ChannelType::Green),
+        value(ChannelType::Blue),
+    ) {
+        let white = value(ChannelType::White).unwrap_or(0);
+        return Color32::from_rgb(
+            red.saturating_add(white),
+            green.saturating_add(white),
+            blue.saturating_add(white),
+        );
+    }
+    if let (Some(cyan), Some(magenta), Some(yellow)) = (
+        value(ChannelType::Cyan),
+        value(ChannelType::Magenta),
+        value(ChannelType::Yellow),
+    ) {
+        return Color32::from_rgb(255 - cyan, 255 - magenta, 255 - yellow);
+    }
+    if let Some(wheel) = value(ChannelType::Color) {
+        let slots = fixture.profile.color_wheel_slots();
+        if let Some(slot) = slots
+            .iter()
+            .rev()
+            .find(|slot| slot.value <= wheel)
+            .or(slots.first())
+        {
+            let [red, green, blue] = slot.color;
+            return Color32::from_rgb(red, green, blue);
+        }
+    }
+    Color32::WHITE
+}
+
+/// A perspective camera orbiting a target point
+struct Camera {
+    eye: Vec3,
+    forward: Vec3,
+    right: Vec3,
+    up: Vec3,
+    center: Pos2,
+    focal_length: f32,
+}
+
+impl Camera {
+    fn orbit(target: Vec3, yaw: f32, pitch: f32, distance: f32, rect: Rect) -> Self {
+        let eye = [
+            target[0] + distance * pitch.cos() * yaw.cos(),
+            target[1] + distance * pitch.cos() * yaw.sin(),
+            target[2] + distance * pitch.sin(),
+        ];
+        let forward = normalize(sub(target, eye));
+        let right = normalize(cross(forward, [0.0, 0.0, 1.0]));
+        let up = cross(right, forward);
+        Self {
+            eye,
+            forward,
+            right,
+            up,
+            center: rect.center(),
+            focal_length: rect.height().min(rect.width()),
+        }
+    }
+
+    fn depth(&self, point: Vec3) -> f32 {
+        dot(sub(point, self.eye), self.forward)
+    }
+
+    fn project(&self, point: Vec3) -> Option<Pos2> {
+        let relative = sub(point, self.eye);
+        let depth = dot(relative, self.forward);
+        if depth < 0.1 {
+            return None;
+        }
+        let x = dot(relative, self.right) / depth * self.focal_length;
+        let y = dot(relative, self.up) / depth * self.focal_length;
+        Some(self.center + Vec2::new(x, -y))
+    }
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: Vec3, s: f32) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: Vec3) -> Vec3 {
+    let length = dot(a, a).sqrt();
+    if length > f32::EPSILON {
+        scale(a, 1.0 / length)
+    } else {
+        a
+    }
+}
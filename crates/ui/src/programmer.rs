@@ -1,17 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::f64::consts::PI;
 
 use eframe::egui::{self, Color32, Pos2, Rect, Sense, Stroke, Vec2};
 use egui_plot::{Line, Plot, PlotPoints};
 use halo_core::{
-    ConsoleCommand, EffectDistribution, EffectType, Interval, PixelEffect, PixelEffectParams,
-    PixelEffectScope, PixelEffectType,
+    ConsoleCommand, EffectDirection, EffectDistribution, EffectType, GroupSelectMode, Interval,
+    MidiMappingAction, PixelBlendMode, PixelEffect, PixelEffectParams, PixelEffectScope,
+    PixelEffectType, PixelLayer, PixelText, PresetType, ScrollDirection, SpatialAxis, Subdivision,
+    SubdivisionModifier,
 };
 use halo_fixtures::FixtureType;
 use tokio::sync::mpsc;
 
+use crate::color_picker::ColorPicker;
 use crate::state::ConsoleState;
 
+const QUICK_SELECT_COUNT: usize = 10;
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum ActiveProgrammerTab {
     Intensity,
@@ -21,6 +26,15 @@ pub enum ActiveProgrammerTab {
     PixelEffects,
 }
 
+/// How dragging a parameter changes the selected fixtures
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncoderMode {
+    /// Every fixture jumps to the slider position
+    Absolute,
+    /// Every fixture moves by the drag distance from its own value
+    Relative,
+}
+
 #[derive(Debug, Clone)]
 pub struct TabEffectConfig {
     pub effect_waveform: u8,
@@ -30,6 +44,8 @@ pub struct TabEffectConfig {
     pub effect_distribution: u8,
     pub effect_step_value: usize,
     pub effect_wave_offset: f32,
+    pub effect_spatial_axis: SpatialAxis,
+    pub effect_spatial_spread: f32,
     // Channel selection for position effects
     pub pan_selected: bool,
     pub tilt_selected: bool,
@@ -45,6 +61,8 @@ impl Default for TabEffectConfig {
             effect_distribution: 0,
             effect_step_value: 1,
             effect_wave_offset: 0.0,
+            effect_spatial_axis: SpatialAxis::X,
+            effect_spatial_spread: 1.0,
             pan_selected: true,
             tilt_selected: true,
         }
@@ -55,7 +73,13 @@ pub struct ProgrammerState {
     pub new_cue_name: String,
     selected_fixtures: Vec<usize>,
     params: HashMap<String, f32>,
+    // Params changed since they were last sent, so untouched channels stay out of the programmer
+    dirty_params: HashSet<String>,
+    encoder_mode: EncoderMode,
+    encoder_sensitivity: HashMap<String, f32>,
     color_presets: Vec<Color32>,
+    picked_color: Color32,
+    color_picker: ColorPicker,
     active_tab: ActiveProgrammerTab,
     tab_effects: HashMap<ActiveProgrammerTab, TabEffectConfig>,
     preview_mode: bool,
@@ -64,10 +88,26 @@ pub struct ProgrammerState {
     pixel_effect_type: usize,
     pixel_effect_scope: usize,
     pixel_effect_color: [f32; 3],
+    pixel_effect_text: String,
+    pixel_effect_scroll_direction: ScrollDirection,
+    pixel_effect_layer: PixelLayer,
+    // None runs the effect once a beat
+    pixel_effect_subdivision: Option<Subdivision>,
+    // Group selection state
+    group_select_mode: GroupSelectMode,
+    new_group_name: String,
+    // Copy parameters state, families follow PresetType::value_types()
+    copy_source: Option<usize>,
+    copy_families: [bool; 4],
+    mirror_tilt: bool,
+    fan_channel: String,
+    fan_start: u8,
+    fan_end: u8,
     // Modal dialog state
     show_record_dialog: bool,
     record_dialog_cue_name: String,
     record_dialog_cue_list_index: usize,
+    record_changed_only: bool,
 }
 
 impl Default for ProgrammerState {
@@ -111,7 +151,12 @@ impl Default for ProgrammerState {
             new_cue_name: String::new(),
             selected_fixtures: Vec::new(),
             params,
+            dirty_params: HashSet::new(),
+            encoder_mode: EncoderMode::Absolute,
+            encoder_sensitivity: HashMap::new(),
             color_presets,
+            picked_color: Color32::from_rgb(255, 127, 0),
+            color_picker: ColorPicker::default(),
             active_tab: ActiveProgrammerTab::Intensity,
             tab_effects,
             preview_mode: false,
@@ -120,10 +165,23 @@ impl Default for ProgrammerState {
             pixel_effect_type: 0,                // Chase
             pixel_effect_scope: 1,               // Individual
             pixel_effect_color: [1.0, 1.0, 1.0], // White
+            pixel_effect_text: String::new(),
+            pixel_effect_scroll_direction: ScrollDirection::Left,
+            pixel_effect_layer: PixelLayer::default(),
+            pixel_effect_subdivision: None,
+            group_select_mode: GroupSelectMode::Replace,
+            new_group_name: String::new(),
+            copy_source: None,
+            copy_families: [true; 4],
+            mirror_tilt: false,
+            fan_channel: "dimmer".to_string(),
+            fan_start: 0,
+            fan_end: 255,
             // Modal dialog defaults
             show_record_dialog: false,
             record_dialog_cue_name: String::new(),
             record_dialog_cue_list_index: 0,
+            record_changed_only: false,
         }
     }
 }
@@ -166,6 +224,7 @@ impl ProgrammerState {
     pub fn set_param(&mut self, param_name: &str, value: f32) {
         if let Some(param) = self.params.get_mut(param_name) {
             *param = value;
+            self.dirty_params.insert(param_name.to_string());
         }
     }
 
@@ -198,17 +257,37 @@ impl ProgrammerState {
                             let _ = console_tx.send(ConsoleCommand::RecordProgrammerToCue {
                                 cue_name: self.new_cue_name.clone(),
                                 list_index: None,
+                                changed_only: self.record_changed_only,
                             });
                         }
                     }
+                    ui.checkbox(&mut self.record_changed_only, "Changed only")
+                        .on_hover_text("Skip values that match the running cues");
 
-                    if ui.button("CLEAR").clicked() {
-                        // Clear the programmer
-                        let _ = console_tx.send(ConsoleCommand::ClearProgrammer);
+                    if ui
+                        .button("CLEAR")
+                        .on_hover_text("Clears intensity, then color, then everything")
+                        .clicked()
+                    {
+                        // Clear the programmer one stage at a time
+                        let _ = console_tx.send(ConsoleCommand::ClearProgrammerStaged);
                     }
 
-                    if ui.button("HIGHLIGHT").clicked() {
-                        // Highlight function would go here
+                    Self::render_highlight_buttons(ui, state, console_tx);
+                    Self::render_undo_button(ui, state, console_tx);
+                    Self::render_capture_button(ui, state, console_tx);
+
+                    let relative = self.encoder_mode == EncoderMode::Relative;
+                    if ui
+                        .add(egui::Button::new(if relative { "REL" } else { "ABS" }).selected(relative))
+                        .on_hover_text("Absolute sliders jump to position, relative sliders move each fixture from its own value")
+                        .clicked()
+                    {
+                        self.encoder_mode = if relative {
+                            EncoderMode::Absolute
+                        } else {
+                            EncoderMode::Relative
+                        };
                     }
 
                     // If the preview button is toggled on, enter preview mode
@@ -229,6 +308,9 @@ impl ProgrammerState {
                 });
             });
 
+            self.render_group_bar(ui, state, console_tx);
+            self.render_quick_select_bar(ui, state, console_tx);
+
             // Only show the rest of the programmer if not collapsed
             if !self.collapsed {
                 // Programmer tabs
@@ -246,7 +328,9 @@ impl ProgrammerState {
                 ui.horizontal(|ui| {
                     ui.vertical(|ui| match self.active_tab {
                         ActiveProgrammerTab::Intensity => self.show_intensity_tab(ui, console_tx),
-                        ActiveProgrammerTab::Color => self.show_color_tab(ui, console_tx),
+                        ActiveProgrammerTab::Color => {
+                            self.show_color_tab(ui, state, console_tx)
+                        }
                         ActiveProgrammerTab::Position => self.show_position_tab(ui, console_tx),
                         ActiveProgrammerTab::Beam => self.show_beam_tab(ui, console_tx),
                         ActiveProgrammerTab::PixelEffects => {
@@ -333,9 +417,6 @@ impl ProgrammerState {
         state: &ConsoleState,
         console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
     ) {
-        // Sync all current dashboard programmer values to console
-        self.sync_all_values_to_console(console_tx);
-
         // Render the record dialog if needed
         self.render_record_dialog(ctx, state, console_tx);
 
@@ -351,6 +432,20 @@ impl ProgrammerState {
                             let _ = console_tx.send(ConsoleCommand::ClearProgrammer);
                         }
 
+                        ui.menu_button("CLEAR…", |ui| {
+                            for family in PresetType::value_types() {
+                                if ui.button(family.as_str()).clicked() {
+                                    let _ = console_tx
+                                        .send(ConsoleCommand::ClearProgrammerFamily { family });
+                                    ui.close();
+                                }
+                            }
+                        });
+
+                        Self::render_highlight_buttons(ui, state, console_tx);
+                        Self::render_undo_button(ui, state, console_tx);
+                        Self::render_capture_button(ui, state, console_tx);
+
                         if ui.button("RECORD TO CUE").clicked() {
                             // Open the record dialog
                             self.show_record_dialog = true;
@@ -361,15 +456,341 @@ impl ProgrammerState {
 
                 ui.separator();
 
+                self.render_group_bar(ui, state, console_tx);
+                self.render_quick_select_bar(ui, state, console_tx);
+                self.render_copy_bar(ui, state, console_tx);
+                self.render_fan_bar(ui, console_tx);
+                ui.separator();
+
                 // Parameter grid
                 ui.heading("Programmer Values");
-                self.render_parameter_grid(ui, state);
+                self.render_parameter_grid(ui, state, console_tx);
 
                 ui.add_space(20.0);
 
                 // Effects summary
-                self.render_effects_summary(ui, state);
+                self.render_effects_summary(ui, state, console_tx);
+            });
+        });
+    }
+
+    // Highlight drives the selection to open white for focusing; lowlight dims the rest
+    fn render_highlight_buttons(
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        if ui
+            .add(egui::Button::new("LOWLIGHT").selected(state.programmer_lowlight))
+            .clicked()
+        {
+            let _ = console_tx.send(ConsoleCommand::SetProgrammerHighlight {
+                highlight: state.programmer_highlight,
+                lowlight: !state.programmer_lowlight,
             });
+        }
+
+        if ui
+            .add(egui::Button::new("HIGHLIGHT").selected(state.programmer_highlight))
+            .clicked()
+        {
+            let _ = console_tx.send(ConsoleCommand::SetProgrammerHighlight {
+                highlight: !state.programmer_highlight,
+                lowlight: state.programmer_lowlight,
+            });
+        }
+    }
+
+    fn render_capture_button(
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        if ui
+            .add_enabled(
+                !state.selected_fixtures.is_empty(),
+                egui::Button::new("CAPTURE"),
+            )
+            .on_hover_text("Load the selected fixtures' live output into the programmer")
+            .clicked()
+        {
+            let _ = console_tx.send(ConsoleCommand::CaptureLiveOutput {
+                fixture_ids: state.selected_fixtures.clone(),
+            });
+        }
+    }
+
+    fn render_undo_button(
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let response = ui.add_enabled(
+            state.programmer_next_undo.is_some(),
+            egui::Button::new("UNDO"),
+        );
+        let response = match &state.programmer_next_undo {
+            Some(action) => response.on_hover_text(format!("Undo {action}")),
+            None => response,
+        };
+        if response.clicked() {
+            let _ = console_tx.send(ConsoleCommand::UndoProgrammer);
+        }
+    }
+
+    // Group buttons select a whole fixture group; the +/- modifiers (or shift/alt-click)
+    // add to or remove from the current selection for the next press
+    fn render_group_bar(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Groups:");
+            let mut add = self.group_select_mode == GroupSelectMode::Add;
+            if ui.toggle_value(&mut add, "+").clicked() {
+                self.group_select_mode = if add {
+                    GroupSelectMode::Add
+                } else {
+                    GroupSelectMode::Replace
+                };
+            }
+            let mut remove = self.group_select_mode == GroupSelectMode::Remove;
+            if ui.toggle_value(&mut remove, "−").clicked() {
+                self.group_select_mode = if remove {
+                    GroupSelectMode::Remove
+                } else {
+                    GroupSelectMode::Replace
+                };
+            }
+            if ui
+                .add_enabled(self.selected_fixtures.len() > 1, egui::Button::new("⇄"))
+                .on_hover_text("Reverse selection order")
+                .clicked()
+            {
+                let _ = console_tx.send(ConsoleCommand::ReverseSelectionOrder);
+            }
+
+            // Step through the selection one fixture at a time
+            let can_step = !self.selected_fixtures.is_empty() || state.selection_step.is_some();
+            if ui
+                .add_enabled(can_step, egui::Button::new("◀"))
+                .on_hover_text("Previous fixture")
+                .clicked()
+            {
+                let _ = console_tx.send(ConsoleCommand::StepSelection { forward: false });
+            }
+            if let Some((index, count)) = state.selection_step {
+                ui.label(format!("{}/{count}", index + 1));
+            }
+            if ui
+                .add_enabled(can_step, egui::Button::new("▶"))
+                .on_hover_text("Next fixture")
+                .clicked()
+            {
+                let _ = console_tx.send(ConsoleCommand::StepSelection { forward: true });
+            }
+            if state.selection_step.is_some() && ui.button("All").clicked() {
+                let _ = console_tx.send(ConsoleCommand::RestoreSelection);
+            }
+            ui.separator();
+
+            for group in &state.fixture_groups {
+                let response = ui
+                    .button(&group.name)
+                    .on_hover_text(format!("{} fixtures", group.fixture_ids.len()));
+                if response.clicked() {
+                    let modifiers = ui.input(|i| i.modifiers);
+                    let mode = if modifiers.shift {
+                        GroupSelectMode::Add
+                    } else if modifiers.alt {
+                        GroupSelectMode::Remove
+                    } else {
+                        self.group_select_mode
+                    };
+                    let _ = console_tx.send(ConsoleCommand::SelectFixtureGroup {
+                        group_id: group.id,
+                        mode,
+                    });
+                    self.group_select_mode = GroupSelectMode::Replace;
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Delete Group").clicked() {
+                        let _ = console_tx
+                            .send(ConsoleCommand::DeleteFixtureGroup { group_id: group.id });
+                        ui.close();
+                    }
+                });
+            }
+
+            ui.separator();
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_group_name)
+                    .hint_text("Group name")
+                    .desired_width(100.0),
+            );
+            if ui
+                .add_enabled(
+                    !self.selected_fixtures.is_empty() && !self.new_group_name.is_empty(),
+                    egui::Button::new("Store Group"),
+                )
+                .clicked()
+            {
+                let _ = console_tx.send(ConsoleCommand::StoreFixtureGroup {
+                    name: std::mem::take(&mut self.new_group_name),
+                    fixture_ids: self.selected_fixtures.clone(),
+                });
+            }
+        });
+    }
+
+    // Numbered selection buttons: click recalls, empty or shift-click stores the selection
+    fn render_quick_select_bar(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Quick:");
+            for number in 1..=QUICK_SELECT_COUNT {
+                let stored = state.quick_selects.iter().find(|q| q.number == number);
+                let button = egui::Button::new(number.to_string())
+                    .min_size(Vec2::new(28.0, 0.0))
+                    .selected(stored.is_some_and(|q| q.fixture_ids == self.selected_fixtures));
+                let response = ui.add(button).on_hover_text(match stored {
+                    Some(q) => {
+                        format!("{} fixtures, shift-click to overwrite", q.fixture_ids.len())
+                    }
+                    None => "Empty, click to store the selection".to_string(),
+                });
+                if response.clicked() {
+                    let store = stored.is_none() || ui.input(|i| i.modifiers.shift);
+                    if !store {
+                        let _ = console_tx.send(ConsoleCommand::RecallQuickSelect { number });
+                    } else if !self.selected_fixtures.is_empty() {
+                        let _ = console_tx.send(ConsoleCommand::StoreQuickSelect {
+                            number,
+                            fixture_ids: self.selected_fixtures.clone(),
+                        });
+                    }
+                }
+                if stored.is_some() {
+                    response.context_menu(|ui| {
+                        // Saved with the other learned mappings, the next control moved
+                        if ui.button("Learn MIDI Control").clicked() {
+                            let _ = console_tx.send(ConsoleCommand::StartMidiLearn {
+                                action: MidiMappingAction::RecallQuickSelect(number),
+                            });
+                            ui.close();
+                        }
+                        if ui.button("Delete").clicked() {
+                            let _ = console_tx.send(ConsoleCommand::DeleteQuickSelect { number });
+                            ui.close();
+                        }
+                    });
+                }
+            }
+        });
+    }
+
+    // Fan a channel across the selection in the order fixtures were selected
+    fn render_fan_bar(
+        &mut self,
+        ui: &mut egui::Ui,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        const FAN_CHANNELS: [&str; 8] = [
+            "dimmer", "pan", "tilt", "red", "green", "blue", "white", "zoom",
+        ];
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Fan:");
+            egui::ComboBox::from_id_salt("fan_channel")
+                .selected_text(self.fan_channel.clone())
+                .show_ui(ui, |ui| {
+                    for channel in FAN_CHANNELS {
+                        ui.selectable_value(&mut self.fan_channel, channel.to_string(), channel);
+                    }
+                });
+            ui.add(egui::DragValue::new(&mut self.fan_start).prefix("from "));
+            ui.add(egui::DragValue::new(&mut self.fan_end).prefix("to "));
+            if ui
+                .add_enabled(
+                    self.selected_fixtures.len() > 1,
+                    egui::Button::new("Fan Selection"),
+                )
+                .clicked()
+            {
+                let _ = console_tx.send(ConsoleCommand::FanProgrammerValue {
+                    fixture_ids: self.selected_fixtures.clone(),
+                    channel: self.fan_channel.clone(),
+                    start: self.fan_start,
+                    end: self.fan_end,
+                });
+            }
+        });
+    }
+
+    // Copy one fixture's current values into the programmer for the selected fixtures
+    fn render_copy_bar(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let mut fixtures: Vec<_> = state.fixtures.values().collect();
+        fixtures.sort_by_key(|f| f.id);
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Copy:");
+            let source_name = self
+                .copy_source
+                .and_then(|id| state.fixtures.get(&id.to_string()))
+                .map(|f| f.name.clone())
+                .unwrap_or_else(|| "Source fixture".to_string());
+            egui::ComboBox::from_id_salt("copy_source")
+                .selected_text(source_name)
+                .show_ui(ui, |ui| {
+                    for fixture in &fixtures {
+                        ui.selectable_value(
+                            &mut self.copy_source,
+                            Some(fixture.id),
+                            format!("{} ({})", fixture.name, fixture.id),
+                        );
+                    }
+                });
+
+            for (family, enabled) in PresetType::value_types()
+                .iter()
+                .zip(self.copy_families.iter_mut())
+            {
+                ui.checkbox(enabled, family.as_str());
+            }
+
+            let can_copy = self.copy_source.is_some()
+                && !self.selected_fixtures.is_empty()
+                && self.copy_families.iter().any(|enabled| *enabled);
+            if ui
+                .add_enabled(can_copy, egui::Button::new("@ Selection"))
+                .on_hover_text("Copy the source fixture's values to the selected fixtures")
+                .clicked()
+            {
+                if let Some(source_fixture_id) = self.copy_source {
+                    let families = PresetType::value_types()
+                        .into_iter()
+                        .zip(self.copy_families)
+                        .filter(|(_, enabled)| *enabled)
+                        .map(|(family, _)| family)
+                        .collect();
+                    let _ = console_tx.send(ConsoleCommand::CopyFixtureParameters {
+                        source_fixture_id,
+                        target_fixture_ids: self.selected_fixtures.clone(),
+                        families,
+                    });
+                }
+            }
         });
     }
 
@@ -419,7 +840,7 @@ impl ProgrammerState {
 
                         // Background
                         ui.painter()
-                            .rect_filled(response.rect, 4.0, Color32::from_gray(30));
+                            .rect_filled(response.rect, 4.0, ui.visuals().extreme_bg_color);
 
                         // Foreground
                         let filled_width = response.rect.width() * progress;
@@ -429,7 +850,7 @@ impl ProgrammerState {
                         );
 
                         ui.painter()
-                            .rect_filled(filled_rect, 4.0, Color32::from_rgb(0, 150, 255));
+                            .rect_filled(filled_rect, 4.0, ui.visuals().selection.bg_fill);
                     }
 
                     ui.end_row();
@@ -438,7 +859,12 @@ impl ProgrammerState {
     }
 
     // Helper method to render the parameter grid
-    fn render_parameter_grid(&self, ui: &mut egui::Ui, state: &ConsoleState) {
+    fn render_parameter_grid(
+        &self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
         if state.programmer_values.is_empty() {
             ui.label("No values in programmer");
             return;
@@ -480,7 +906,27 @@ impl ProgrammerState {
                     // Header row
                     ui.label("Fixture");
                     for channel in &sorted_channels {
-                        ui.label(channel);
+                        ui.label(channel)
+                            .on_hover_text("Right-click to knock out")
+                            .context_menu(|ui| {
+                                if ui
+                                    .button(format!("Off: {channel} (all fixtures)"))
+                                    .clicked()
+                                {
+                                    let fixture_ids = state
+                                        .programmer_values
+                                        .keys()
+                                        .filter(|(_, c)| c == channel)
+                                        .map(|(id, _)| *id)
+                                        .collect();
+                                    let _ =
+                                        console_tx.send(ConsoleCommand::KnockoutProgrammerValue {
+                                            fixture_ids,
+                                            channel: channel.clone(),
+                                        });
+                                    ui.close();
+                                }
+                            });
                     }
                     ui.end_row();
 
@@ -502,13 +948,31 @@ impl ProgrammerState {
                                 ui.label(fixture_name);
 
                                 for channel in &sorted_channels {
-                                    let value = state
+                                    let Some(value) = state
                                         .programmer_values
                                         .get(&(*fixture_id, channel.clone()))
                                         .copied()
-                                        .unwrap_or(0);
-
-                                    self.render_parameter_cell(ui, channel, value);
+                                    else {
+                                        // Not in the programmer (or knocked out)
+                                        ui.label("");
+                                        continue;
+                                    };
+
+                                    let changed = state
+                                        .programmer_changed
+                                        .contains(&(*fixture_id, channel.clone()));
+                                    self.render_parameter_cell(ui, channel, value, changed)
+                                        .context_menu(|ui| {
+                                            if ui.button(format!("Off: {channel}")).clicked() {
+                                                let _ = console_tx.send(
+                                                    ConsoleCommand::KnockoutProgrammerValue {
+                                                        fixture_ids: vec![*fixture_id],
+                                                        channel: channel.clone(),
+                                                    },
+                                                );
+                                                ui.close();
+                                            }
+                                        });
                                 }
                                 ui.end_row();
                             }
@@ -519,9 +983,15 @@ impl ProgrammerState {
     }
 
     // Helper method to render individual parameter cells
-    fn render_parameter_cell(&self, ui: &mut egui::Ui, channel: &str, value: u8) {
+    fn render_parameter_cell(
+        &self,
+        ui: &mut egui::Ui,
+        channel: &str,
+        value: u8,
+        changed: bool,
+    ) -> egui::Response {
         let cell_size = Vec2::new(60.0, 30.0);
-        let (rect, _response) = ui.allocate_exact_size(cell_size, Sense::hover());
+        let (rect, response) = ui.allocate_exact_size(cell_size, Sense::click());
 
         // Determine if this is a color channel
         let is_color_channel = channel.to_lowercase().contains("red")
@@ -575,7 +1045,8 @@ impl ProgrammerState {
             let progress = value as f32 / 255.0;
 
             // Background
-            ui.painter().rect_filled(rect, 4.0, Color32::from_gray(30));
+            ui.painter()
+                .rect_filled(rect, 4.0, ui.visuals().extreme_bg_color);
             ui.painter().rect_stroke(
                 rect,
                 4.0,
@@ -587,7 +1058,7 @@ impl ProgrammerState {
             let fill_width = rect.width() * progress;
             let fill_rect = Rect::from_min_size(rect.min, Vec2::new(fill_width, rect.height()));
             ui.painter()
-                .rect_filled(fill_rect, 4.0, Color32::from_rgb(0, 150, 255));
+                .rect_filled(fill_rect, 4.0, ui.visuals().selection.bg_fill);
 
             // Value text
             ui.painter().text(
@@ -598,10 +1069,30 @@ impl ProgrammerState {
                 Color32::WHITE,
             );
         }
+
+        // Mark values that differ from what the cue stack is already outputting
+        if changed {
+            ui.painter().circle_filled(
+                rect.right_top() + Vec2::new(-5.0, 5.0),
+                3.0,
+                Color32::from_rgb(255, 170, 0),
+            );
+        }
+
+        response.on_hover_text(if changed {
+            "Changed from cue"
+        } else {
+            "Same as cue"
+        })
     }
 
     // Helper method to render effects summary
-    fn render_effects_summary(&self, ui: &mut egui::Ui, state: &ConsoleState) {
+    fn render_effects_summary(
+        &self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
         ui.heading("EFFECTS");
         ui.separator();
 
@@ -610,13 +1101,42 @@ impl ProgrammerState {
             return;
         }
 
-        for (i, (name, effect_type, fixture_ids)) in state.programmer_effects.iter().enumerate() {
+        for (i, (name, effect_type, fixture_ids, channels, effect_direction)) in
+            state.programmer_effects.iter().enumerate()
+        {
             ui.collapsing(format!("Effect #{}: {}", i + 1, name), |ui| {
                 ui.horizontal(|ui| {
                     ui.vertical(|ui| {
                         ui.label(format!("Name: {}", name));
                         ui.label(format!("Type: {:?}", effect_type));
                         ui.label(format!("Fixtures: {} fixtures", fixture_ids.len()));
+                        ui.label(format!("Channels: {}", channels.join(", ")));
+                        ui.horizontal(|ui| {
+                            for direction in EffectDirection::all() {
+                                if ui
+                                    .selectable_label(
+                                        *effect_direction == direction,
+                                        direction.as_str(),
+                                    )
+                                    .clicked()
+                                {
+                                    let _ = console_tx.send(
+                                        ConsoleCommand::SetProgrammerEffectDirection {
+                                            effect_index: i,
+                                            direction,
+                                        },
+                                    );
+                                }
+                            }
+                        });
+                        if ui.button("Stop").clicked() {
+                            for channel in channels {
+                                let _ = console_tx.send(ConsoleCommand::ClearEffect {
+                                    fixture_ids: fixture_ids.clone(),
+                                    channel_type: channel.clone(),
+                                });
+                            }
+                        }
                     });
 
                     // Add a small waveform preview
@@ -718,6 +1238,11 @@ impl ProgrammerState {
                                 }
                             });
 
+                        ui.add_space(10.0);
+
+                        ui.checkbox(&mut self.record_changed_only, "Changed only")
+                            .on_hover_text("Skip values that match the running cues");
+
                         ui.add_space(20.0);
 
                         ui.horizontal(|ui| {
@@ -727,6 +1252,7 @@ impl ProgrammerState {
                                         console_tx.send(ConsoleCommand::RecordProgrammerToCue {
                                             cue_name: self.record_dialog_cue_name.clone(),
                                             list_index: Some(self.record_dialog_cue_list_index),
+                                            changed_only: self.record_changed_only,
                                         });
                                     self.show_record_dialog = false;
                                     self.record_dialog_cue_name.clear();
@@ -827,8 +1353,30 @@ impl ProgrammerState {
             ui.radio_value(&mut self.pixel_effect_type, 1, "Wave");
             ui.radio_value(&mut self.pixel_effect_type, 2, "Strobe");
             ui.radio_value(&mut self.pixel_effect_type, 3, "Color Cycle");
+            ui.radio_value(&mut self.pixel_effect_type, 4, "Text");
         });
 
+        if self.pixel_effect_type == 4 {
+            ui.add_space(10.0);
+
+            ui.label("Text:");
+            ui.text_edit_singleline(&mut self.pixel_effect_text);
+
+            ui.label("Scroll Direction:");
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut self.pixel_effect_scroll_direction,
+                    ScrollDirection::Left,
+                    "Left",
+                );
+                ui.radio_value(
+                    &mut self.pixel_effect_scroll_direction,
+                    ScrollDirection::Right,
+                    "Right",
+                );
+            });
+        }
+
         ui.add_space(10.0);
 
         ui.label("Scope:");
@@ -844,6 +1392,63 @@ impl ProgrammerState {
             ui.color_edit_button_rgb(&mut self.pixel_effect_color);
         });
 
+        ui.add_space(10.0);
+
+        ui.label("Timing:");
+        ui.horizontal(|ui| {
+            let straight = self
+                .pixel_effect_subdivision
+                .map(|s| s.with_modifier(Default::default()));
+            egui::ComboBox::from_id_salt("pixel_effect_subdivision")
+                .selected_text(match straight {
+                    Some(subdivision) => subdivision.label(),
+                    None => "Every beat".to_string(),
+                })
+                .show_ui(ui, |ui| {
+                    if ui
+                        .selectable_label(straight.is_none(), "Every beat")
+                        .clicked()
+                    {
+                        self.pixel_effect_subdivision = None;
+                    }
+                    let modifier = self
+                        .pixel_effect_subdivision
+                        .map(|s| s.modifier)
+                        .unwrap_or_default();
+                    for preset in Subdivision::presets() {
+                        if ui
+                            .selectable_label(straight == Some(preset), preset.label())
+                            .clicked()
+                        {
+                            self.pixel_effect_subdivision = Some(preset.with_modifier(modifier));
+                        }
+                    }
+                });
+            if let Some(subdivision) = self.pixel_effect_subdivision.as_mut() {
+                for modifier in SubdivisionModifier::all() {
+                    ui.radio_value(&mut subdivision.modifier, modifier, modifier.as_str());
+                }
+            }
+        });
+
+        ui.add_space(10.0);
+
+        ui.label("Layer:");
+        ui.horizontal(|ui| {
+            ui.label("Order");
+            ui.add(egui::DragValue::new(&mut self.pixel_effect_layer.order).range(-16..=16));
+            ui.label("Opacity");
+            ui.add(egui::Slider::new(
+                &mut self.pixel_effect_layer.opacity,
+                0.0..=1.0,
+            ));
+        });
+        ui.horizontal(|ui| {
+            for mode in PixelBlendMode::all() {
+                ui.radio_value(&mut self.pixel_effect_layer.blend_mode, mode, mode.as_str());
+            }
+        });
+
         ui.add_space(20.0);
 
         // Show current settings
@@ -854,6 +1459,7 @@ impl ProgrammerState {
                 1 => "Wave",
                 2 => "Strobe",
                 3 => "Color Cycle",
+                4 => "Text",
                 _ => "Unknown",
             };
             let scope_name = if self.pixel_effect_scope == 0 {
@@ -868,6 +1474,12 @@ impl ProgrammerState {
             );
 
             ui.label(format!("Effect: {} | Scope: {}", effect_name, scope_name));
+            ui.label(format!(
+                "Layer: {} | {} @ {:.0}%",
+                self.pixel_effect_layer.order,
+                self.pixel_effect_layer.blend_mode.as_str(),
+                self.pixel_effect_layer.opacity * 100.0
+            ));
             ui.horizontal(|ui| {
                 ui.label(format!(
                     "Color: RGB({}, {}, {})",
@@ -906,6 +1518,7 @@ impl ProgrammerState {
                     1 => PixelEffectType::Wave,
                     2 => PixelEffectType::Strobe,
                     3 => PixelEffectType::ColorCycle,
+                    4 => PixelEffectType::Text,
                     _ => PixelEffectType::Chase,
                 };
 
@@ -925,12 +1538,22 @@ impl ProgrammerState {
                         interval_ratio: 1.0,
                         phase: 0.0,
                         speed: 1.0,
+                        subdivision: self.pixel_effect_subdivision,
                     },
+                    text: (effect_type == PixelEffectType::Text).then(|| PixelText {
+                        content: self.pixel_effect_text.clone(),
+                        direction: self.pixel_effect_scroll_direction,
+                    }),
+                    layer: self.pixel_effect_layer.clone(),
                 };
 
                 // Send command to apply pixel effect
                 let _ = console_tx.send(ConsoleCommand::AddPixelEffect {
-                    name: format!("Programmer_PixelFX_{}", self.selected_fixtures.len()),
+                    name: format!(
+                        "Programmer_PixelFX_L{}_{}",
+                        self.pixel_effect_layer.order,
+                        self.selected_fixtures.len()
+                    ),
                     fixture_ids: self.selected_fixtures.clone(),
                     effect: pixel_effect,
                     distribution: EffectDistribution::All,
@@ -1000,19 +1623,29 @@ impl ProgrammerState {
             );
 
             if response.dragged() {
-                let mouse_pos = response
-                    .interact_pointer_pos()
-                    .unwrap_or(Pos2::new(0.0, 0.0));
-                let normalized = 1.0 - ((mouse_pos.y - rect.min.y) / slider_height).clamp(0.0, 1.0);
-                value = min + normalized * (max - min);
-                self.set_param(param_name, value);
-                self.update_fixture_values(console_tx);
+                match self.encoder_mode {
+                    EncoderMode::Absolute => {
+                        let mouse_pos = response
+                            .interact_pointer_pos()
+                            .unwrap_or(Pos2::new(0.0, 0.0));
+                        let normalized =
+                            1.0 - ((mouse_pos.y - rect.min.y) / slider_height).clamp(0.0, 1.0);
+                        value = min + normalized * (max - min);
+                        self.set_param(param_name, value);
+                        self.update_fixture_values(console_tx);
+                    }
+                    EncoderMode::Relative => {
+                        let delta = -response.drag_delta().y / slider_height * (max - min);
+                        value = (value + delta).clamp(min, max);
+                        self.adjust_fixture_values(param_name, value, delta, console_tx);
+                    }
+                }
                 changed = true;
             }
 
             // Draw the slider background
             ui.painter()
-                .rect_filled(rect, 4.0, Color32::from_rgb(30, 30, 30));
+                .rect_filled(rect, 4.0, ui.visuals().extreme_bg_color);
 
             // Draw the fill
             let fill_height =
@@ -1051,29 +1684,107 @@ impl ProgrammerState {
             // Draw + and - buttons for some sliders
             if param_name == "dimmer" || param_name == "strobe" {
                 ui.horizontal(|ui| {
+                    let step = (max - min) / 20.0;
                     if ui.button("-").clicked() {
-                        value = (value - (max - min) / 20.0).max(min);
-                        self.set_param(param_name, value);
-                        self.update_fixture_values(console_tx);
+                        value = (value - step).max(min);
+                        if self.encoder_mode == EncoderMode::Relative {
+                            self.adjust_fixture_values(param_name, value, -step, console_tx);
+                        } else {
+                            self.set_param(param_name, value);
+                            self.update_fixture_values(console_tx);
+                        }
                         changed = true;
                     }
 
                     if ui.button("+").clicked() {
-                        value = (value + (max - min) / 20.0).min(max);
-                        self.set_param(param_name, value);
-                        self.update_fixture_values(console_tx);
+                        value = (value + step).min(max);
+                        if self.encoder_mode == EncoderMode::Relative {
+                            self.adjust_fixture_values(param_name, value, step, console_tx);
+                        } else {
+                            self.set_param(param_name, value);
+                            self.update_fixture_values(console_tx);
+                        }
                         changed = true;
                     }
                 });
             }
+
+            // Per-parameter sensitivity only applies to relative moves
+            if self.encoder_mode == EncoderMode::Relative {
+                let sensitivity = self
+                    .encoder_sensitivity
+                    .entry(param_name.to_string())
+                    .or_insert(1.0);
+                if ui
+                    .add(
+                        egui::DragValue::new(sensitivity)
+                            .range(0.05..=10.0)
+                            .speed(0.05)
+                            .prefix("×"),
+                    )
+                    .on_hover_text("Relative sensitivity")
+                    .changed()
+                {
+                    let _ = console_tx.send(ConsoleCommand::SetEncoderSensitivity {
+                        channel: param_name.to_string(),
+                        sensitivity: *sensitivity,
+                    });
+                }
+            }
         });
 
         changed
     }
 
-    fn update_fixture_values(&self, console_tx: &mpsc::UnboundedSender<ConsoleCommand>) {
-        for &fixture_id in &self.selected_fixtures {
-            for (channel, value) in &self.params {
+    // Move each selected fixture from its own value; the local param only tracks the display
+    fn adjust_fixture_values(
+        &mut self,
+        param_name: &str,
+        display_value: f32,
+        delta: f32,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        if let Some(param) = self.params.get_mut(param_name) {
+            *param = display_value;
+        }
+        let _ = console_tx.send(ConsoleCommand::AdjustProgrammerValue {
+            fixture_ids: self.selected_fixtures.clone(),
+            channel: param_name.to_string(),
+            delta,
+        });
+    }
+
+    // Let the console work out which color channels each selected fixture has
+    fn set_fixture_color(
+        &mut self,
+        color: Color32,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        self.picked_color = color;
+        for (param_name, value) in [
+            ("red", color.r()),
+            ("green", color.g()),
+            ("blue", color.b()),
+        ] {
+            if let Some(param) = self.params.get_mut(param_name) {
+                *param = value as f32;
+            }
+        }
+        let _ = console_tx.send(ConsoleCommand::SetProgrammerColor {
+            fixture_ids: self.selected_fixtures.clone(),
+            red: color.r(),
+            green: color.g(),
+            blue: color.b(),
+        });
+    }
+
+    // Send only the params that were touched, so knocked out channels stay out
+    fn update_fixture_values(&mut self, console_tx: &mpsc::UnboundedSender<ConsoleCommand>) {
+        for channel in std::mem::take(&mut self.dirty_params) {
+            let Some(value) = self.params.get(&channel) else {
+                continue;
+            };
+            for &fixture_id in &self.selected_fixtures {
                 let _ = console_tx.send(ConsoleCommand::SetProgrammerValue {
                     fixture_id,
                     channel: channel.clone(),
@@ -1083,23 +1794,6 @@ impl ProgrammerState {
         }
     }
 
-    /// Sync all current dashboard programmer values to the console
-    /// This ensures the full view shows the current state of the dashboard programmer
-    fn sync_all_values_to_console(&self, console_tx: &mpsc::UnboundedSender<ConsoleCommand>) {
-        for &fixture_id in &self.selected_fixtures {
-            for (channel, value) in &self.params {
-                // Only send non-zero values to avoid cluttering the console state
-                if *value > 0.0 {
-                    let _ = console_tx.send(ConsoleCommand::SetProgrammerValue {
-                        fixture_id,
-                        channel: channel.clone(),
-                        value: *value as u8,
-                    });
-                }
-            }
-        }
-    }
-
     // Intensity tab content
     fn show_intensity_tab(
         &mut self,
@@ -1140,6 +1834,7 @@ impl ProgrammerState {
     fn show_color_tab(
         &mut self,
         ui: &mut egui::Ui,
+        state: &ConsoleState,
         console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
     ) {
         ui.horizontal(|ui| {
@@ -1160,6 +1855,18 @@ impl ProgrammerState {
 
             ui.add_space(spacing * 2.0);
 
+            // Color picker, mapped per fixture onto RGB, CMY or a color wheel by the console
+            ui.vertical(|ui| {
+                ui.label("Picker");
+                ui.add_space(5.0);
+                let mut color = self.picked_color;
+                if self.color_picker.show(ui, &mut color, state, console_tx) {
+                    self.set_fixture_color(color, console_tx);
+                }
+            });
+
+            ui.add_space(spacing);
+
             // Color presets
             ui.vertical(|ui| {
                 ui.label("Presets");
@@ -1186,21 +1893,7 @@ impl ProgrammerState {
                             );
 
                             if response.clicked() {
-                                let r = color.r();
-                                let g = color.g();
-                                let b = color.b();
-
-                                self.set_param("red", r as f32);
-                                self.set_param("green", g as f32);
-                                self.set_param("blue", b as f32);
-
-                                if r == g && g == b && r > 200 {
-                                    // White preset also sets white channel for RGBW fixtures
-                                    self.set_param("white", 255.0);
-                                } else {
-                                    self.set_param("white", 0.0);
-                                }
-                                self.update_fixture_values(console_tx);
+                                self.set_fixture_color(*color, console_tx);
                             }
 
                             if (i + 1) % 2 == 0 {
@@ -1241,7 +1934,7 @@ impl ProgrammerState {
 
                 // Draw position grid background
                 ui.painter()
-                    .rect_filled(rect, grid_size / 2.0, Color32::from_gray(30));
+                    .rect_filled(rect, grid_size / 2.0, ui.visuals().extreme_bg_color);
                 ui.painter().rect_stroke(
                     rect,
                     grid_size / 2.0,
@@ -1311,6 +2004,28 @@ impl ProgrammerState {
                     ui.checkbox(&mut tab_effect.tilt_selected, "Tilt");
                 }
             });
+
+            ui.add_space(spacing);
+
+            // Mirror the right half of the selection from the left half
+            ui.vertical(|ui| {
+                ui.label("Symmetry");
+                ui.add_space(5.0);
+                ui.checkbox(&mut self.mirror_tilt, "Include Tilt");
+                if ui
+                    .add_enabled(
+                        self.selected_fixtures.len() >= 2,
+                        egui::Button::new("Mirror Pan"),
+                    )
+                    .on_hover_text("Invert the right half of the selection from the left half")
+                    .clicked()
+                {
+                    let _ = console_tx.send(ConsoleCommand::MirrorProgrammerPositions {
+                        fixture_ids: self.selected_fixtures.clone(),
+                        mirror_tilt: self.mirror_tilt,
+                    });
+                }
+            });
         });
     }
 
@@ -1505,12 +2220,18 @@ impl ProgrammerState {
                     0 => "All",
                     1 => "Step",
                     2 => "Wave",
+                    3 => "Linear",
+                    4 => "Radial",
                     _ => "All",
                 })
                 .show_ui(ui, |ui| {
                     ui.selectable_value(&mut tab_effect.effect_distribution, 0, "All");
                     ui.selectable_value(&mut tab_effect.effect_distribution, 1, "Step");
                     ui.selectable_value(&mut tab_effect.effect_distribution, 2, "Wave");
+                    ui.selectable_value(&mut tab_effect.effect_distribution, 3, "Linear")
+                        .on_hover_text("Travel across fixture positions along an axis");
+                    ui.selectable_value(&mut tab_effect.effect_distribution, 4, "Radial")
+                        .on_hover_text("Ripple outward from the center of the selection");
                 });
 
             // After the Distribution dropdown
@@ -1548,6 +2269,28 @@ impl ProgrammerState {
                         }
                     });
                 }
+                3 | 4 => {
+                    // Spatial distribution
+                    if tab_effect.effect_distribution == 3 {
+                        ui.horizontal(|ui| {
+                            ui.label("Direction:");
+                            for axis in SpatialAxis::all() {
+                                ui.radio_value(
+                                    &mut tab_effect.effect_spatial_axis,
+                                    axis,
+                                    axis.as_str(),
+                                );
+                            }
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("Spread:");
+                        ui.add(
+                            egui::Slider::new(&mut tab_effect.effect_spatial_spread, 0.0..=4.0)
+                                .suffix(" cycles"),
+                        );
+                    });
+                }
                 _ => {}
             }
 
@@ -1598,6 +2341,16 @@ impl ProgrammerState {
                         } else {
                             None
                         },
+                        spatial_axis: if tab_effect.effect_distribution == 3 {
+                            Some(tab_effect.effect_spatial_axis)
+                        } else {
+                            None
+                        },
+                        spatial_spread: if matches!(tab_effect.effect_distribution, 3 | 4) {
+                            Some(tab_effect.effect_spatial_spread)
+                        } else {
+                            None
+                        },
                     });
                 }
             }
@@ -1697,7 +2450,8 @@ impl ProgrammerState {
             .0;
 
         // Draw background
-        ui.painter().rect_filled(rect, 4.0, Color32::from_gray(40));
+        ui.painter()
+            .rect_filled(rect, 4.0, ui.visuals().extreme_bg_color);
 
         // Calculate value position
         let normalized_value = (*value - min) / (max - min);
@@ -1709,7 +2463,7 @@ impl ProgrammerState {
             Vec2::new(rect.width(), 4.0),
         );
         ui.painter()
-            .rect_filled(indicator_rect, 2.0, Color32::from_rgb(0, 150, 255));
+            .rect_filled(indicator_rect, 2.0, ui.visuals().selection.bg_fill);
 
         // Handle input
         if ui.is_rect_visible(rect) {
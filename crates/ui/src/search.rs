@@ -0,0 +1,185 @@
+use eframe::egui::{self, Key, RichText};
+
+use crate::shortcuts::{KeyBindings, ShortcutAction};
+use crate::state::ConsoleState;
+
+const MAX_RESULTS: usize = 12;
+
+/// Something the search palette can jump to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchTarget {
+    Fixture(usize),
+    Group(usize),
+    CueList(usize),
+    Cue { list_index: usize, cue_index: usize },
+}
+
+struct SearchResult {
+    target: SearchTarget,
+    kind: &'static str,
+    label: String,
+    // Lower is a better match
+    rank: u8,
+}
+
+/// Search palette (Cmd+K by default) for finding fixtures, groups, cue lists and cues by name
+#[derive(Default)]
+pub struct SearchPalette {
+    open: bool,
+    query: String,
+    highlighted: usize,
+}
+
+impl SearchPalette {
+    /// Open or close the palette on its shortcut, call this before the other keyboard shortcuts
+    pub fn handle_shortcut(&mut self, ctx: &egui::Context, key_bindings: &KeyBindings) {
+        let Some(shortcut) = key_bindings.get(ShortcutAction::OpenSearch) else {
+            return;
+        };
+        if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+            self.open = !self.open;
+            self.query.clear();
+            self.highlighted = 0;
+        }
+    }
+
+    /// Show the palette, returning what was picked this frame
+    pub fn render(&mut self, ctx: &egui::Context, state: &ConsoleState) -> Option<SearchTarget> {
+        if !self.open {
+            return None;
+        }
+
+        let results = search(state, &self.query);
+        self.highlighted = self.highlighted.min(results.len().saturating_sub(1));
+
+        let (up, down, enter, escape) = ctx.input(|i| {
+            (
+                i.key_pressed(Key::ArrowUp),
+                i.key_pressed(Key::ArrowDown),
+                i.key_pressed(Key::Enter),
+                i.key_pressed(Key::Escape),
+            )
+        });
+        if down && self.highlighted + 1 < results.len() {
+            self.highlighted += 1;
+        }
+        if up {
+            self.highlighted = self.highlighted.saturating_sub(1);
+        }
+
+        let mut picked = None;
+        egui::Window::new("Search")
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .fixed_size([420.0, 0.0])
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text("Search fixtures, groups, cue lists and cues")
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.highlighted = 0;
+                }
+                ui.separator();
+
+                if results.is_empty() {
+                    ui.weak(if self.query.is_empty() {
+                        "Type to search"
+                    } else {
+                        "Nothing found"
+                    });
+                }
+                for (i, result) in results.iter().enumerate() {
+                    let text = format!("{}  {}", result.label, result.kind);
+                    let row = ui.selectable_label(i == self.highlighted, RichText::new(text));
+                    if row.hovered() {
+                        self.highlighted = i;
+                    }
+                    if row.clicked() {
+                        picked = Some(result.target);
+                    }
+                }
+            });
+
+        if enter {
+            picked = picked.or(results.get(self.highlighted).map(|result| result.target));
+        }
+        if picked.is_some() || escape {
+            self.open = false;
+        }
+        picked
+    }
+}
+
+// 0 for a prefix match, 1 for a match anywhere in the name
+fn rank(name: &str, query: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    if name.starts_with(query) {
+        Some(0)
+    } else if name.contains(query) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+fn search(state: &ConsoleState, query: &str) -> Vec<SearchResult> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results = Vec::new();
+    for fixture in state.fixtures.values() {
+        // Fixtures can also be found by number
+        let number_match = query.parse::<usize>().ok() == Some(fixture.id);
+        if let Some(rank) = rank(&fixture.name, &query).or(number_match.then_some(0)) {
+            results.push(SearchResult {
+                target: SearchTarget::Fixture(fixture.id),
+                kind: "Fixture",
+                label: format!("{} ({})", fixture.name, fixture.id),
+                rank,
+            });
+        }
+    }
+    for group in &state.fixture_groups {
+        if let Some(rank) = rank(&group.name, &query) {
+            results.push(SearchResult {
+                target: SearchTarget::Group(group.id),
+                kind: "Group",
+                label: group.name.clone(),
+                rank,
+            });
+        }
+    }
+    for (list_index, cue_list) in state.cue_lists.iter().enumerate() {
+        if let Some(rank) = rank(&cue_list.name, &query) {
+            results.push(SearchResult {
+                target: SearchTarget::CueList(list_index),
+                kind: "Cue List",
+                label: cue_list.name.clone(),
+                rank,
+            });
+        }
+        for (cue_index, cue) in cue_list.cues.iter().enumerate() {
+            if let Some(rank) = rank(&cue.name, &query) {
+                results.push(SearchResult {
+                    target: SearchTarget::Cue {
+                        list_index,
+                        cue_index,
+                    },
+                    kind: "Cue",
+                    label: format!("{} ({})", cue.name, cue_list.name),
+                    rank,
+                });
+            }
+        }
+    }
+
+    results.sort_by(|a, b| a.rank.cmp(&b.rank).then_with(|| a.label.cmp(&b.label)));
+    results.truncate(MAX_RESULTS);
+    results
+}
@@ -16,7 +16,8 @@ enum ClockMode {
 /// This includes:
 /// - A toggable clock that can show either timecode or the system clock.
 /// - The Master BPM display +/- buttons.
-/// - Ableton Link status and connected peers.
+/// - Ableton Link status, peers, tempo and phase, with quantum, start/stop sync and downbeat
+///   controls.
 /// - Large transport controls (GO, HOLD, STOP).
 pub struct SessionPanel {
     // Clock state
@@ -195,6 +196,50 @@ impl SessionPanel {
                                 "No peers connected".to_string()
                             };
                             ui.label(peers_text);
+
+                            if state.link_enabled {
+                                ui.label(format!("{:.1} BPM", state.link_tempo));
+                                draw_link_phase(ui, state.link_phase, state.link_quantum);
+                            }
+
+                            ui.horizontal(|ui| {
+                                ui.label("Quantum");
+                                let mut quantum = state.link_quantum;
+                                if ui
+                                    .add(
+                                        eframe::egui::DragValue::new(&mut quantum)
+                                            .range(1.0..=16.0)
+                                            .speed(0.1)
+                                            .fixed_decimals(0),
+                                    )
+                                    .changed()
+                                {
+                                    let _ = console_tx.send(ConsoleCommand::SetLinkQuantum {
+                                        quantum: quantum.round(),
+                                    });
+                                }
+                            });
+
+                            let mut start_stop_sync = state.link_start_stop_sync;
+                            if ui
+                                .checkbox(&mut start_stop_sync, "Start/stop sync")
+                                .changed()
+                            {
+                                let _ = console_tx.send(ConsoleCommand::SetLinkStartStopSync {
+                                    enabled: start_stop_sync,
+                                });
+                            }
+
+                            if ui
+                                .add_enabled(
+                                    state.link_enabled,
+                                    eframe::egui::Button::new("Re-align Downbeat"),
+                                )
+                                .on_hover_text("Make now beat 1 of the bar for every Link peer")
+                                .clicked()
+                            {
+                                let _ = console_tx.send(ConsoleCommand::RealignLinkDownbeat);
+                            }
                         });
                     });
                 });
@@ -268,3 +313,24 @@ impl SessionPanel {
         });
     }
 }
+
+/// One block per beat of the quantum, lit up to the current beat
+fn draw_link_phase(ui: &mut eframe::egui::Ui, phase: f64, quantum: f64) {
+    let beats = quantum.max(1.0) as usize;
+    let current_beat = phase.floor() as usize;
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 3.0;
+        for beat in 0..beats {
+            let (rect, _) = ui
+                .allocate_exact_size(eframe::egui::vec2(14.0, 10.0), eframe::egui::Sense::hover());
+            let color = if beat == current_beat {
+                Color32::from_rgb(66, 133, 244)
+            } else if beat < current_beat {
+                Color32::from_rgb(40, 70, 120)
+            } else {
+                ui.visuals().extreme_bg_color
+            };
+            ui.painter().rect_filled(rect, 2.0, color);
+        }
+    });
+}
@@ -1,7 +1,11 @@
 use eframe::egui;
-use halo_core::{ConsoleCommand, Settings};
+use halo_core::{
+    ConsoleCommand, DashboardColumn, DashboardLayout, DashboardWidget, MidiMapping,
+    MidiMappingAction, RelativeEncoding, Settings, UiTheme, RTP_MIDI_DEVICE,
+};
 use tokio::sync::mpsc;
 
+use crate::shortcuts::{KeyBindings, ShortcutAction};
 use crate::state::ConsoleState;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -11,6 +15,7 @@ enum SettingsTab {
     Midi,
     Outputs,
     PixelEngine,
+    Shortcuts,
 }
 
 #[derive(Clone)]
@@ -23,6 +28,13 @@ pub struct SettingsPanel {
     pub enable_autosave: bool,
     pub autosave_interval: String,
 
+    // Appearance settings
+    pub theme: UiTheme,
+    pub accent_color: [u8; 3],
+    pub touch_layout: bool,
+    pub performance_overlay: bool,
+    pub dashboard_layout: DashboardLayout,
+
     // Audio settings
     pub audio_device: String,
     pub audio_buffer_size: String,
@@ -32,6 +44,16 @@ pub struct SettingsPanel {
     pub midi_enabled: bool,
     pub midi_device: String,
     pub midi_channel: String,
+    pub rtp_midi_port: String,
+    // Learned by the console, kept here so applying the other settings doesn't drop them
+    pub midi_mappings: Vec<MidiMapping>,
+    midi_learn_action: MidiMappingAction,
+    pub midi_profile: String,
+    pub mtc_input_enabled: bool,
+    pub mtc_offset: String,
+    pub mtc_freewheel: String,
+    pub mtc_output_enabled: bool,
+    pub midi_clock_output_enabled: bool,
 
     // Output settings (DMX/Art-Net)
     pub dmx_enabled: bool,
@@ -49,6 +71,10 @@ pub struct SettingsPanel {
     // Fixture settings
     pub enable_pan_tilt_limits: bool,
 
+    // Keyboard shortcuts, and the action waiting for a key press
+    key_bindings: KeyBindings,
+    capturing_shortcut: Option<ShortcutAction>,
+
     // Internal state
     initialized: bool,
 }
@@ -73,6 +99,15 @@ impl Default for SettingsPanel {
             midi_enabled: false,
             midi_device: "None".to_string(),
             midi_channel: "1".to_string(),
+            rtp_midi_port: "5004".to_string(),
+            midi_mappings: Vec::new(),
+            midi_learn_action: MidiMappingAction::Go,
+            midi_profile: "Akai MPK49".to_string(),
+            mtc_input_enabled: false,
+            mtc_offset: "0".to_string(),
+            mtc_freewheel: "2".to_string(),
+            mtc_output_enabled: false,
+            midi_clock_output_enabled: false,
 
             // Output defaults
             dmx_enabled: true,
@@ -90,6 +125,16 @@ impl Default for SettingsPanel {
             // Fixture defaults
             enable_pan_tilt_limits: true,
 
+            key_bindings: KeyBindings::from_settings(&std::collections::HashMap::new()),
+            capturing_shortcut: None,
+
+            // Appearance defaults
+            theme: Settings::default().theme,
+            accent_color: Settings::default().accent_color,
+            touch_layout: false,
+            performance_overlay: false,
+            dashboard_layout: DashboardLayout::default(),
+
             // Internal state
             initialized: false,
         }
@@ -119,6 +164,13 @@ impl SettingsPanel {
         self.enable_autosave = settings.enable_autosave;
         self.autosave_interval = settings.autosave_interval_secs.to_string();
 
+        // Load appearance settings
+        self.theme = settings.theme;
+        self.accent_color = settings.accent_color;
+        self.touch_layout = settings.touch_layout;
+        self.performance_overlay = settings.performance_overlay;
+        self.dashboard_layout = settings.dashboard_layout.clone();
+
         // Load audio settings
         self.audio_device = settings.audio_device.clone();
         self.audio_buffer_size = settings.audio_buffer_size.to_string();
@@ -128,6 +180,14 @@ impl SettingsPanel {
         self.midi_enabled = settings.midi_enabled;
         self.midi_device = settings.midi_device.clone();
         self.midi_channel = settings.midi_channel.to_string();
+        self.rtp_midi_port = settings.rtp_midi_port.to_string();
+        self.midi_mappings = settings.midi_mappings.clone();
+        self.midi_profile = settings.midi_profile.clone();
+        self.mtc_input_enabled = settings.mtc_input_enabled;
+        self.mtc_offset = settings.mtc_offset_secs.to_string();
+        self.mtc_freewheel = settings.mtc_freewheel_secs.to_string();
+        self.mtc_output_enabled = settings.mtc_output_enabled;
+        self.midi_clock_output_enabled = settings.midi_clock_output_enabled;
 
         // Load output settings
         self.dmx_enabled = settings.dmx_enabled;
@@ -144,6 +204,9 @@ impl SettingsPanel {
 
         // Load fixture settings
         self.enable_pan_tilt_limits = settings.enable_pan_tilt_limits;
+
+        // Load keyboard shortcuts
+        self.key_bindings = KeyBindings::from_settings(&settings.key_bindings);
     }
 
     pub fn render(
@@ -160,6 +223,7 @@ impl SettingsPanel {
         if !self.initialized {
             self.load_from_state(state);
             Self::request_audio_devices(console_tx);
+            let _ = console_tx.send(ConsoleCommand::QueryMidiProfiles);
             self.initialized = true;
         }
 
@@ -201,6 +265,7 @@ impl SettingsPanel {
                 SettingsTab::PixelEngine,
                 "Pixel Engine",
             );
+            ui.selectable_value(&mut self.active_tab, SettingsTab::Shortcuts, "Shortcuts");
         });
 
         ui.separator();
@@ -208,9 +273,10 @@ impl SettingsPanel {
         egui::ScrollArea::vertical().show(ui, |ui| match self.active_tab {
             SettingsTab::General => self.render_general_tab(ui, console_tx),
             SettingsTab::Audio => self.render_audio_tab(ui, state, console_tx),
-            SettingsTab::Midi => self.render_midi_tab(ui, console_tx),
+            SettingsTab::Midi => self.render_midi_tab(ui, state, console_tx),
             SettingsTab::Outputs => self.render_outputs_tab(ui, console_tx),
             SettingsTab::PixelEngine => self.render_pixel_engine_tab(ui, state, console_tx),
+            SettingsTab::Shortcuts => self.render_shortcuts_tab(ui),
         });
 
         ui.separator();
@@ -274,12 +340,123 @@ impl SettingsPanel {
         ui.separator();
         ui.add_space(10.0);
 
+        ui.label("Appearance");
+        ui.add_space(5.0);
+        egui::Grid::new("appearance_settings_grid")
+            .num_columns(2)
+            .spacing([40.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Theme:");
+                egui::ComboBox::from_id_salt("ui_theme")
+                    .selected_text(self.theme.as_str())
+                    .show_ui(ui, |ui| {
+                        for theme in UiTheme::all() {
+                            ui.selectable_value(&mut self.theme, theme, theme.as_str());
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Accent color:");
+                ui.horizontal(|ui| {
+                    ui.color_edit_button_srgb(&mut self.accent_color);
+                    if ui.button("Reset").clicked() {
+                        self.accent_color = Settings::default().accent_color;
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Touchscreen:");
+                ui.checkbox(
+                    &mut self.touch_layout,
+                    "Larger controls, simple cue list and Go bar",
+                );
+                ui.end_row();
+
+                ui.label("Diagnostics:");
+                ui.checkbox(&mut self.performance_overlay, "Show performance overlay");
+                ui.end_row();
+            });
+
+        ui.add_space(20.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Dashboard");
+            if ui.button("Reset").clicked() {
+                self.dashboard_layout = DashboardLayout::default();
+            }
+        });
+        ui.add_space(5.0);
+        self.render_dashboard_layout(ui);
+
+        ui.add_space(20.0);
+        ui.separator();
+        ui.add_space(10.0);
+
         ui.label("Application Information");
         ui.add_space(5.0);
         ui.label(format!("Version: {}", env!("CARGO_PKG_VERSION")));
         ui.label("Halo Lighting Console");
     }
 
+    /// Show, hide and reorder the dashboard widgets, top to bottom within each column
+    fn render_dashboard_layout(&mut self, ui: &mut egui::Ui) {
+        let layout = &mut self.dashboard_layout;
+        let hidden = DashboardWidget::all()
+            .into_iter()
+            .filter(|widget| layout.column_of(*widget).is_none());
+        let widgets: Vec<DashboardWidget> = layout
+            .main
+            .iter()
+            .chain(layout.side.iter())
+            .copied()
+            .chain(hidden)
+            .collect();
+
+        egui::Grid::new("dashboard_layout_grid")
+            .num_columns(3)
+            .spacing([40.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for widget in widgets {
+                    ui.label(widget.as_str());
+
+                    let mut column = layout.column_of(widget);
+                    let column_label = |column: Option<DashboardColumn>| match column {
+                        Some(DashboardColumn::Main) => "Main",
+                        Some(DashboardColumn::Side) => "Side",
+                        None => "Hidden",
+                    };
+                    egui::ComboBox::from_id_salt(("dashboard_widget_column", widget))
+                        .selected_text(column_label(column))
+                        .show_ui(ui, |ui| {
+                            for option in [
+                                Some(DashboardColumn::Main),
+                                Some(DashboardColumn::Side),
+                                None,
+                            ] {
+                                ui.selectable_value(&mut column, option, column_label(option));
+                            }
+                        });
+                    layout.set_column(widget, column);
+
+                    ui.add_enabled_ui(column.is_some(), |ui| {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("▲").on_hover_text("Move up").clicked() {
+                                layout.shift(widget, -1);
+                            }
+                            if ui.small_button("▼").on_hover_text("Move down").clicked() {
+                                layout.shift(widget, 1);
+                            }
+                        });
+                    });
+                    ui.end_row();
+                }
+            });
+    }
+
     fn render_audio_tab(
         &mut self,
         ui: &mut egui::Ui,
@@ -371,7 +548,8 @@ impl SettingsPanel {
     fn render_midi_tab(
         &mut self,
         ui: &mut egui::Ui,
-        _console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
     ) {
         ui.heading("MIDI Settings");
         ui.add_space(10.0);
@@ -396,11 +574,29 @@ impl SettingsPanel {
                                 "Virtual MIDI".to_string(),
                                 "Virtual MIDI",
                             );
+                            ui.selectable_value(
+                                &mut self.midi_device,
+                                RTP_MIDI_DEVICE.to_string(),
+                                RTP_MIDI_DEVICE,
+                            );
                             // In a real implementation, enumerate actual MIDI devices here
                             ui.label("(Available MIDI devices would be listed here)");
                         });
                     ui.end_row();
 
+                    if self.midi_device == RTP_MIDI_DEVICE {
+                        ui.label("RTP-MIDI Port:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.rtp_midi_port)
+                                .desired_width(100.0),
+                        )
+                        .on_hover_text(
+                            "Add this machine's address and port as a session on the other \
+                             computer, data uses the next port up",
+                        );
+                        ui.end_row();
+                    }
+
                     ui.label("MIDI Channel:");
                     egui::ComboBox::from_id_salt("midi_channel")
                         .selected_text(format!("Channel {}", self.midi_channel))
@@ -414,11 +610,208 @@ impl SettingsPanel {
                             }
                         });
                     ui.end_row();
+
+                    ui.label("Controller Profile:");
+                    egui::ComboBox::from_id_salt("midi_profile")
+                        .selected_text(&self.midi_profile)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.midi_profile, "None".to_string(), "None");
+                            for profile in &state.midi_profiles {
+                                ui.selectable_value(
+                                    &mut self.midi_profile,
+                                    profile.clone(),
+                                    profile,
+                                );
+                            }
+                        });
+                    ui.end_row();
+                }
+            });
+
+        ui.label(format!(
+            "Add your own profiles as JSON files in the {} folder.",
+            halo_core::MIDI_PROFILE_DIR
+        ));
+
+        ui.add_space(10.0);
+        ui.label("MIDI Sync");
+        ui.separator();
+        ui.add_space(5.0);
+
+        egui::Grid::new("mtc_settings_grid")
+            .num_columns(2)
+            .spacing([40.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("MTC Input:");
+                ui.checkbox(&mut self.mtc_input_enabled, "Chase MIDI timecode");
+                ui.end_row();
+
+                if self.mtc_input_enabled {
+                    ui.label("Offset (seconds):");
+                    ui.add(egui::TextEdit::singleline(&mut self.mtc_offset).desired_width(100.0));
+                    ui.end_row();
+
+                    ui.label("Freewheel (seconds):");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.mtc_freewheel).desired_width(100.0),
+                    );
+                    ui.end_row();
                 }
+
+                ui.label("MTC Output:");
+                ui.checkbox(&mut self.mtc_output_enabled, "Send MIDI timecode");
+                ui.end_row();
+
+                ui.label("MIDI Clock Output:");
+                ui.checkbox(
+                    &mut self.midi_clock_output_enabled,
+                    "Send MIDI clock at the console tempo",
+                );
+                ui.end_row();
             });
 
         ui.add_space(10.0);
-        ui.label("MIDI Learn and mapping features coming soon.");
+        self.render_midi_mappings(ui, state, console_tx);
+    }
+
+    fn render_midi_mappings(
+        &mut self,
+        ui: &mut egui::Ui,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        // Mappings are learned and removed by the console, so always show its copy
+        self.midi_mappings = state.settings.midi_mappings.clone();
+
+        ui.label("MIDI Mappings");
+        ui.separator();
+        ui.add_space(5.0);
+
+        if self.midi_mappings.is_empty() {
+            ui.label("No controls mapped yet.");
+        } else {
+            egui::Grid::new("midi_mappings_grid")
+                .num_columns(3)
+                .spacing([40.0, 8.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (index, mapping) in self.midi_mappings.iter().enumerate() {
+                        ui.label(mapping.control.label());
+                        ui.label(mapping.action.label());
+                        if ui.button("Remove").clicked() {
+                            let _ = console_tx.send(ConsoleCommand::RemoveMidiMapping { index });
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+
+        ui.add_space(10.0);
+        ui.horizontal(|ui| {
+            ui.label("Learn:");
+            egui::ComboBox::from_id_salt("midi_learn_action")
+                .selected_text(self.midi_learn_action.label())
+                .show_ui(ui, |ui| {
+                    for action in [
+                        MidiMappingAction::Go,
+                        MidiMappingAction::GoBack,
+                        MidiMappingAction::Stop,
+                        MidiMappingAction::Bpm,
+                        MidiMappingAction::FxRateMaster,
+                        MidiMappingAction::FxSizeMaster,
+                        MidiMappingAction::ProgrammerChannel("dimmer".to_string()),
+                        MidiMappingAction::ProgrammerEncoder {
+                            channel: "dimmer".to_string(),
+                            encoding: RelativeEncoding::TwosComplement,
+                        },
+                        MidiMappingAction::RecallQuickSelect(1),
+                        MidiMappingAction::Smoke,
+                        MidiMappingAction::FlashGroup(
+                            state.fixture_groups.first().map_or(0, |group| group.id),
+                        ),
+                    ] {
+                        let selected = std::mem::discriminant(&self.midi_learn_action)
+                            == std::mem::discriminant(&action);
+                        let label = match &action {
+                            MidiMappingAction::ProgrammerChannel(_) => {
+                                "Programmer Parameter".to_string()
+                            }
+                            MidiMappingAction::ProgrammerEncoder { .. } => {
+                                "Programmer Encoder".to_string()
+                            }
+                            MidiMappingAction::RecallQuickSelect(_) => "Quick Select".to_string(),
+                            MidiMappingAction::FlashGroup(_) => "Flash Group".to_string(),
+                            action => action.label(),
+                        };
+                        if ui.selectable_label(selected, label).clicked() && !selected {
+                            self.midi_learn_action = action;
+                        }
+                    }
+                });
+
+            match &mut self.midi_learn_action {
+                MidiMappingAction::ProgrammerChannel(channel) => {
+                    ui.add(
+                        egui::TextEdit::singleline(channel)
+                            .hint_text("dimmer, pan, red...")
+                            .desired_width(100.0),
+                    );
+                }
+                MidiMappingAction::ProgrammerEncoder { channel, encoding } => {
+                    ui.add(
+                        egui::TextEdit::singleline(channel)
+                            .hint_text("dimmer, pan, red...")
+                            .desired_width(100.0),
+                    );
+                    egui::ComboBox::from_id_salt("midi_learn_encoding")
+                        .selected_text(encoding.as_str())
+                        .show_ui(ui, |ui| {
+                            for option in RelativeEncoding::all() {
+                                ui.selectable_value(encoding, option, option.as_str());
+                            }
+                        });
+                }
+                MidiMappingAction::RecallQuickSelect(number) => {
+                    ui.add(egui::DragValue::new(number).range(1..=99));
+                }
+                MidiMappingAction::FlashGroup(group_id) => {
+                    let selected = state
+                        .fixture_groups
+                        .iter()
+                        .find(|group| group.id == *group_id)
+                        .map_or("No groups", |group| group.name.as_str());
+                    egui::ComboBox::from_id_salt("midi_learn_group")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for group in &state.fixture_groups {
+                                ui.selectable_value(group_id, group.id, &group.name);
+                            }
+                        });
+                }
+                _ => {}
+            }
+
+            if state.midi_learn.is_some() {
+                if ui.button("Cancel").clicked() {
+                    let _ = console_tx.send(ConsoleCommand::CancelMidiLearn);
+                }
+            } else if ui.button("Learn").clicked() {
+                let _ = console_tx.send(ConsoleCommand::StartMidiLearn {
+                    action: self.midi_learn_action.clone(),
+                });
+            }
+        });
+
+        if let Some(action) = &state.midi_learn {
+            ui.label(
+                egui::RichText::new(format!(
+                    "Move a control on your MIDI device to map it to {}",
+                    action.label()
+                ))
+                .color(egui::Color32::YELLOW),
+            );
+        }
     }
 
     fn render_outputs_tab(
@@ -590,6 +983,67 @@ impl SettingsPanel {
         ui.label("Pixel effects can be applied through cues or the programmer panel.");
     }
 
+    fn render_shortcuts_tab(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Keyboard Shortcuts");
+        ui.add_space(10.0);
+
+        // Record the next key press for the action being set
+        if let Some(action) = self.capturing_shortcut {
+            let pressed = ui.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some(egui::KeyboardShortcut::new(*modifiers, *key)),
+                    _ => None,
+                })
+            });
+            if let Some(shortcut) = pressed {
+                self.key_bindings.set(action, Some(shortcut));
+                self.capturing_shortcut = None;
+            }
+        }
+
+        egui::Grid::new("shortcut_settings_grid")
+            .num_columns(3)
+            .spacing([40.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                for action in ShortcutAction::all() {
+                    ui.label(action.label());
+                    if self.capturing_shortcut == Some(action) {
+                        ui.label(egui::RichText::new("Press a key…").italics());
+                    } else {
+                        match self.key_bindings.get(action) {
+                            Some(shortcut) => ui.monospace(ui.ctx().format_shortcut(&shortcut)),
+                            None => ui.weak("Unbound"),
+                        };
+                    }
+                    ui.horizontal(|ui| {
+                        if self.capturing_shortcut == Some(action) {
+                            if ui.button("Cancel").clicked() {
+                                self.capturing_shortcut = None;
+                            }
+                        } else if ui.button("Set").clicked() {
+                            self.capturing_shortcut = Some(action);
+                        }
+                        if ui.button("Clear").clicked() {
+                            self.key_bindings.set(action, None);
+                        }
+                        if ui.button("Reset").clicked() {
+                            self.key_bindings.set(action, action.default_shortcut());
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(10.0);
+        ui.label("Shortcuts are ignored while typing in a text field. Apply to save them.");
+    }
+
     fn apply_settings(&self, console_tx: &mpsc::UnboundedSender<ConsoleCommand>) {
         // Convert UI settings to Settings struct
         let settings = Settings {
@@ -604,6 +1058,14 @@ impl SettingsPanel {
             midi_enabled: self.midi_enabled,
             midi_device: self.midi_device.clone(),
             midi_channel: self.midi_channel.parse().unwrap_or(1),
+            rtp_midi_port: self.rtp_midi_port.parse().unwrap_or(5004),
+            midi_mappings: self.midi_mappings.clone(),
+            midi_profile: self.midi_profile.clone(),
+            mtc_input_enabled: self.mtc_input_enabled,
+            mtc_offset_secs: self.mtc_offset.parse().unwrap_or(0.0),
+            mtc_freewheel_secs: self.mtc_freewheel.parse().unwrap_or(2.0),
+            mtc_output_enabled: self.mtc_output_enabled,
+            midi_clock_output_enabled: self.midi_clock_output_enabled,
 
             dmx_enabled: self.dmx_enabled,
             dmx_broadcast: self.dmx_broadcast,
@@ -618,6 +1080,14 @@ impl SettingsPanel {
             pixel_universe_mapping: std::collections::HashMap::new(),
 
             enable_pan_tilt_limits: self.enable_pan_tilt_limits,
+
+            key_bindings: self.key_bindings.to_settings(),
+
+            theme: self.theme,
+            accent_color: self.accent_color,
+            touch_layout: self.touch_layout,
+            performance_overlay: self.performance_overlay,
+            dashboard_layout: self.dashboard_layout.clone(),
         };
 
         // Send update command
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+use halo_core::ConsoleCommand;
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+use crate::ActiveTab;
+
+/// Something a keyboard shortcut can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShortcutAction {
+    Go,
+    GoBack,
+    Stop,
+    ClearProgrammer,
+    UndoProgrammer,
+    RedoProgrammer,
+    ToggleHighlight,
+    NextFixture,
+    PreviousFixture,
+    CaptureOutput,
+    OpenSearch,
+    ShowDashboard,
+    ShowProgrammer,
+    ShowCueEditor,
+    ShowPatch,
+    ShowPresets,
+    ShowVisualizer,
+    ShowShows,
+}
+
+impl ShortcutAction {
+    pub fn all() -> [ShortcutAction; 18] {
+        [
+            ShortcutAction::Go,
+            ShortcutAction::GoBack,
+            ShortcutAction::Stop,
+            ShortcutAction::ClearProgrammer,
+            ShortcutAction::UndoProgrammer,
+            ShortcutAction::RedoProgrammer,
+            ShortcutAction::ToggleHighlight,
+            ShortcutAction::NextFixture,
+            ShortcutAction::PreviousFixture,
+            ShortcutAction::CaptureOutput,
+            ShortcutAction::OpenSearch,
+            ShortcutAction::ShowDashboard,
+            ShortcutAction::ShowProgrammer,
+            ShortcutAction::ShowCueEditor,
+            ShortcutAction::ShowPatch,
+            ShortcutAction::ShowPresets,
+            ShortcutAction::ShowVisualizer,
+            ShortcutAction::ShowShows,
+        ]
+    }
+
+    /// Stable name used in the config file
+    pub fn id(&self) -> &'static str {
+        match self {
+            ShortcutAction::Go => "go",
+            ShortcutAction::GoBack => "go_back",
+            ShortcutAction::Stop => "stop",
+            ShortcutAction::ClearProgrammer => "clear_programmer",
+            ShortcutAction::UndoProgrammer => "undo_programmer",
+            ShortcutAction::RedoProgrammer => "redo_programmer",
+            ShortcutAction::ToggleHighlight => "toggle_highlight",
+            ShortcutAction::NextFixture => "next_fixture",
+            ShortcutAction::PreviousFixture => "previous_fixture",
+            ShortcutAction::CaptureOutput => "capture_output",
+            ShortcutAction::OpenSearch => "open_search",
+            ShortcutAction::ShowDashboard => "show_dashboard",
+            ShortcutAction::ShowProgrammer => "show_programmer",
+            ShortcutAction::ShowCueEditor => "show_cue_editor",
+            ShortcutAction::ShowPatch => "show_patch",
+            ShortcutAction::ShowPresets => "show_presets",
+            ShortcutAction::ShowVisualizer => "show_visualizer",
+            ShortcutAction::ShowShows => "show_shows",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::Go => "Go",
+            ShortcutAction::GoBack => "Go Back",
+            ShortcutAction::Stop => "Stop",
+            ShortcutAction::ClearProgrammer => "Clear Programmer",
+            ShortcutAction::UndoProgrammer => "Undo Programmer",
+            ShortcutAction::RedoProgrammer => "Redo Programmer",
+            ShortcutAction::ToggleHighlight => "Toggle Highlight",
+            ShortcutAction::NextFixture => "Next Fixture",
+            ShortcutAction::PreviousFixture => "Previous Fixture",
+            ShortcutAction::CaptureOutput => "Capture Live Output",
+            ShortcutAction::OpenSearch => "Search",
+            ShortcutAction::ShowDashboard => "Dashboard Tab",
+            ShortcutAction::ShowProgrammer => "Programmer Tab",
+            ShortcutAction::ShowCueEditor => "Cue Editor Tab",
+            ShortcutAction::ShowPatch => "Patch Tab",
+            ShortcutAction::ShowPresets => "Presets Tab",
+            ShortcutAction::ShowVisualizer => "Visualizer Tab",
+            ShortcutAction::ShowShows => "Shows Tab",
+        }
+    }
+
+    pub fn default_shortcut(&self) -> Option<KeyboardShortcut> {
+        let shortcut = match self {
+            ShortcutAction::Go => KeyboardShortcut::new(Modifiers::NONE, Key::Space),
+            ShortcutAction::GoBack => KeyboardShortcut::new(Modifiers::SHIFT, Key::Space),
+            ShortcutAction::Stop => KeyboardShortcut::new(Modifiers::NONE, Key::Escape),
+            ShortcutAction::ClearProgrammer => {
+                KeyboardShortcut::new(Modifiers::COMMAND, Key::Backspace)
+            }
+            ShortcutAction::UndoProgrammer => KeyboardShortcut::new(Modifiers::COMMAND, Key::Z),
+            ShortcutAction::RedoProgrammer => {
+                KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z)
+            }
+            ShortcutAction::ToggleHighlight => KeyboardShortcut::new(Modifiers::NONE, Key::H),
+            ShortcutAction::NextFixture => {
+                KeyboardShortcut::new(Modifiers::NONE, Key::CloseBracket)
+            }
+            ShortcutAction::PreviousFixture => {
+                KeyboardShortcut::new(Modifiers::NONE, Key::OpenBracket)
+            }
+            ShortcutAction::CaptureOutput => return None,
+            ShortcutAction::OpenSearch => KeyboardShortcut::new(Modifiers::COMMAND, Key::K),
+            ShortcutAction::ShowDashboard => KeyboardShortcut::new(Modifiers::NONE, Key::F1),
+            ShortcutAction::ShowProgrammer => KeyboardShortcut::new(Modifiers::NONE, Key::F2),
+            ShortcutAction::ShowCueEditor => KeyboardShortcut::new(Modifiers::NONE, Key::F3),
+            ShortcutAction::ShowPatch => KeyboardShortcut::new(Modifiers::NONE, Key::F4),
+            ShortcutAction::ShowPresets => KeyboardShortcut::new(Modifiers::NONE, Key::F5),
+            ShortcutAction::ShowVisualizer => KeyboardShortcut::new(Modifiers::NONE, Key::F6),
+            ShortcutAction::ShowShows => KeyboardShortcut::new(Modifiers::NONE, Key::F7),
+        };
+        Some(shortcut)
+    }
+}
+
+/// Parse a chord like "Cmd+Shift+Z"; "Cmd" is Ctrl on Windows and Linux
+pub fn parse_shortcut(chord: &str) -> Option<KeyboardShortcut> {
+    let mut modifiers = Modifiers::NONE;
+    let mut key = None;
+    for part in chord.split('+').map(str::trim) {
+        match part {
+            "Cmd" | "Ctrl" => modifiers |= Modifiers::COMMAND,
+            "Alt" => modifiers |= Modifiers::ALT,
+            "Shift" => modifiers |= Modifiers::SHIFT,
+            name => key = Some(Key::from_name(name)?),
+        }
+    }
+    Some(KeyboardShortcut::new(modifiers, key?))
+}
+
+/// The inverse of `parse_shortcut`
+pub fn format_shortcut(shortcut: &KeyboardShortcut) -> String {
+    let mut parts = Vec::new();
+    if shortcut.modifiers.command || shortcut.modifiers.ctrl {
+        parts.push("Cmd");
+    }
+    if shortcut.modifiers.alt {
+        parts.push("Alt");
+    }
+    if shortcut.modifiers.shift {
+        parts.push("Shift");
+    }
+    parts.push(shortcut.logical_key.name());
+    parts.join("+")
+}
+
+/// The active shortcut for every action: the defaults with the user's overrides applied
+///
+/// Overrides are stored in `Settings::key_bindings` by action id, with an empty chord meaning
+/// the action is unbound.
+#[derive(Clone)]
+pub struct KeyBindings {
+    bindings: Vec<(ShortcutAction, Option<KeyboardShortcut>)>,
+}
+
+impl KeyBindings {
+    pub fn from_settings(overrides: &HashMap<String, String>) -> Self {
+        let bindings = ShortcutAction::all()
+            .into_iter()
+            .map(|action| {
+                let shortcut = match overrides.get(action.id()) {
+                    Some(chord) => parse_shortcut(chord),
+                    None => action.default_shortcut(),
+                };
+                (action, shortcut)
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    /// Only the bindings that differ from the defaults, for saving to settings
+    pub fn to_settings(&self) -> HashMap<String, String> {
+        self.bindings
+            .iter()
+            .filter(|(action, shortcut)| *shortcut != action.default_shortcut())
+            .map(|(action, shortcut)| {
+                (
+                    action.id().to_string(),
+                    shortcut.as_ref().map(format_shortcut).unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn get(&self, action: ShortcutAction) -> Option<KeyboardShortcut> {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .and_then(|(_, shortcut)| *shortcut)
+    }
+
+    pub fn set(&mut self, action: ShortcutAction, shortcut: Option<KeyboardShortcut>) {
+        // A chord can only do one thing, so take it away from any other action
+        if shortcut.is_some() {
+            for (_, existing) in self.bindings.iter_mut() {
+                if *existing == shortcut {
+                    *existing = None;
+                }
+            }
+        }
+        if let Some((_, existing)) = self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            *existing = shortcut;
+        }
+    }
+
+    /// Run the actions whose shortcuts were pressed this frame
+    pub fn handle_input(
+        &self,
+        ctx: &egui::Context,
+        active_tab: &mut ActiveTab,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        // Leave keys alone while typing into a text field
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        // Consume chords with more modifiers first so Shift+Space doesn't also fire Space
+        let mut bindings: Vec<_> = self
+            .bindings
+            .iter()
+            .filter_map(|(action, shortcut)| Some((*action, (*shortcut)?)))
+            .collect();
+        bindings.sort_by_key(|(_, shortcut)| {
+            std::cmp::Reverse(
+                [
+                    shortcut.modifiers.command,
+                    shortcut.modifiers.alt,
+                    shortcut.modifiers.shift,
+                ]
+                .iter()
+                .filter(|m| **m)
+                .count(),
+            )
+        });
+
+        for (action, shortcut) in bindings {
+            if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+                Self::run(action, active_tab, state, console_tx);
+            }
+        }
+    }
+
+    fn run(
+        action: ShortcutAction,
+        active_tab: &mut ActiveTab,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let command = match action {
+            ShortcutAction::Go => ConsoleCommand::Play,
+            ShortcutAction::GoBack => ConsoleCommand::PrevCue {
+                list_index: state.current_cue_list_index,
+            },
+            ShortcutAction::Stop => ConsoleCommand::Stop,
+            ShortcutAction::ClearProgrammer => ConsoleCommand::ClearProgrammerStaged,
+            ShortcutAction::UndoProgrammer => ConsoleCommand::UndoProgrammer,
+            ShortcutAction::RedoProgrammer => ConsoleCommand::RedoProgrammer,
+            ShortcutAction::ToggleHighlight => ConsoleCommand::SetProgrammerHighlight {
+                highlight: !state.programmer_highlight,
+                lowlight: state.programmer_lowlight,
+            },
+            ShortcutAction::NextFixture => ConsoleCommand::StepSelection { forward: true },
+            ShortcutAction::PreviousFixture => ConsoleCommand::StepSelection { forward: false },
+            ShortcutAction::CaptureOutput => ConsoleCommand::CaptureLiveOutput {
+                fixture_ids: state.selected_fixtures.clone(),
+            },
+            // The search palette takes its own shortcut first, even while typing
+            ShortcutAction::OpenSearch => return,
+            ShortcutAction::ShowDashboard
+            | ShortcutAction::ShowProgrammer
+            | ShortcutAction::ShowCueEditor
+            | ShortcutAction::ShowPatch
+            | ShortcutAction::ShowPresets
+            | ShortcutAction::ShowVisualizer
+            | ShortcutAction::ShowShows => {
+                *active_tab = match action {
+                    ShortcutAction::ShowProgrammer => ActiveTab::Programmer,
+                    ShortcutAction::ShowCueEditor => ActiveTab::CueEditor,
+                    ShortcutAction::ShowPatch => ActiveTab::PatchPanel,
+                    ShortcutAction::ShowPresets => ActiveTab::PresetPool,
+                    ShortcutAction::ShowVisualizer => ActiveTab::Previs,
+                    ShortcutAction::ShowShows => ActiveTab::ShowManager,
+                    _ => ActiveTab::Dashboard,
+                };
+                return;
+            }
+        };
+        let _ = console_tx.send(command);
+    }
+}
@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::SystemTime;
 
 use halo_core::audio::waveform::WaveformData;
 use halo_core::{
-    AudioDeviceInfo, ConsoleCommand, CueList, PlaybackState, RhythmState, Settings, Show, TimeCode,
+    AudioDeviceInfo, ColorSwatch, ConsoleCommand, CueList, FixtureGridLayout, FixtureGroup,
+    MagicSheet, MidiMappingAction, NetworkStatus, NextCuePreview, PerformanceMetrics,
+    PlaybackState, PresetLibrary, ProgrammerEffectSummary, QuickSelect, RhythmState, Settings,
+    Show, TimeCode,
 };
 use halo_fixtures::{Fixture, FixtureLibrary};
 use tokio::sync::mpsc;
@@ -12,6 +15,17 @@ use tokio::sync::mpsc;
 pub struct ConsoleState {
     pub fixtures: HashMap<String, Fixture>,
     pub cue_lists: Vec<CueList>,
+    pub presets: PresetLibrary,
+    pub fixture_groups: Vec<FixtureGroup>,
+    pub quick_selects: Vec<QuickSelect>,
+    pub color_swatches: Vec<ColorSwatch>,
+    pub fixture_grid: FixtureGridLayout,
+    pub magic_sheet: MagicSheet,
+    pub smoke_active: bool,
+    /// The action MIDI learn is waiting to map, if armed
+    pub midi_learn: Option<MidiMappingAction>,
+    pub network_status: NetworkStatus,
+    pub performance: PerformanceMetrics,
     pub current_cue_list_index: usize,
     pub current_cue_index: usize,
     pub current_cue_progress: f32,
@@ -21,24 +35,36 @@ pub struct ConsoleState {
     pub link_peers: u32,
     pub link_quantum: f64,
     pub link_tempo: f64,
+    pub link_phase: f64,
     pub link_start_stop_sync: bool,
     pub link_enabled: bool,
     pub rhythm_state: RhythmState,
     pub show: Option<Show>,
     pub timecode: Option<TimeCode>,
     pub programmer_preview_mode: bool,
+    pub programmer_highlight: bool,
+    pub programmer_next_undo: Option<String>,
+    pub programmer_next_redo: Option<String>,
+    pub show_dirty: bool,
+    pub selection_step: Option<(usize, usize)>,
+    pub programmer_lowlight: bool,
     pub selected_fixtures: Vec<usize>,
     pub programmer_values: HashMap<(usize, String), u8>, // (fixture_id, channel) -> value
-    pub programmer_effects: Vec<(String, halo_core::EffectType, Vec<usize>)>, /* (name, effect_type, fixture_ids) */
+    pub programmer_changed: HashSet<(usize, String)>,    // values that differ from tracking
+    pub programmer_effects: Vec<ProgrammerEffectSummary>,
     pub settings: Settings,
     pub audio_devices: Vec<AudioDeviceInfo>,
+    pub midi_profiles: Vec<String>,
     pub fixture_library: FixtureLibrary,
     pub active_effects_count: usize,
+    pub fx_rate_master: f64,
+    pub fx_size_master: f64,
     pub last_error: Option<String>,
     pub audio_waveform: Option<WaveformData>,
     pub audio_duration: Option<f64>,
     pub audio_bpm: Option<f64>,
     pub pixel_data: HashMap<usize, Vec<(u8, u8, u8)>>,
+    pub next_cue: Option<NextCuePreview>,
 }
 
 impl Default for ConsoleState {
@@ -46,6 +72,16 @@ impl Default for ConsoleState {
         Self {
             fixtures: HashMap::new(),
             cue_lists: Vec::new(),
+            presets: PresetLibrary::default(),
+            fixture_groups: Vec::new(),
+            quick_selects: Vec::new(),
+            color_swatches: Vec::new(),
+            fixture_grid: FixtureGridLayout::default(),
+            magic_sheet: MagicSheet::default(),
+            smoke_active: false,
+            midi_learn: None,
+            network_status: NetworkStatus::default(),
+            performance: PerformanceMetrics::default(),
             current_cue_list_index: 0,
             current_cue_index: 0,
             current_cue_progress: 0.0,
@@ -55,9 +91,11 @@ impl Default for ConsoleState {
             link_peers: 0,
             link_quantum: 4.0,
             link_tempo: 120.0,
+            link_phase: 0.0,
             link_start_stop_sync: false,
             link_enabled: false,
             rhythm_state: RhythmState {
+                beat_time: 0.0,
                 beat_phase: 0.0,
                 bar_phase: 0.0,
                 phrase_phase: 0.0,
@@ -69,18 +107,29 @@ impl Default for ConsoleState {
             show: None,
             timecode: None,
             programmer_preview_mode: false,
+            programmer_highlight: false,
+            programmer_next_undo: None,
+            programmer_next_redo: None,
+            show_dirty: false,
+            selection_step: None,
+            programmer_lowlight: false,
             selected_fixtures: Vec::new(),
             programmer_values: HashMap::new(),
+            programmer_changed: HashSet::new(),
             programmer_effects: Vec::new(),
             settings: Settings::default(),
             audio_devices: Vec::new(),
+            midi_profiles: Vec::new(),
             fixture_library: FixtureLibrary::new(),
             active_effects_count: 0,
+            fx_rate_master: 1.0,
+            fx_size_master: 1.0,
             last_error: None,
             audio_waveform: None,
             audio_duration: None,
             audio_bpm: None,
             pixel_data: HashMap::new(),
+            next_cue: None,
         }
     }
 }
@@ -97,6 +146,39 @@ impl ConsoleState {
             halo_core::ConsoleEvent::CueListsUpdated { cue_lists } => {
                 self.cue_lists = cue_lists;
             }
+            halo_core::ConsoleEvent::PresetsUpdated { presets } => {
+                self.presets = presets;
+            }
+            halo_core::ConsoleEvent::FixtureGroupsUpdated { groups } => {
+                self.fixture_groups = groups;
+            }
+            halo_core::ConsoleEvent::QuickSelectsUpdated { quick_selects } => {
+                self.quick_selects = quick_selects;
+            }
+            halo_core::ConsoleEvent::ColorSwatchesUpdated { swatches } => {
+                self.color_swatches = swatches;
+            }
+            halo_core::ConsoleEvent::FixtureGridLayoutUpdated { layout } => {
+                self.fixture_grid = layout;
+            }
+            halo_core::ConsoleEvent::MagicSheetUpdated { sheet } => {
+                self.magic_sheet = sheet;
+            }
+            halo_core::ConsoleEvent::SmokeChanged { active } => {
+                self.smoke_active = active;
+            }
+            halo_core::ConsoleEvent::MidiLearnChanged { action } => {
+                self.midi_learn = action;
+            }
+            halo_core::ConsoleEvent::MidiProfilesList { profiles } => {
+                self.midi_profiles = profiles;
+            }
+            halo_core::ConsoleEvent::NetworkStatusUpdated { status } => {
+                self.network_status = status;
+            }
+            halo_core::ConsoleEvent::PerformanceMetricsUpdated { metrics } => {
+                self.performance = metrics;
+            }
             halo_core::ConsoleEvent::CueListSelected { list_index } => {
                 self.current_cue_list_index = list_index;
             }
@@ -107,6 +189,9 @@ impl ConsoleState {
                 self.current_cue_index = cue_index;
                 self.current_cue_progress = progress;
             }
+            halo_core::ConsoleEvent::NextCuePreviewUpdated { preview } => {
+                self.next_cue = preview;
+            }
             halo_core::ConsoleEvent::PlaybackStateChanged { state } => {
                 self.playback_state = state;
             }
@@ -120,6 +205,17 @@ impl ConsoleState {
                 self.link_peers = num_peers as u32;
                 self.link_enabled = enabled;
             }
+            halo_core::ConsoleEvent::LinkSessionUpdated {
+                tempo,
+                phase,
+                quantum,
+                start_stop_sync,
+            } => {
+                self.link_tempo = tempo;
+                self.link_phase = phase;
+                self.link_quantum = quantum;
+                self.link_start_stop_sync = start_stop_sync;
+            }
             halo_core::ConsoleEvent::FixturePatched {
                 fixture_id,
                 fixture,
@@ -151,6 +247,12 @@ impl ConsoleState {
                         .insert(fixture.id.to_string(), fixture.clone());
                 }
                 self.cue_lists = show.cue_lists.clone();
+                self.presets = show.presets.clone();
+                self.fixture_groups = show.fixture_groups.clone();
+                self.quick_selects = show.quick_selects.clone();
+                self.color_swatches = show.color_swatches.clone();
+                self.fixture_grid = show.fixture_grid.clone();
+                self.magic_sheet = show.magic_sheet.clone();
                 self.current_cue_list_index = 0; // Reset to first cue list when show is loaded
                 self.show = Some(show);
             }
@@ -163,6 +265,28 @@ impl ConsoleState {
             } => {
                 self.programmer_preview_mode = preview_mode;
                 self.selected_fixtures = selected_fixtures;
+                // Any selection change ends stepping; a step sends its position right after
+                self.selection_step = None;
+            }
+            halo_core::ConsoleEvent::SelectionStepChanged { step } => {
+                self.selection_step = step;
+            }
+            halo_core::ConsoleEvent::ShowDirtyChanged { dirty } => {
+                self.show_dirty = dirty;
+            }
+            halo_core::ConsoleEvent::ProgrammerUndoChanged {
+                next_undo,
+                next_redo,
+            } => {
+                self.programmer_next_undo = next_undo;
+                self.programmer_next_redo = next_redo;
+            }
+            halo_core::ConsoleEvent::ProgrammerHighlightChanged {
+                highlight,
+                lowlight,
+            } => {
+                self.programmer_highlight = highlight;
+                self.programmer_lowlight = lowlight;
             }
             halo_core::ConsoleEvent::ProgrammerValuesUpdated { values } => {
                 self.programmer_values.clear();
@@ -225,6 +349,13 @@ impl ConsoleState {
             } => {
                 self.active_effects_count = active_effect_count;
             }
+            halo_core::ConsoleEvent::ProgrammerChangedValuesUpdated { changed } => {
+                self.programmer_changed = changed.into_iter().collect();
+            }
+            halo_core::ConsoleEvent::FxMastersChanged { rate, size } => {
+                self.fx_rate_master = rate;
+                self.fx_size_master = size;
+            }
             halo_core::ConsoleEvent::Error { message } => {
                 self.last_error = Some(message);
             }
@@ -1,17 +1,32 @@
-use eframe::egui::{Align2, Color32, FontId, Painter, Rect, Stroke};
+use eframe::egui::{self, Align2, Color32, FontId, Painter, Rect, Sense, Stroke, Vec2};
 use halo_core::{ConsoleCommand, TimeCode};
 use tokio::sync::mpsc;
 
 use crate::state::ConsoleState;
 
+// Height of the grab handle at the top of each cue marker
+const MARKER_HANDLE_HEIGHT: f32 = 14.0;
+// Smallest scrub movement that sends another seek, so dragging doesn't flood the audio module
+const MIN_SEEK_STEP_SECONDS: f64 = 0.05;
+
 #[derive(Debug, Clone)]
 pub struct TimelineState {
     pub is_expanded: bool,
+    // Cue being dragged and where it would land, in seconds
+    dragging_cue: Option<(usize, f64)>,
+    // Playhead position while scrubbing the waveform, and the last position seeked to
+    scrub_seconds: Option<f64>,
+    last_seek_seconds: Option<f64>,
 }
 
 impl Default for TimelineState {
     fn default() -> Self {
-        Self { is_expanded: false }
+        Self {
+            is_expanded: false,
+            dragging_cue: None,
+            scrub_seconds: None,
+            last_seek_seconds: None,
+        }
     }
 }
 
@@ -48,16 +63,26 @@ pub fn render(
                 ui.available_rect_before_wrap().min,
                 [ui.available_width(), timeline_height].into(),
             ),
-            eframe::egui::Sense::click(),
+            eframe::egui::Sense::click_and_drag(),
         );
 
         // Draw timeline content
         if let Some(waveform_data) = &state.audio_waveform {
-            draw_timeline_content(
+            let playhead_seconds = draw_timeline_content(
                 &timeline_response,
                 ui.painter(),
                 waveform_data,
                 state,
+                timeline_state,
+                console_tx,
+            );
+            drag_cue_markers(
+                ui,
+                timeline_response.rect,
+                state,
+                timeline_state,
+                waveform_data,
+                playhead_seconds,
                 console_tx,
             );
         } else {
@@ -73,37 +98,55 @@ pub fn render(
     }
 }
 
+/// Draw the waveform and playhead, returning the playhead position in seconds
+///
+/// Clicking seeks straight to a point, dragging scrubs with the playhead following the pointer.
 fn draw_timeline_content(
     response: &eframe::egui::Response,
     painter: &Painter,
     waveform_data: &halo_core::audio::waveform::WaveformData,
     state: &ConsoleState,
+    timeline_state: &mut TimelineState,
     console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
-) {
+) -> Option<f64> {
     let rect = response.rect;
     let width = rect.width();
 
-    // Handle click for needle drop
-    if response.clicked() {
+    if response.clicked() || response.dragged() || response.drag_stopped() {
         if let Some(pos) = response.interact_pointer_pos() {
             let click_x = pos.x - rect.min.x;
             let time_ratio = (click_x / width).clamp(0.0, 1.0);
             let seek_time = time_ratio as f64 * waveform_data.duration_seconds;
-            let _ = console_tx.send(ConsoleCommand::SeekAudio {
-                position_seconds: seek_time,
-            });
+
+            // Always seek on release so the audio ends up exactly where the pointer stopped
+            let moved_enough = timeline_state
+                .last_seek_seconds
+                .is_none_or(|last| (last - seek_time).abs() >= MIN_SEEK_STEP_SECONDS);
+            if !response.dragged() || moved_enough {
+                let _ = console_tx.send(ConsoleCommand::SeekAudio {
+                    position_seconds: seek_time,
+                });
+                timeline_state.last_seek_seconds = Some(seek_time);
+            }
+            timeline_state.scrub_seconds = response.dragged().then_some(seek_time);
         }
     }
+    if !response.dragged() {
+        timeline_state.scrub_seconds = None;
+        timeline_state.last_seek_seconds = None;
+    }
 
     // Draw waveform
     draw_waveform(painter, rect, waveform_data);
 
-    // Draw cue markers
-    draw_cue_markers(painter, rect, state, waveform_data);
-
-    // Draw playback position indicator
-    if let Some(timecode) = &state.timecode {
-        let current_time = timecode.to_seconds();
+    // Draw playback position indicator, following the pointer while scrubbing
+    let playhead_seconds = timeline_state.scrub_seconds.or_else(|| {
+        state
+            .timecode
+            .as_ref()
+            .map(|timecode| timecode.to_seconds())
+    });
+    if let Some(current_time) = playhead_seconds {
         let time_ratio = (current_time / waveform_data.duration_seconds).clamp(0.0, 1.0);
         let position_x = rect.min.x + (time_ratio * width as f64) as f32;
 
@@ -115,6 +158,7 @@ fn draw_timeline_content(
             Stroke::new(2.0, Color32::from_rgb(255, 100, 50)),
         );
     }
+    playhead_seconds
 }
 
 fn draw_waveform(
@@ -168,8 +212,8 @@ fn draw_waveform(
     }
 }
 
-/// Extract timecoded cues from the current cue list
-fn get_timecoded_cues(state: &ConsoleState) -> Vec<(usize, String, f64)> {
+/// Extract timecoded cues from the current cue list, with their times and frame rates
+fn get_timecoded_cues(state: &ConsoleState) -> Vec<(usize, String, f64, u8)> {
     let mut timecoded_cues = Vec::new();
 
     if let Some(cue_list) = state.cue_lists.get(state.current_cue_list_index) {
@@ -178,7 +222,7 @@ fn get_timecoded_cues(state: &ConsoleState) -> Vec<(usize, String, f64)> {
                 let mut timecode = TimeCode::default();
                 if timecode.from_string(timecode_str).is_ok() {
                     let seconds = timecode.to_seconds();
-                    timecoded_cues.push((index, cue.name.clone(), seconds));
+                    timecoded_cues.push((index, cue.name.clone(), seconds, timecode.frame_rate));
                 }
             }
         }
@@ -189,43 +233,133 @@ fn get_timecoded_cues(state: &ConsoleState) -> Vec<(usize, String, f64)> {
     timecoded_cues
 }
 
-/// Draw cue markers and labels on the timeline
-fn draw_cue_markers(
-    painter: &Painter,
+/// Draw cue markers and labels on the timeline, dragging a marker's handle retimes the cue
+///
+/// Markers the playhead has already passed are dimmed.
+fn drag_cue_markers(
+    ui: &mut egui::Ui,
     rect: Rect,
     state: &ConsoleState,
+    timeline_state: &mut TimelineState,
     waveform_data: &halo_core::audio::waveform::WaveformData,
+    playhead_seconds: Option<f64>,
+    console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
 ) {
-    let timecoded_cues = get_timecoded_cues(state);
+    let duration = waveform_data.duration_seconds;
+    if duration <= 0.0 {
+        return;
+    }
+    let seconds_to_x =
+        |seconds: f64| rect.min.x + (seconds / duration * rect.width() as f64) as f32;
 
-    for (cue_index, cue_name, cue_seconds) in timecoded_cues {
+    for (cue_index, cue_name, cue_seconds, frame_rate) in get_timecoded_cues(state) {
         // Only draw cues that are within the audio duration
-        if cue_seconds > waveform_data.duration_seconds {
+        if cue_seconds > duration {
             continue;
         }
 
-        let time_ratio = (cue_seconds / waveform_data.duration_seconds).clamp(0.0, 1.0);
-        let position_x = rect.min.x + (time_ratio * rect.width() as f64) as f32;
+        let dragged_seconds = match timeline_state.dragging_cue {
+            Some((index, seconds)) if index == cue_index => Some(seconds),
+            _ => None,
+        };
+        let position_x = seconds_to_x(dragged_seconds.unwrap_or(cue_seconds));
+
+        let handle_rect = Rect::from_center_size(
+            egui::pos2(position_x, rect.min.y + MARKER_HANDLE_HEIGHT / 2.0),
+            Vec2::new(10.0, MARKER_HANDLE_HEIGHT),
+        );
+        let response = ui
+            .interact(
+                handle_rect,
+                ui.id().with(("cue_marker", cue_index)),
+                Sense::drag(),
+            )
+            .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+
+        if response.dragged() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let ratio = ((pos.x - rect.min.x) / rect.width()).clamp(0.0, 1.0) as f64;
+                timeline_state.dragging_cue = Some((cue_index, ratio * duration));
+            }
+        }
+        if response.drag_stopped() {
+            if let Some(seconds) = dragged_seconds {
+                retime_cue(state, cue_index, seconds, frame_rate, console_tx);
+            }
+            timeline_state.dragging_cue = None;
+        }
+
+        let passed = playhead_seconds.is_some_and(|playhead| cue_seconds <= playhead);
+        let color = if dragged_seconds.is_some() || response.hovered() {
+            Color32::WHITE
+        } else if passed {
+            Color32::from_rgb(140, 140, 70)
+        } else {
+            Color32::from_rgb(255, 255, 100)
+        };
+        let painter = ui.painter();
 
-        // Draw thin vertical marker line
+        // Draw thin vertical marker line with a grab handle at the top
         painter.line_segment(
             [
-                eframe::egui::pos2(position_x, rect.min.y),
-                eframe::egui::pos2(position_x, rect.max.y),
+                egui::pos2(position_x, rect.min.y),
+                egui::pos2(position_x, rect.max.y),
             ],
-            Stroke::new(1.0, Color32::from_rgb(255, 255, 100)),
+            Stroke::new(1.0, color),
         );
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                handle_rect.left_top(),
+                handle_rect.right_top(),
+                egui::pos2(position_x, handle_rect.max.y),
+            ],
+            color,
+            Stroke::NONE,
+        ));
 
-        // Draw cue label above the marker
-        let label_text = format!("Cue {}: {}", cue_index + 1, cue_name);
-        let label_pos = eframe::egui::pos2(position_x, rect.min.y - 5.0);
+        // Draw cue label above the marker, with the new time while dragging
+        let label_text = match dragged_seconds {
+            Some(seconds) => format!(
+                "Cue {}: {}",
+                cue_index + 1,
+                TimeCode::from_seconds(seconds, frame_rate).to_string()
+            ),
+            None => format!("Cue {}: {}", cue_index + 1, cue_name),
+        };
+        let label_pos = egui::pos2(position_x, rect.min.y - 5.0);
 
         painter.text(
             label_pos,
             Align2::CENTER_BOTTOM,
             label_text,
             FontId::proportional(10.0),
-            Color32::from_rgb(255, 255, 100),
+            color,
         );
     }
 }
+
+/// Send the cue's other fields unchanged along with the new timecode, at the cue's frame rate
+fn retime_cue(
+    state: &ConsoleState,
+    cue_index: usize,
+    seconds: f64,
+    frame_rate: u8,
+    console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+) {
+    let Some(cue) = state
+        .cue_lists
+        .get(state.current_cue_list_index)
+        .and_then(|list| list.cues.get(cue_index))
+    else {
+        return;
+    };
+    let timecode = TimeCode::from_seconds(seconds, frame_rate);
+    let _ = console_tx.send(ConsoleCommand::UpdateCue {
+        list_index: state.current_cue_list_index,
+        cue_index,
+        name: cue.name.clone(),
+        fade_time: cue.fade_time.as_secs_f64(),
+        timecode: Some(timecode.to_string()),
+        is_blocking: cue.is_blocking,
+    });
+}
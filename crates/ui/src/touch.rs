@@ -0,0 +1,151 @@
+use eframe::egui::{self, Color32, RichText, Vec2};
+use halo_core::{ConsoleCommand, PlaybackState};
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+
+pub const GO_BAR_HEIGHT: f32 = 72.0;
+const CUE_ROW_HEIGHT: f32 = 52.0;
+
+/// Bigger targets and text for fingers, or back to the egui defaults
+pub fn apply_style(ctx: &egui::Context, touch_layout: bool) {
+    let defaults = egui::Style::default();
+    ctx.all_styles_mut(|style| {
+        style.spacing = defaults.spacing.clone();
+        style.text_styles = defaults.text_styles.clone();
+        if touch_layout {
+            style.spacing.interact_size = Vec2::new(56.0, 40.0);
+            style.spacing.button_padding = Vec2::new(14.0, 10.0);
+            style.spacing.item_spacing = Vec2::new(10.0, 10.0);
+            style.spacing.slider_width = 220.0;
+            style.spacing.icon_width = 24.0;
+            style.spacing.scroll.bar_width = 16.0;
+            for font in style.text_styles.values_mut() {
+                font.size *= 1.3;
+            }
+        }
+    });
+}
+
+/// Large Go/Back/Pause/Stop buttons along the bottom of the screen
+pub fn render_go_bar(
+    ui: &mut egui::Ui,
+    state: &ConsoleState,
+    console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+) {
+    let list_index = state.current_cue_list_index;
+    let cues = state
+        .cue_lists
+        .get(list_index)
+        .map(|list| list.cues.as_slice())
+        .unwrap_or_default();
+
+    ui.horizontal_centered(|ui| {
+        let button = |text: &str, color: Color32| {
+            egui::Button::new(
+                RichText::new(text)
+                    .size(24.0)
+                    .strong()
+                    .color(Color32::WHITE),
+            )
+            .fill(color)
+            .min_size(Vec2::new(140.0, GO_BAR_HEIGHT - 12.0))
+        };
+
+        if ui.add(button("BACK", Color32::from_gray(70))).clicked() {
+            let _ = console_tx.send(ConsoleCommand::PrevCue { list_index });
+        }
+
+        let (pause_text, pause_command) = match state.playback_state {
+            PlaybackState::Holding => ("RESUME", ConsoleCommand::Resume),
+            _ => ("PAUSE", ConsoleCommand::Pause),
+        };
+        if ui
+            .add_enabled(
+                state.playback_state != PlaybackState::Stopped,
+                button(pause_text, Color32::from_rgb(180, 130, 0)),
+            )
+            .clicked()
+        {
+            let _ = console_tx.send(pause_command);
+        }
+
+        if ui
+            .add(button("STOP", Color32::from_rgb(180, 40, 40)))
+            .clicked()
+        {
+            let _ = console_tx.send(ConsoleCommand::Stop);
+        }
+
+        // Current and next cue, so the operator knows what GO will do
+        ui.vertical(|ui| {
+            let current = cues.get(state.current_cue_index).map(|c| c.name.as_str());
+            let next = cues
+                .get(state.current_cue_index + 1)
+                .map(|c| c.name.as_str());
+            ui.label(RichText::new(format!("Current: {}", current.unwrap_or("-"))).size(18.0));
+            ui.label(
+                RichText::new(format!("Next: {}", next.unwrap_or("-")))
+                    .size(18.0)
+                    .weak(),
+            );
+        });
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let go = egui::Button::new(
+                RichText::new("GO")
+                    .size(32.0)
+                    .strong()
+                    .color(Color32::WHITE),
+            )
+            .fill(Color32::from_rgb(30, 150, 60))
+            .min_size(Vec2::new(220.0, GO_BAR_HEIGHT - 12.0));
+            if ui.add(go).clicked() {
+                let _ = console_tx.send(ConsoleCommand::Play);
+            }
+        });
+    });
+}
+
+/// Cue list with one large row per cue, tapping a row jumps to that cue
+pub fn render_cue_list(
+    ui: &mut egui::Ui,
+    state: &ConsoleState,
+    console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+) {
+    let list_index = state.current_cue_list_index;
+    let Some(cue_list) = state.cue_lists.get(list_index) else {
+        ui.label("No cue lists");
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        if ui.button("←").clicked() {
+            let _ = console_tx.send(ConsoleCommand::SelectPreviousCueList);
+        }
+        ui.heading(&cue_list.name);
+        if ui.button("→").clicked() {
+            let _ = console_tx.send(ConsoleCommand::SelectNextCueList);
+        }
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (cue_index, cue) in cue_list.cues.iter().enumerate() {
+            let is_current = cue_index == state.current_cue_index;
+            let mut text = RichText::new(format!("{}  {}", cue_index + 1, cue.name)).size(20.0);
+            if is_current {
+                text = text.strong();
+            }
+            let row = egui::Button::new(text)
+                .selected(is_current)
+                .min_size(Vec2::new(ui.available_width(), CUE_ROW_HEIGHT));
+            if ui.add(row).clicked() {
+                let _ = console_tx.send(ConsoleCommand::GoToCue {
+                    list_index,
+                    cue_index,
+                });
+            }
+        }
+    });
+}
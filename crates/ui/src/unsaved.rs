@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+use halo_core::ConsoleCommand;
+use tokio::sync::mpsc;
+
+use crate::state::ConsoleState;
+
+/// Something that would throw away unsaved show edits
+#[derive(Clone)]
+pub enum ShowAction {
+    New { name: String },
+    Load { path: PathBuf },
+    Reload,
+    Quit,
+}
+
+impl ShowAction {
+    fn label(&self) -> &'static str {
+        match self {
+            ShowAction::New { .. } => "creating a new show",
+            ShowAction::Load { .. } => "opening another show",
+            ShowAction::Reload => "reloading the show",
+            ShowAction::Quit => "quitting",
+        }
+    }
+}
+
+/// Asks to save or discard unsaved edits before the show is replaced or the app quits
+#[derive(Default)]
+pub struct UnsavedChanges {
+    // Waiting for the user to choose save, discard or cancel
+    prompt: Option<ShowAction>,
+    // Waiting for the save to finish before carrying on
+    after_save: Option<ShowAction>,
+    // Quit has been confirmed, let the window close
+    closing: bool,
+}
+
+impl UnsavedChanges {
+    /// Run the action now, or prompt first if the show has unsaved edits
+    pub fn request(
+        &mut self,
+        action: ShowAction,
+        ctx: &egui::Context,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        if state.show_dirty {
+            self.prompt = Some(action);
+        } else {
+            self.perform(action, ctx, console_tx);
+        }
+    }
+
+    /// Intercept the window closing while there are unsaved edits
+    pub fn handle_close_request(&mut self, ctx: &egui::Context, state: &ConsoleState) {
+        if ctx.input(|i| i.viewport().close_requested()) && state.show_dirty && !self.closing {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.prompt = Some(ShowAction::Quit);
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        state: &ConsoleState,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        // Carry on once the save has gone through, or give up if it failed
+        if self.after_save.is_some() {
+            if state.last_error.is_some() {
+                self.after_save = None;
+            } else if !state.show_dirty {
+                if let Some(action) = self.after_save.take() {
+                    self.perform(action, ctx, console_tx);
+                }
+            }
+        }
+
+        let Some(action) = self.prompt.clone() else {
+            return;
+        };
+
+        let mut choice = None;
+        egui::Modal::new(egui::Id::new("unsaved_changes")).show(ctx, |ui| {
+            ui.set_width(360.0);
+            ui.heading("Unsaved Changes");
+            ui.add_space(8.0);
+            let show_name = state
+                .show
+                .as_ref()
+                .map(|show| show.name.as_str())
+                .unwrap_or("The show");
+            ui.label(format!(
+                "{show_name} has changes that haven't been saved. Save them before {}?",
+                action.label()
+            ));
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                if ui.button("Save").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Discard").clicked() {
+                    choice = Some(false);
+                }
+                if ui.button("Cancel").clicked() {
+                    self.prompt = None;
+                }
+            });
+        });
+
+        match choice {
+            Some(true) => {
+                self.prompt = None;
+                let _ = console_tx.send(ConsoleCommand::SaveShow);
+                self.after_save = Some(action);
+            }
+            Some(false) => {
+                self.prompt = None;
+                self.perform(action, ctx, console_tx);
+            }
+            None => {}
+        }
+    }
+
+    fn perform(
+        &mut self,
+        action: ShowAction,
+        ctx: &egui::Context,
+        console_tx: &mpsc::UnboundedSender<ConsoleCommand>,
+    ) {
+        let command = match action {
+            ShowAction::New { name } => ConsoleCommand::NewShow { name },
+            ShowAction::Load { path } => ConsoleCommand::LoadShow { path },
+            ShowAction::Reload => ConsoleCommand::ReloadShow,
+            ShowAction::Quit => {
+                self.closing = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                return;
+            }
+        };
+        let _ = console_tx.send(command);
+    }
+}
@@ -1,27 +1,107 @@
-use eframe::egui::Color32;
+use eframe::egui::{self, Color32, Stroke};
+use halo_core::{Settings, UiTheme};
 
 pub struct Theme {
+    pub mode: UiTheme,
     pub bg_color: Color32,
-    pub _panel_bg: Color32,
+    pub panel_bg: Color32,
     pub _element_bg: Color32,
     pub _text_color: Color32,
     pub text_dim: Color32,
     pub _border_color: Color32,
-    pub _highlight_color: Color32,
+    pub accent_color: Color32,
     pub _active_color: Color32,
 }
 
 impl Default for Theme {
     fn default() -> Self {
         Self {
+            mode: UiTheme::Dark,
             bg_color: Color32::from_rgb(0, 0, 0),
-            _panel_bg: Color32::from_rgb(16, 16, 16),
+            panel_bg: Color32::from_rgb(20, 20, 20),
             _element_bg: Color32::from_rgb(32, 32, 32),
             _text_color: Color32::from_rgb(255, 255, 255),
             text_dim: Color32::from_rgb(156, 163, 175),
             _border_color: Color32::from_rgb(55, 65, 81),
-            _highlight_color: Color32::from_rgb(59, 130, 246),
+            accent_color: Color32::from_rgb(59, 130, 246),
             _active_color: Color32::from_rgb(30, 64, 175),
         }
     }
 }
+
+impl Theme {
+    pub fn from_settings(settings: &Settings) -> Self {
+        let [r, g, b] = settings.accent_color;
+        let accent_color = Color32::from_rgb(r, g, b);
+        let dark = Self {
+            accent_color,
+            _active_color: accent_color.gamma_multiply(0.6),
+            ..Self::default()
+        };
+
+        match settings.theme {
+            UiTheme::Dark => dark,
+            UiTheme::Light => Self {
+                mode: UiTheme::Light,
+                bg_color: Color32::from_rgb(235, 235, 235),
+                panel_bg: Color32::from_rgb(225, 225, 225),
+                _element_bg: Color32::from_rgb(210, 210, 210),
+                _text_color: Color32::from_rgb(20, 20, 20),
+                text_dim: Color32::from_rgb(75, 85, 99),
+                _border_color: Color32::from_rgb(160, 160, 160),
+                ..dark
+            },
+            // Pure black and white with heavy outlines, readable in direct sunlight
+            UiTheme::HighContrast => Self {
+                mode: UiTheme::HighContrast,
+                bg_color: Color32::BLACK,
+                panel_bg: Color32::BLACK,
+                _element_bg: Color32::from_rgb(25, 25, 25),
+                _text_color: Color32::WHITE,
+                text_dim: Color32::from_rgb(220, 220, 220),
+                _border_color: Color32::WHITE,
+                ..dark
+            },
+        }
+    }
+
+    /// egui visuals for the whole app, so standard widgets follow the theme
+    pub fn visuals(&self) -> egui::Visuals {
+        let mut visuals = match self.mode {
+            UiTheme::Light => egui::Visuals::light(),
+            UiTheme::Dark | UiTheme::HighContrast => egui::Visuals::dark(),
+        };
+
+        if self.mode == UiTheme::HighContrast {
+            visuals.override_text_color = Some(Color32::WHITE);
+            visuals.panel_fill = Color32::BLACK;
+            visuals.window_fill = Color32::BLACK;
+            visuals.extreme_bg_color = Color32::BLACK;
+            visuals.faint_bg_color = Color32::from_rgb(25, 25, 25);
+            visuals.window_stroke = Stroke::new(2.0, Color32::WHITE);
+            visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.0, Color32::from_gray(200));
+            visuals.widgets.inactive.bg_stroke = Stroke::new(1.5, Color32::WHITE);
+            visuals.widgets.inactive.weak_bg_fill = Color32::from_rgb(25, 25, 25);
+            visuals.widgets.inactive.bg_fill = Color32::from_rgb(25, 25, 25);
+        }
+
+        // Text on the accent needs to stay readable whatever color was picked
+        let luminance = 0.299 * self.accent_color.r() as f32
+            + 0.587 * self.accent_color.g() as f32
+            + 0.114 * self.accent_color.b() as f32;
+        let on_accent = if luminance > 150.0 {
+            Color32::BLACK
+        } else {
+            Color32::WHITE
+        };
+
+        visuals.selection.bg_fill = self.accent_color;
+        visuals.selection.stroke = Stroke::new(1.0, on_accent);
+        visuals.hyperlink_color = self.accent_color;
+        visuals.widgets.hovered.bg_stroke = Stroke::new(1.5, self.accent_color);
+        visuals.widgets.active.bg_fill = self.accent_color;
+        visuals.widgets.active.weak_bg_fill = self.accent_color;
+        visuals.widgets.active.fg_stroke = Stroke::new(1.5, on_accent);
+        visuals
+    }
+}
@@ -1,8 +1,9 @@
-use eframe::egui::{self, Color32, Pos2, Rect, Vec2};
+use eframe::egui::{self, Color32, Vec2};
 use halo_core::ConsoleCommand;
 use halo_fixtures::FixtureType;
 use tokio::sync::mpsc;
 
+use crate::pixel_strip;
 use crate::state::ConsoleState;
 
 pub fn render(
@@ -69,44 +70,7 @@ fn render_fixture_pixels(
 
         // Get pixel data for this fixture
         if let Some(pixels) = pixel_data.get(&fixture.id) {
-            // Calculate pixel size to fit within available width
-            let available_width = 230.0; // Slightly less than panel width for padding
-            let pixel_count = pixels.len();
-            let pixel_width = if pixel_count > 0 {
-                (available_width / pixel_count as f32).min(10.0)
-            } else {
-                5.0
-            };
-            let pixel_height = 15.0;
-
-            // Draw the pixel bar
-            let (response, painter) = ui.allocate_painter(
-                Vec2::new(available_width, pixel_height),
-                egui::Sense::hover(),
-            );
-
-            let rect = response.rect;
-            let start_x = rect.min.x;
-            let y = rect.min.y;
-
-            for (i, (r, g, b)) in pixels.iter().enumerate() {
-                let x = start_x + (i as f32 * pixel_width);
-                let pixel_rect =
-                    Rect::from_min_size(Pos2::new(x, y), Vec2::new(pixel_width, pixel_height));
-
-                // Draw the pixel with its RGB color
-                painter.rect_filled(pixel_rect, 0.0, Color32::from_rgb(*r, *g, *b));
-
-                // Draw a subtle border between pixels
-                if pixel_width > 2.0 {
-                    painter.rect_stroke(
-                        pixel_rect,
-                        0.0,
-                        egui::Stroke::new(0.5, Color32::from_gray(40)),
-                        egui::StrokeKind::Middle,
-                    );
-                }
-            }
+            pixel_strip::show(ui, pixels, 15.0);
         } else {
             // No data available for this fixture
             ui.label(